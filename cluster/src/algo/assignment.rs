@@ -0,0 +1,218 @@
+//! Weighted assignment between the two sides of a bipartite Cluster.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::{Weight, WeightedCluster, WeightedNode};
+
+/// Assignment of minimum total cost, pairing nodes of one side to distinct nodes of the other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment<K, W> {
+    /// The (left, right) pairs of the assignment, in the order of the left side.
+    pub pairs: Vec<(K, K)>,
+    /// The sum of the costs of the pairs.
+    pub cost: W,
+}
+
+/// Compute an assignment of minimum cost between a set of nodes and the other nodes of a Cluster with the Hungarian (Kuhn–Munkres) algorithm,
+/// the weight of an edge being the cost of pairing its ends. Every node of the smaller side is assigned, only the pairs linked by an edge being allowed.
+/// The Cluster is seen as undirected : when both directions of a link exist, the cheaper one is used.
+/// Edges between two nodes of the same side and edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The weighted Cluster holding the costs.
+/// - left - The keys of the nodes of the left side, the right side being made of the other nodes of the Cluster.
+/// # Return
+/// An Option containing the Assignment, None if the nodes of the smaller side cannot all be assigned.
+pub fn hungarian<K, N, C>(cluster: &C, left: &HashSet<K>) -> Option<Assignment<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let (rows, columns): (Vec<K>, Vec<K>) = cluster.keys().cloned().partition(|k| left.contains(k));
+    let row_index: HashMap<&K, usize> = rows.iter().enumerate().map(|(i, k)| (k, i)).collect();
+    let column_index: HashMap<&K, usize> =
+        columns.iter().enumerate().map(|(j, k)| (k, j)).collect();
+    let mut costs: Vec<Vec<Option<N::Weight>>> = vec![vec![None; columns.len()]; rows.len()];
+    for src in cluster.keys() {
+        for (dst, weight) in cluster.weighted_adj(src).into_iter().flatten() {
+            let (i, j) = match (row_index.get(src), column_index.get(&dst)) {
+                (Some(i), Some(j)) => (*i, *j),
+                _ => match (row_index.get(&dst), column_index.get(src)) {
+                    (Some(i), Some(j)) => (*i, *j),
+                    _ => continue,
+                },
+            };
+            if costs[i][j].is_none_or(|cost| weight < cost) {
+                costs[i][j] = Some(weight);
+            }
+        }
+    }
+    let columns_of = assign(&costs, rows.len(), columns.len())?;
+    let mut cost = N::Weight::zero();
+    let mut pairs = Vec::with_capacity(columns_of.len());
+    for (i, j) in columns_of {
+        cost = cost + costs[i][j]?;
+        pairs.push((rows[i].clone(), columns[j].clone()));
+    }
+    Some(Assignment { pairs, cost })
+}
+
+/// Compute an assignment of minimum cost over a cost matrix with the Hungarian (Kuhn–Munkres) algorithm.
+/// Every row is assigned to a distinct column if there are at least as many columns as rows, every column to a distinct row otherwise.
+/// # Parameter
+/// - costs - The cost of assigning each row to each column.
+/// # Return
+/// An Option containing the Assignment of the (row, column) pairs, None if the rows do not all have the same length.
+pub fn hungarian_matrix<W: Weight>(costs: &[Vec<W>]) -> Option<Assignment<usize, W>> {
+    let columns = costs.first().map_or(0, |row| row.len());
+    if costs.iter().any(|row| row.len() != columns) {
+        return None;
+    }
+    let allowed: Vec<Vec<Option<W>>> = costs
+        .iter()
+        .map(|row| row.iter().copied().map(Some).collect())
+        .collect();
+    let pairs = assign(&allowed, costs.len(), columns)?;
+    let cost = pairs
+        .iter()
+        .fold(W::zero(), |total, (i, j)| total + costs[*i][*j]);
+    Some(Assignment { pairs, cost })
+}
+
+/// Assign the rows to the columns of a rectangular matrix, or the columns to the rows if they are fewer, None meaning a forbidden pair.
+/// # Return
+/// An Option containing the (row, column) pairs sorted by row, None if the smaller side cannot be fully assigned.
+fn assign<W: Weight>(
+    costs: &[Vec<Option<W>>],
+    rows: usize,
+    columns: usize,
+) -> Option<Vec<(usize, usize)>> {
+    if rows <= columns {
+        let columns_of = shortest_augmentations(rows, columns, |i, j| costs[i][j])?;
+        return Some(columns_of.into_iter().enumerate().collect());
+    }
+    let rows_of = shortest_augmentations(columns, rows, |j, i| costs[i][j])?;
+    let mut pairs: Vec<(usize, usize)> = rows_of
+        .into_iter()
+        .enumerate()
+        .map(|(j, i)| (i, j))
+        .collect();
+    pairs.sort_unstable();
+    Some(pairs)
+}
+
+/// Hungarian algorithm by successive shortest augmenting paths, with a potential on each row and each column.
+/// Each row is added in turn, the dual variables growing until a free column is reached through tight pairs.
+/// The potentials of the columns are stored negated, so that unsigned weights never go below zero.
+/// # Return
+/// An Option containing the column assigned to each row, None if a row cannot be assigned.
+fn shortest_augmentations<W, F>(rows: usize, columns: usize, cost: F) -> Option<Vec<usize>>
+where
+    W: Weight,
+    F: Fn(usize, usize) -> Option<W>,
+{
+    let zero = W::zero();
+    // Index 0 is a virtual column, rows and columns being numbered from 1 below.
+    let mut row_potentials = vec![zero; rows + 1];
+    let mut column_raises = vec![zero; columns + 1];
+    let mut row_of = vec![0; columns + 1];
+    let mut previous = vec![0; columns + 1];
+    for row in 1..=rows {
+        row_of[0] = row;
+        let mut column = 0;
+        let mut slack: Vec<Option<W>> = vec![None; columns + 1];
+        let mut used = vec![false; columns + 1];
+        loop {
+            used[column] = true;
+            let current = row_of[column];
+            let mut delta: Option<W> = None;
+            let mut next = 0;
+            for j in 1..=columns {
+                if used[j] {
+                    continue;
+                }
+                if let Some(cost) = cost(current - 1, j - 1) {
+                    let reduced = cost + column_raises[j] - row_potentials[current];
+                    if slack[j].is_none_or(|slack| reduced < slack) {
+                        slack[j] = Some(reduced);
+                        previous[j] = column;
+                    }
+                }
+                if let Some(slack) = slack[j] {
+                    if delta.is_none_or(|delta| slack < delta) {
+                        delta = Some(slack);
+                        next = j;
+                    }
+                }
+            }
+            let delta = delta?;
+            for j in 0..=columns {
+                if used[j] {
+                    row_potentials[row_of[j]] = row_potentials[row_of[j]] + delta;
+                    column_raises[j] = column_raises[j] + delta;
+                } else if let Some(slack) = slack[j].as_mut() {
+                    *slack = *slack - delta;
+                }
+            }
+            column = next;
+            if row_of[column] == 0 {
+                break;
+            }
+        }
+        while column != 0 {
+            let before = previous[column];
+            row_of[column] = row_of[before];
+            column = before;
+        }
+    }
+    let mut columns_of = vec![0; rows];
+    for (j, row) in row_of.iter().enumerate().skip(1) {
+        if *row != 0 {
+            columns_of[row - 1] = j - 1;
+        }
+    }
+    Some(columns_of)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cluster, UndirectedHashCluster, WeightedBasicNode};
+
+    #[test]
+    fn square_matrices_assign_every_row() {
+        let costs = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        let assignment = hungarian_matrix(&costs).unwrap();
+        assert_eq!(assignment.pairs, vec![(0, 1), (1, 0), (2, 2)]);
+        assert_eq!(assignment.cost, 5);
+    }
+
+    #[test]
+    fn the_smaller_side_of_a_rectangle_is_assigned() {
+        let wide = hungarian_matrix(&[vec![1, 2, 3], vec![3, 1, 2]]).unwrap();
+        assert_eq!((wide.pairs, wide.cost), (vec![(0, 0), (1, 1)], 2));
+        let tall = hungarian_matrix(&[vec![5], vec![1], vec![3]]).unwrap();
+        assert_eq!((tall.pairs, tall.cost), (vec![(1, 0)], 1));
+        assert!(hungarian_matrix(&[vec![1, 2], vec![3]]).is_none());
+    }
+
+    #[test]
+    fn only_the_edges_of_the_cluster_are_allowed() {
+        let mut cluster: UndirectedHashCluster<u32, WeightedBasicNode<u32, i64>> =
+            UndirectedHashCluster::new();
+        for key in 0..4 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in [(0, 2, 1), (0, 3, 2), (1, 2, 5)] {
+            cluster.add_weighted_edge(src, dst, weight).unwrap();
+        }
+        let left = HashSet::from([0, 1]);
+        let mut assignment = hungarian(&cluster, &left).unwrap();
+        assignment.pairs.sort();
+        assert_eq!(assignment.pairs, vec![(0, 3), (1, 2)]);
+        assert_eq!(assignment.cost, 7);
+        cluster.remove_edge(&1, &2).unwrap();
+        assert!(hungarian(&cluster, &left).is_none());
+    }
+}
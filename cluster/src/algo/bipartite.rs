@@ -0,0 +1,312 @@
+//! Bipartite structure of a Cluster : 2-coloring and matchings.
+
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Outcome of the bipartiteness check of a Cluster.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bipartiteness<K: Hash + Eq> {
+    /// The Cluster is bipartite : every edge links a node of one color class to a node of the other.
+    Bipartite {
+        /// The nodes of the first color, holding the first node of each connected component.
+        left: HashSet<K>,
+        /// The nodes of the second color.
+        right: HashSet<K>,
+    },
+    /// The Cluster is not bipartite, as shown by a cycle of odd length, each key being linked to the next one and the last to the first.
+    OddCycle(Vec<K>),
+}
+
+impl<K: Hash + Eq> Bipartiteness<K> {
+    /// Check if the outcome is a 2-coloring.
+    /// # Return
+    /// True if the Cluster is bipartite, false if an odd cycle was found.
+    pub fn is_bipartite(&self) -> bool {
+        matches!(self, Bipartiteness::Bipartite { .. })
+    }
+}
+
+/// Check if a Cluster seen as undirected is bipartite, by 2-coloring each connected component with a breadth-first search.
+/// An edge between two nodes of the same color closes an odd cycle through the search tree, returned as a witness.
+/// A self-loop is an odd cycle of a single node. Edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to check.
+/// # Return
+/// The two color classes of the Cluster, or an odd cycle if there is none.
+pub fn is_bipartite<K, N, C>(cluster: &C) -> Bipartiteness<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let loops = IndexedGraph::directed(cluster);
+    if let Some(v) = (0..loops.keys.len()).find(|v| loops.adj[*v].contains(v)) {
+        return Bipartiteness::OddCycle(vec![loops.keys[v].clone()]);
+    }
+    let graph = IndexedGraph::undirected(cluster);
+    let n = graph.keys.len();
+    let mut depths: Vec<Option<usize>> = vec![None; n];
+    let mut parents = vec![usize::MAX; n];
+    for root in 0..n {
+        if depths[root].is_some() {
+            continue;
+        }
+        depths[root] = Some(0);
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            let depth = depths[v].expect("Queued nodes have a depth.");
+            for w in &graph.adj[v] {
+                match depths[*w] {
+                    None => {
+                        depths[*w] = Some(depth + 1);
+                        parents[*w] = v;
+                        queue.push_back(*w);
+                    }
+                    Some(other) if other % 2 == depth % 2 => {
+                        let cycle = odd_cycle(&parents, &depths, v, *w);
+                        return Bipartiteness::OddCycle(
+                            cycle.into_iter().map(|v| graph.keys[v].clone()).collect(),
+                        );
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    let (mut left, mut right) = (HashSet::new(), HashSet::new());
+    for (v, key) in graph.keys.into_iter().enumerate() {
+        if depths[v].is_some_and(|depth| depth % 2 == 0) {
+            left.insert(key);
+        } else {
+            right.insert(key);
+        }
+    }
+    Bipartiteness::Bipartite { left, right }
+}
+
+/// Maximum matching of a bipartite Cluster, along with a minimum vertex cover of the same size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BipartiteMatching<K: Hash + Eq> {
+    /// The (left, right) pairs of the matched nodes, no node appearing twice.
+    pub pairs: Vec<(K, K)>,
+    /// The nodes of a minimum vertex cover : every edge between both sides has an end in it.
+    pub cover: HashSet<K>,
+}
+
+/// Compute a maximum matching between a set of nodes and the other nodes of a Cluster with the Hopcroft–Karp algorithm.
+/// Each phase finds the shortest augmenting paths by a breadth-first search from the free left nodes,
+/// then augments along a maximal set of them by depth-first searches in the layered graph.
+/// The minimum vertex cover follows from König's theorem : the left nodes not reachable by alternating paths from the free left nodes,
+/// and the right nodes that are.
+/// The Cluster is seen as undirected, edges between two nodes of the same side and edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The Cluster to match.
+/// - left - The keys of the nodes of the left side, the right side being made of the other nodes of the Cluster.
+/// # Return
+/// The BipartiteMatching of the Cluster.
+pub fn max_bipartite_matching<K, N, C>(cluster: &C, left: &HashSet<K>) -> BipartiteMatching<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let mut graph = IndexedGraph::undirected(cluster);
+    let n = graph.keys.len();
+    let is_left: Vec<bool> = graph.keys.iter().map(|key| left.contains(key)).collect();
+    for (v, neighbours) in graph.adj.iter_mut().enumerate() {
+        if is_left[v] {
+            neighbours.retain(|w| !is_left[*w]);
+        } else {
+            neighbours.clear();
+        }
+    }
+    let mut mates: Vec<Option<usize>> = vec![None; n];
+    let mut layers = vec![usize::MAX; n];
+    while augmenting_layers(&graph.adj, &is_left, &mates, &mut layers) {
+        let mut next = vec![0; n];
+        for root in 0..n {
+            if is_left[root] && mates[root].is_none() {
+                augment(&graph.adj, &mut mates, &mut layers, &mut next, root);
+            }
+        }
+    }
+    let pairs = (0..n)
+        .filter(|v| is_left[*v])
+        .filter_map(|v| Some((graph.keys[v].clone(), graph.keys[mates[v]?].clone())))
+        .collect();
+    let cover = (0..n)
+        .filter(|v| {
+            if is_left[*v] {
+                layers[*v] == usize::MAX
+            } else {
+                mates[*v].is_some_and(|mate| layers[mate] != usize::MAX)
+            }
+        })
+        .map(|v| graph.keys[v].clone())
+        .collect();
+    BipartiteMatching { pairs, cover }
+}
+
+/// Compute the layer of each left node reachable by an alternating path from a free left node, the others getting usize::MAX.
+/// # Return
+/// True if a free right node can be reached, so an augmenting path exists.
+fn augmenting_layers(
+    adj: &[Vec<usize>],
+    is_left: &[bool],
+    mates: &[Option<usize>],
+    layers: &mut [usize],
+) -> bool {
+    let mut queue = VecDeque::new();
+    for v in 0..adj.len() {
+        if is_left[v] && mates[v].is_none() {
+            layers[v] = 0;
+            queue.push_back(v);
+        } else {
+            layers[v] = usize::MAX;
+        }
+    }
+    let mut found = false;
+    while let Some(u) = queue.pop_front() {
+        for w in &adj[u] {
+            match mates[*w] {
+                None => found = true,
+                Some(mate) if layers[mate] == usize::MAX => {
+                    layers[mate] = layers[u] + 1;
+                    queue.push_back(mate);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    found
+}
+
+/// Search an augmenting path from a free left node through the layers, flipping it if one is found.
+/// The recursion is replaced by an explicit stack, the left nodes leading nowhere being removed from the layers.
+fn augment(
+    adj: &[Vec<usize>],
+    mates: &mut [Option<usize>],
+    layers: &mut [usize],
+    next: &mut [usize],
+    root: usize,
+) {
+    let mut stack = vec![root];
+    while let Some(u) = stack.last().copied() {
+        let Some(w) = adj[u].get(next[u]).copied() else {
+            layers[u] = usize::MAX;
+            stack.pop();
+            if let Some(parent) = stack.last() {
+                next[*parent] += 1;
+            }
+            continue;
+        };
+        match mates[w] {
+            None => {
+                for u in stack {
+                    let w = adj[u][next[u]];
+                    mates[u] = Some(w);
+                    mates[w] = Some(u);
+                }
+                return;
+            }
+            Some(mate) if layers[mate] == layers[u] + 1 => stack.push(mate),
+            Some(_) => next[u] += 1,
+        }
+    }
+}
+
+/// Close the cycle made of the edge between a and b and the paths of the search tree from both of them to their lowest common ancestor.
+fn odd_cycle(
+    parents: &[usize],
+    depths: &[Option<usize>],
+    mut a: usize,
+    mut b: usize,
+) -> Vec<usize> {
+    let mut from_a = Vec::new();
+    let mut from_b = Vec::new();
+    while a != b {
+        if depths[a] >= depths[b] {
+            from_a.push(a);
+            a = parents[a];
+        } else {
+            from_b.push(b);
+            b = parents[b];
+        }
+    }
+    from_a.push(a);
+    from_a.extend(from_b.into_iter().rev());
+    from_a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, UndirectedHashCluster};
+
+    fn undirected(edges: &[(u32, u32)]) -> UndirectedHashCluster<u32, BasicNode<u32>> {
+        let mut cluster = UndirectedHashCluster::new();
+        for (src, dst) in edges {
+            cluster.insert(*src, BasicNode::new(()));
+            cluster.insert(*dst, BasicNode::new(()));
+        }
+        for (src, dst) in edges {
+            cluster.add_edge(*src, *dst).unwrap();
+        }
+        cluster
+    }
+
+    fn check_matching(edges: &[(u32, u32)], left: &HashSet<u32>, size: usize) {
+        let matching = max_bipartite_matching(&undirected(edges), left);
+        assert_eq!(matching.pairs.len(), size);
+        assert_eq!(matching.cover.len(), size);
+        let mut matched = HashSet::new();
+        for (l, r) in &matching.pairs {
+            assert!(left.contains(l) && !left.contains(r));
+            assert!(edges.contains(&(*l, *r)));
+            assert!(matched.insert(*l) && matched.insert(*r));
+        }
+        for (src, dst) in edges {
+            assert!(matching.cover.contains(src) || matching.cover.contains(dst));
+        }
+    }
+
+    #[test]
+    fn even_cycles_are_two_colored() {
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 0)];
+        let Bipartiteness::Bipartite { left, right } = is_bipartite(&undirected(&edges)) else {
+            panic!("an even cycle is bipartite");
+        };
+        assert_eq!(left.len() + right.len(), 4);
+        for (src, dst) in edges {
+            assert_ne!(left.contains(&src), left.contains(&dst));
+        }
+    }
+
+    #[test]
+    fn odd_cycles_are_reported() {
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (0, 5)];
+        let Bipartiteness::OddCycle(cycle) = is_bipartite(&undirected(&edges)) else {
+            panic!("a pentagon is not bipartite");
+        };
+        assert_eq!(cycle.len() % 2, 1);
+        for (i, src) in cycle.iter().enumerate() {
+            let dst = cycle[(i + 1) % cycle.len()];
+            assert!(edges.contains(&(*src, dst)) || edges.contains(&(dst, *src)));
+        }
+        assert_eq!(
+            is_bipartite(&undirected(&[(0, 1), (1, 1)])),
+            Bipartiteness::OddCycle(vec![1])
+        );
+    }
+
+    #[test]
+    fn matchings_are_maximum_with_a_cover_of_the_same_size() {
+        let left = HashSet::from([0, 1, 2]);
+        check_matching(&[(0, 3), (0, 4), (1, 3), (2, 3)], &left, 2);
+        check_matching(&[(0, 3), (0, 4), (1, 4), (1, 5), (2, 5)], &left, 3);
+        check_matching(&[(0, 1), (1, 3)], &left, 1);
+    }
+}
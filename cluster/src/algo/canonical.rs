@@ -0,0 +1,256 @@
+//! Canonical labeling of a Cluster, giving the same form to isomorphic Clusters.
+
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Structure of a Cluster stripped of its keys, the nodes being numbered in canonical order.
+/// Two Clusters have the same CanonicalForm if and only if they are isomorphic, so it can be hashed to deduplicate them.
+/// The values of the nodes and the weights of the edges are not part of the form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalForm {
+    node_count: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+impl CanonicalForm {
+    /// Get the number of nodes of the form.
+    /// # Return
+    /// The number of nodes of the Cluster.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Get the edges of the form, between the canonical positions of their nodes.
+    /// # Return
+    /// The sorted (source, destination) pairs of the edges, both directions being listed for an undirected Cluster.
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+}
+
+/// Order the nodes of a Cluster canonically : isomorphic Clusters get their nodes ordered the same way, up to their automorphisms.
+/// The search refines the colors of the nodes from their neighbourhoods, then individualizes a node of an ambiguous color
+/// and refines again, keeping the ordering giving the smallest edge list. Automorphisms met on the way prune the search.
+/// Edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to order.
+/// # Return
+/// The keys of the Cluster in canonical order.
+pub fn canonical_order<K, N, C>(cluster: &C) -> Vec<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    let (order, _) = Search::new(&graph.adj).run();
+    order.into_iter().map(|v| graph.keys[v].clone()).collect()
+}
+
+/// Compute the CanonicalForm of a Cluster, its nodes being numbered in the order given by canonical_order.
+/// # Parameter
+/// - cluster - The Cluster to describe.
+/// # Return
+/// The CanonicalForm of the Cluster.
+pub fn canonical_form<K, N, C>(cluster: &C) -> CanonicalForm
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    let (_, edges) = Search::new(&graph.adj).run();
+    CanonicalForm {
+        node_count: graph.keys.len(),
+        edges,
+    }
+}
+
+/// Sorted edge list of a discrete coloring along with the nodes in the order of their colors.
+type Leaf = (Vec<(usize, usize)>, Vec<usize>);
+
+/// Individualization-refinement search tree over the colorings of an indexed graph.
+struct Search<'a> {
+    adj: &'a [Vec<usize>],
+    reverse: Vec<Vec<usize>>,
+    best: Option<Leaf>,
+    first: Option<Leaf>,
+    first_path: Vec<usize>,
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl<'a> Search<'a> {
+    fn new(adj: &'a [Vec<usize>]) -> Search<'a> {
+        let mut reverse = vec![Vec::new(); adj.len()];
+        for (v, dsts) in adj.iter().enumerate() {
+            for w in dsts {
+                reverse[*w].push(v);
+            }
+        }
+        Search {
+            adj,
+            reverse,
+            best: None,
+            first: None,
+            first_path: Vec::new(),
+            automorphisms: Vec::new(),
+        }
+    }
+
+    /// Explore the search tree, returning the best ordering and its edge list.
+    fn run(mut self) -> (Vec<usize>, Vec<(usize, usize)>) {
+        let colors = self.refine(vec![0; self.adj.len()]);
+        self.explore(colors, &mut Vec::new());
+        let (edges, order) = self.best.unwrap_or_default();
+        (order, edges)
+    }
+
+    /// Explore the subtree below a coloring, the prefix holding the nodes individualized to reach it.
+    /// A leaf equivalent to the first one makes the search go back to the node where their paths diverge,
+    /// the subtree being the image of an explored one : the depth of that node is returned in that case.
+    fn explore(&mut self, colors: Vec<usize>, prefix: &mut Vec<usize>) -> Option<usize> {
+        let Some(cell) = target_cell(&colors) else {
+            return self.leaf(&colors, prefix);
+        };
+        let depth = prefix.len();
+        let mut explored: Vec<usize> = Vec::new();
+        for v in cell {
+            if !explored.is_empty() && self.same_orbit(prefix, &explored, v) {
+                continue;
+            }
+            explored.push(v);
+            let individualized = colors
+                .iter()
+                .enumerate()
+                .map(|(u, c)| 2 * c + usize::from(u != v))
+                .collect();
+            let refined = self.refine(individualized);
+            prefix.push(v);
+            let jump = self.explore(refined, prefix);
+            prefix.pop();
+            if jump.is_some_and(|level| level < depth) {
+                return jump;
+            }
+        }
+        None
+    }
+
+    /// Record a discrete coloring, comparing its edge list with the best and first ones met so far.
+    fn leaf(&mut self, colors: &[usize], prefix: &[usize]) -> Option<usize> {
+        let mut order = vec![0; colors.len()];
+        for (v, c) in colors.iter().enumerate() {
+            order[*c] = v;
+        }
+        let mut edges: Vec<(usize, usize)> = self
+            .adj
+            .iter()
+            .enumerate()
+            .flat_map(|(v, dsts)| dsts.iter().map(move |w| (colors[v], colors[*w])))
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        for (is_first, reference) in [(true, &self.first), (false, &self.best)] {
+            let Some((reference, reference_order)) = reference else {
+                continue;
+            };
+            if *reference == edges {
+                let mut automorphism = vec![0; order.len()];
+                for (position, v) in order.iter().enumerate() {
+                    automorphism[*v] = reference_order[position];
+                }
+                self.automorphisms.push(automorphism);
+                return is_first.then(|| {
+                    prefix
+                        .iter()
+                        .zip(&self.first_path)
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                });
+            }
+        }
+        if self.first.is_none() {
+            self.first = Some((edges.clone(), order.clone()));
+            self.first_path = prefix.to_vec();
+        }
+        if self.best.as_ref().is_none_or(|(best, _)| edges < *best) {
+            self.best = Some((edges, order));
+        }
+        None
+    }
+
+    /// Check if v is mapped to an explored node by the automorphisms found so far that fix the prefix.
+    fn same_orbit(&self, prefix: &[usize], explored: &[usize], v: usize) -> bool {
+        let mut orbits: Vec<usize> = (0..self.adj.len()).collect();
+        for automorphism in &self.automorphisms {
+            if prefix.iter().any(|u| automorphism[*u] != *u) {
+                continue;
+            }
+            for (u, image) in automorphism.iter().enumerate() {
+                let (a, b) = (orbit(&mut orbits, u), orbit(&mut orbits, *image));
+                orbits[a] = b;
+            }
+        }
+        let target = orbit(&mut orbits, v);
+        explored.iter().any(|u| orbit(&mut orbits, *u) == target)
+    }
+
+    /// Refine a coloring until the color of each node tells the colors of its successors and predecessors.
+    /// The new colors are ranks of the refined signatures, so they only depend on the structure of the graph.
+    fn refine(&self, mut colors: Vec<usize>) -> Vec<usize> {
+        let mut count = distinct(&colors);
+        loop {
+            let signatures: Vec<(usize, Vec<usize>, Vec<usize>)> = (0..colors.len())
+                .map(|v| {
+                    let mut out: Vec<usize> = self.adj[v].iter().map(|w| colors[*w]).collect();
+                    let mut incoming: Vec<usize> =
+                        self.reverse[v].iter().map(|u| colors[*u]).collect();
+                    out.sort_unstable();
+                    incoming.sort_unstable();
+                    (colors[v], out, incoming)
+                })
+                .collect();
+            let mut sorted: Vec<&(usize, Vec<usize>, Vec<usize>)> = signatures.iter().collect();
+            sorted.sort_unstable();
+            sorted.dedup();
+            colors = signatures
+                .iter()
+                .map(|signature| sorted.binary_search(&signature).unwrap())
+                .collect();
+            let refined = sorted.len();
+            if refined == count {
+                return colors;
+            }
+            count = refined;
+        }
+    }
+}
+
+/// Get the nodes of the smallest color shared by several nodes, the lowest such color winning ties.
+fn target_cell(colors: &[usize]) -> Option<Vec<usize>> {
+    let mut sizes = vec![0; colors.len()];
+    for c in colors {
+        sizes[*c] += 1;
+    }
+    let color = (0..sizes.len())
+        .filter(|c| sizes[*c] > 1)
+        .min_by_key(|c| sizes[*c])?;
+    Some((0..colors.len()).filter(|v| colors[*v] == color).collect())
+}
+
+fn distinct(colors: &[usize]) -> usize {
+    let mut sorted = colors.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    sorted.len()
+}
+
+/// Find the representative of the orbit of a node, compressing the path to it.
+fn orbit(orbits: &mut [usize], mut v: usize) -> usize {
+    while orbits[v] != v {
+        orbits[v] = orbits[orbits[v]];
+        v = orbits[v];
+    }
+    v
+}
@@ -0,0 +1,732 @@
+//! Scores measuring the importance of the nodes of a Cluster.
+
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::weighted_index;
+#[cfg(feature = "rand")]
+use crate::random::shuffle;
+#[cfg(feature = "rand")]
+use crate::RandomSource;
+use crate::{Cluster, Node, ReverseAdjacency, ReverseIndex, WeightedCluster, WeightedNode};
+
+/// Compute the PageRank of the nodes of a Cluster by power iteration, the random walk following each edge of a node with the same probability.
+/// The score of the nodes without edges is spread over all the nodes, as if they were linked to every node.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - damping - The probability to follow an edge at each step rather than to jump to any node, usually 0.85.
+/// - max_iterations - The maximum number of iterations.
+/// - tolerance - The total change of the scores under which they are considered converged.
+/// # Return
+/// The score of each key of the Cluster, the scores summing to 1.
+pub fn pagerank<K, N, C>(
+    cluster: &C,
+    damping: f64,
+    max_iterations: usize,
+    tolerance: f64,
+) -> HashMap<K, f64>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+    let out: Vec<Vec<(usize, f64)>> = adj
+        .iter()
+        .map(|dsts| dsts.iter().map(|dst| (*dst, 1.0)).collect())
+        .collect();
+    let scores = power_iteration(&out, damping, max_iterations, tolerance);
+    keys.into_iter().zip(scores).collect()
+}
+
+/// Compute the PageRank of the nodes of a weighted Cluster by power iteration, the random walk following each edge of a node
+/// with a probability proportional to its weight. Edges with a negative weight are ignored.
+/// The score of the nodes without edges of positive weight is spread over all the nodes, as if they were linked to every node.
+/// # Parameters
+/// - cluster - The weighted Cluster to rank.
+/// - damping - The probability to follow an edge at each step rather than to jump to any node, usually 0.85.
+/// - max_iterations - The maximum number of iterations.
+/// - tolerance - The total change of the scores under which they are considered converged.
+/// # Return
+/// The score of each key of the Cluster, the scores summing to 1.
+pub fn weighted_pagerank<K, N, C>(
+    cluster: &C,
+    damping: f64,
+    max_iterations: usize,
+    tolerance: f64,
+) -> HashMap<K, f64>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Into<f64>,
+    C: WeightedCluster<K, N>,
+{
+    let index = weighted_index(cluster);
+    let out: Vec<Vec<(usize, f64)>> = index
+        .adj
+        .iter()
+        .map(|dsts| {
+            dsts.iter()
+                .map(|(dst, weight)| (*dst, (*weight).into()))
+                .filter(|(_, weight)| *weight > 0.0)
+                .collect()
+        })
+        .collect();
+    let scores = power_iteration(&out, damping, max_iterations, tolerance);
+    index.keys.into_iter().zip(scores).collect()
+}
+
+/// Iterate the PageRank scores over weighted out-edges, the dangling nodes linking to every node.
+fn power_iteration(
+    out: &[Vec<(usize, f64)>],
+    damping: f64,
+    max_iterations: usize,
+    tolerance: f64,
+) -> Vec<f64> {
+    let n = out.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let totals: Vec<f64> = out
+        .iter()
+        .map(|dsts| dsts.iter().map(|(_, weight)| weight).sum())
+        .collect();
+    let mut scores = vec![1.0 / n as f64; n];
+    for _ in 0..max_iterations {
+        let dangling: f64 = (0..n)
+            .filter(|v| totals[*v] == 0.0)
+            .map(|v| scores[v])
+            .sum();
+        let base = (1.0 - damping + damping * dangling) / n as f64;
+        let mut next = vec![base; n];
+        for (src, dsts) in out.iter().enumerate() {
+            for (dst, weight) in dsts {
+                next[*dst] += damping * scores[src] * weight / totals[src];
+            }
+        }
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < tolerance {
+            break;
+        }
+    }
+    scores
+}
+
+/// Compute the Katz centrality of the nodes of a Cluster by fixed-point iteration :
+/// the score of a node is beta plus alpha times the sum of the scores of the nodes having an edge toward it,
+/// so that every walk ending at the node counts, attenuated by alpha at each step.
+/// The iteration only converges when alpha is lower than the inverse of the largest eigenvalue of the adjacency matrix.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - alpha - The attenuation of each step of the walks, usually small such as 0.1.
+/// - beta - The score every node gets on its own, usually 1.
+/// - max_iterations - The maximum number of iterations.
+/// - tolerance - The total change of the scores under which they are considered converged.
+/// # Return
+/// An Option containing the score of each key of the Cluster, None if the scores did not converge within max_iterations.
+pub fn katz<K, N, C>(
+    cluster: &C,
+    alpha: f64,
+    beta: f64,
+    max_iterations: usize,
+    tolerance: f64,
+) -> Option<HashMap<K, f64>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+    let mut scores = vec![beta; keys.len()];
+    for _ in 0..max_iterations {
+        let mut next = vec![beta; keys.len()];
+        for (src, dsts) in adj.iter().enumerate() {
+            for dst in dsts {
+                next[*dst] += alpha * scores[src];
+            }
+        }
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < tolerance {
+            return Some(keys.into_iter().zip(scores).collect());
+        }
+    }
+    None
+}
+
+/// Compute the eigenvector centrality of the nodes of a Cluster by power iteration :
+/// the score of a node is proportional to the sum of the scores of the nodes having an edge toward it.
+/// The iteration is shifted by the identity, which keeps the same eigenvector but also converges on bipartite Clusters.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - max_iterations - The maximum number of iterations.
+/// - tolerance - The total change of the scores under which they are considered converged.
+/// # Return
+/// An Option containing the score of each key of the Cluster, the scores summing to 1, None if they did not converge within max_iterations.
+pub fn eigenvector_centrality<K, N, C>(
+    cluster: &C,
+    max_iterations: usize,
+    tolerance: f64,
+) -> Option<HashMap<K, f64>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+    let mut scores = vec![1.0 / keys.len().max(1) as f64; keys.len()];
+    for _ in 0..max_iterations {
+        let mut next = scores.clone();
+        for (src, dsts) in adj.iter().enumerate() {
+            for dst in dsts {
+                next[*dst] += scores[src];
+            }
+        }
+        normalize(&mut next);
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < tolerance {
+            return Some(keys.into_iter().zip(scores).collect());
+        }
+    }
+    None
+}
+
+/// Approximate the personalized PageRank of the nodes around a seed node, using local pushes.
+/// Only the neighbourhood of the seed holding a significant part of the score is explored.
+/// The walk from a node without edges restarts at the seed.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - seed - The key of the node the random walk restarts from.
+/// - alpha - The probability to restart at the seed at each step, between 0 and 1.
+/// - epsilon - The residual score per edge under which a node is not pushed anymore, the smaller the more precise.
+/// # Return
+/// The sparse map of the approximate scores, nodes not in it have a negligible score.
+pub fn personalized_pagerank_push<K, N, C>(
+    cluster: &C,
+    seed: &K,
+    alpha: f64,
+    epsilon: f64,
+) -> HashMap<K, f64>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let mut scores: HashMap<K, f64> = HashMap::new();
+    if !cluster.contains_key(seed) {
+        return scores;
+    }
+    let mut residuals: HashMap<K, f64> = HashMap::from([(seed.clone(), 1.0)]);
+    let mut queue = VecDeque::from([seed.clone()]);
+    let mut queued = HashSet::from([seed.clone()]);
+    while let Some(key) = queue.pop_front() {
+        queued.remove(&key);
+        let residual = residuals.insert(key.clone(), 0.0).unwrap_or(0.0);
+        *scores.entry(key.clone()).or_default() += alpha * residual;
+        let spread = (1.0 - alpha) * residual;
+        let adj: Vec<&K> = cluster
+            .get_adj(&key)
+            .into_iter()
+            .flatten()
+            .filter(|k| cluster.contains_key(k))
+            .collect();
+        let targets = if adj.is_empty() { vec![seed] } else { adj };
+        let share = spread / targets.len() as f64;
+        for target in targets {
+            let r = residuals.entry(target.clone()).or_default();
+            *r += share;
+            let degree = cluster.get_adj(target).map_or(0, |adj| adj.len()).max(1);
+            if *r >= epsilon * degree as f64 && queued.insert(target.clone()) {
+                queue.push_back(target.clone());
+            }
+        }
+    }
+    scores
+}
+
+/// Estimate the personalized PageRank of the nodes of a Cluster for a set of seed nodes by Monte Carlo simulation.
+/// Each walk starts at a seed drawn uniformly and follows a random edge with probability damping at each step, stopping otherwise,
+/// the walk from a node without edges jumping to a random seed. The score of a node is its share of the visits,
+/// an unbiased estimate of the probability to stand on it in the stationary state of the walk restarting at the seeds.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - seeds - The keys of the nodes the walks start from, those not in the Cluster being ignored.
+/// - damping - The probability to follow an edge at each step, lower than 1.
+/// - walks - The number of walks simulated, the more the more precise.
+/// - rng - The source of randomness.
+/// # Return
+/// The sparse map of the estimated scores, summing to about 1, empty if no seed is in the Cluster.
+#[cfg(feature = "rand")]
+pub fn personalized_pagerank<K, N, C, R>(
+    cluster: &C,
+    seeds: &[K],
+    damping: f64,
+    walks: usize,
+    rng: &mut R,
+) -> HashMap<K, f64>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: RandomSource + ?Sized,
+{
+    let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+    let wanted: HashSet<&K> = seeds.iter().collect();
+    let starts: Vec<usize> = (0..keys.len())
+        .filter(|v| wanted.contains(&keys[*v]))
+        .collect();
+    let mut visits: HashMap<usize, usize> = HashMap::new();
+    if starts.is_empty() {
+        return HashMap::new();
+    }
+    for _ in 0..walks {
+        let mut v = starts[rng.below(starts.len())];
+        loop {
+            *visits.entry(v).or_default() += 1;
+            if rng.next_f64() >= damping {
+                break;
+            }
+            v = match adj[v].len() {
+                0 => starts[rng.below(starts.len())],
+                degree => adj[v][rng.below(degree)],
+            };
+        }
+    }
+    let scale = (1.0 - damping) / walks.max(1) as f64;
+    visits
+        .into_iter()
+        .map(|(v, count)| (keys[v].clone(), count as f64 * scale))
+        .collect()
+}
+
+/// Hub and authority scores computed by the HITS algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitsScores<K: Hash + Eq> {
+    /// Hub score of each node, high when the node points to good authorities.
+    pub hubs: HashMap<K, f64>,
+    /// Authority score of each node, high when the node is pointed by good hubs.
+    pub authorities: HashMap<K, f64>,
+}
+
+/// Compute the hub and authority scores of the nodes of a Cluster with the HITS algorithm.
+/// Both kinds of scores are normalized so that they sum to 1.
+/// The reverse adjacency of the Cluster is indexed first, see hits_with to provide it.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - max_iterations - The maximum number of iterations.
+/// - tolerance - The total change of the hub scores under which the scores are considered converged.
+/// # Return
+/// The HitsScores of the nodes of the Cluster.
+pub fn hits<K, N, C>(cluster: &C, max_iterations: usize, tolerance: f64) -> HitsScores<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    hits_with(
+        cluster,
+        &ReverseIndex::new(cluster),
+        max_iterations,
+        tolerance,
+    )
+}
+
+/// Compute the hub and authority scores of the nodes of a Cluster with the HITS algorithm.
+/// The authority of a node gathers the hub scores of its predecessors, its hub score the authorities of its successors,
+/// both kinds of scores being normalized so that they sum to 1.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - reverse - The reverse adjacency of the Cluster, a ReverseIndex or the Cluster itself when undirected.
+/// - max_iterations - The maximum number of iterations.
+/// - tolerance - The total change of the hub scores under which the scores are considered converged.
+/// # Return
+/// The HitsScores of the nodes of the Cluster.
+pub fn hits_with<K, N, C, R>(
+    cluster: &C,
+    reverse: &R,
+    max_iterations: usize,
+    tolerance: f64,
+) -> HitsScores<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: ReverseAdjacency<K>,
+{
+    let IndexedGraph { keys, adj: out } = IndexedGraph::directed(cluster);
+    let index: HashMap<&K, usize> = keys.iter().enumerate().map(|(i, k)| (k, i)).collect();
+    let preds: Vec<Vec<usize>> = keys
+        .iter()
+        .map(|key| {
+            reverse
+                .reverse_adj(key)
+                .into_iter()
+                .flatten()
+                .filter_map(|k| index.get(k).copied())
+                .collect()
+        })
+        .collect();
+    let n = keys.len();
+    let mut hubs = vec![1.0 / n.max(1) as f64; n];
+    let mut authorities = vec![0.0; n];
+    for _ in 0..max_iterations {
+        for (authority, adj) in authorities.iter_mut().zip(&preds) {
+            *authority = adj.iter().fold(0.0, |acc, src| acc + hubs[*src]);
+        }
+        normalize(&mut authorities);
+        let mut next: Vec<f64> = out
+            .iter()
+            .map(|adj| adj.iter().fold(0.0, |acc, dst| acc + authorities[*dst]))
+            .collect();
+        normalize(&mut next);
+        let change: f64 = next.iter().zip(&hubs).map(|(a, b)| (a - b).abs()).sum();
+        hubs = next;
+        if change < tolerance {
+            break;
+        }
+    }
+    let scores = |values: &[f64]| -> HashMap<K, f64> {
+        keys.iter().cloned().zip(values.iter().copied()).collect()
+    };
+    HitsScores {
+        hubs: scores(&hubs),
+        authorities: scores(&authorities),
+    }
+}
+
+/// Betweenness centrality of the nodes and edges of a Cluster : the number of shortest paths going through them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Betweenness<K: Hash + Eq> {
+    /// Betweenness of each node, counting the shortest paths between other nodes going through it.
+    pub nodes: HashMap<K, f64>,
+    /// Betweenness of each edge, as source, destination and the shortest paths using the edge.
+    pub edges: Vec<(K, K, f64)>,
+}
+
+/// Compute the exact betweenness of the nodes and edges of a Cluster with the algorithm of Brandes, in O(nm) time.
+/// Each pair of nodes linked by several shortest paths shares its contribution between them.
+/// The pairs of an undirected Cluster are counted once, its edges being listed once.
+/// # Parameter
+/// - cluster - The Cluster to rank.
+/// # Return
+/// The Betweenness of the nodes and edges of the Cluster, not normalized.
+pub fn betweenness<K, N, C>(cluster: &C) -> Betweenness<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = EdgeIndex::new(cluster);
+    let sources = 0..graph.keys.len();
+    graph.brandes(sources, 1.0)
+}
+
+/// Approximate the betweenness of the nodes and edges of a Cluster from the shortest paths of a few pivot sources drawn at random.
+/// The contributions of the pivots are scaled up to the number of nodes, giving an unbiased estimate in O(pm) time.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - pivots - The number of sources, the exact betweenness being computed when there are at least as many as nodes.
+/// - rng - The source of randomness.
+/// # Return
+/// The estimated Betweenness of the nodes and edges of the Cluster, not normalized.
+#[cfg(feature = "rand")]
+pub fn sampled_betweenness<K, N, C, R>(cluster: &C, pivots: usize, rng: &mut R) -> Betweenness<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: RandomSource + ?Sized,
+{
+    let graph = EdgeIndex::new(cluster);
+    let n = graph.keys.len();
+    if pivots >= n {
+        return graph.brandes(0..n, 1.0);
+    }
+    let mut sources: Vec<usize> = (0..n).collect();
+    shuffle(&mut sources, rng);
+    sources.truncate(pivots);
+    graph.brandes(sources, n as f64 / pivots.max(1) as f64)
+}
+
+/// Snapshot of a Cluster where each node and each edge is designed by its index, each edge of an undirected Cluster
+/// being shared by both of its directions.
+struct EdgeIndex<K> {
+    keys: Vec<K>,
+    adj: Vec<Vec<(usize, usize)>>,
+    ends: Vec<(usize, usize)>,
+    directed: bool,
+}
+
+impl<K: Hash + Eq + Clone> EdgeIndex<K> {
+    /// Index the edges of a Cluster, dropping self-loops and parallel edges.
+    fn new<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> EdgeIndex<K> {
+        let IndexedGraph { keys, adj: out } = IndexedGraph::directed(cluster);
+        let directed = cluster.is_directed();
+        let mut ids: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut ends = Vec::new();
+        let mut adj = vec![Vec::new(); keys.len()];
+        for (src, dsts) in out.iter().enumerate() {
+            for dst in dsts.iter().copied().filter(|dst| *dst != src) {
+                let pair = if directed {
+                    (src, dst)
+                } else {
+                    (src.min(dst), src.max(dst))
+                };
+                let next = ends.len();
+                let id = *ids.entry(pair).or_insert(next);
+                if id == next {
+                    ends.push(pair);
+                    adj[pair.0].push((pair.1, id));
+                    if !directed {
+                        adj[pair.1].push((pair.0, id));
+                    }
+                }
+            }
+        }
+        EdgeIndex {
+            keys,
+            adj,
+            ends,
+            directed,
+        }
+    }
+
+    /// Accumulate the dependencies of the shortest paths from the given sources, scaled by the given factor.
+    fn brandes(&self, sources: impl IntoIterator<Item = usize>, scale: f64) -> Betweenness<K> {
+        let n = self.keys.len();
+        let mut nodes = vec![0.0; n];
+        let mut edges = vec![0.0; self.ends.len()];
+        let mut distances = vec![usize::MAX; n];
+        let mut paths = vec![0.0; n];
+        let mut dependencies = vec![0.0; n];
+        let mut predecessors: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+        for source in sources {
+            let mut order = Vec::new();
+            distances[source] = 0;
+            paths[source] = 1.0;
+            let mut queue = VecDeque::from([source]);
+            while let Some(v) = queue.pop_front() {
+                order.push(v);
+                for (w, edge) in &self.adj[v] {
+                    if distances[*w] == usize::MAX {
+                        distances[*w] = distances[v] + 1;
+                        queue.push_back(*w);
+                    }
+                    if distances[*w] == distances[v] + 1 {
+                        paths[*w] += paths[v];
+                        predecessors[*w].push((v, *edge));
+                    }
+                }
+            }
+            while let Some(w) = order.pop() {
+                for (v, edge) in &predecessors[w] {
+                    let share = paths[*v] / paths[w] * (1.0 + dependencies[w]);
+                    edges[*edge] += share;
+                    dependencies[*v] += share;
+                }
+                if w != source {
+                    nodes[w] += dependencies[w];
+                }
+                distances[w] = usize::MAX;
+                paths[w] = 0.0;
+                dependencies[w] = 0.0;
+                predecessors[w].clear();
+            }
+        }
+        let scale = if self.directed { scale } else { scale / 2.0 };
+        Betweenness {
+            nodes: self
+                .keys
+                .iter()
+                .cloned()
+                .zip(nodes.into_iter().map(|b| b * scale))
+                .collect(),
+            edges: self
+                .ends
+                .iter()
+                .zip(edges)
+                .map(|((src, dst), b)| {
+                    (self.keys[*src].clone(), self.keys[*dst].clone(), b * scale)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Closeness and harmonic centrality of the nodes of a Cluster, measured on the distances from the other nodes toward them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Closeness<K: Hash + Eq> {
+    /// Closeness of each node : the inverse of the average distance from the nodes reaching it, scaled by the share of nodes reaching it
+    /// so that the nodes of small components are not favoured, null if no other node reaches it.
+    pub closeness: HashMap<K, f64>,
+    /// Harmonic centrality of each node : the sum of the inverses of the distances from the other nodes, unreachable ones counting for 0.
+    pub harmonic: HashMap<K, f64>,
+}
+
+/// Compute the closeness and harmonic centrality of the nodes of a Cluster, each edge counting for 1, with a breadth-first search per node.
+/// # Parameter
+/// - cluster - The Cluster to rank.
+/// # Return
+/// The Closeness of the nodes of the Cluster.
+pub fn closeness<K, N, C>(cluster: &C) -> Closeness<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let keys: Vec<K> = cluster.keys().cloned().collect();
+    closeness_from(cluster, &keys)
+}
+
+/// Approximate the closeness and harmonic centrality of the nodes of a Cluster, each edge counting for 1,
+/// from the distances of a subset of sources only, their contributions being scaled up to the number of nodes.
+/// The sources should be drawn uniformly from the Cluster for the estimate to be unbiased.
+/// # Parameters
+/// - cluster - The Cluster to rank.
+/// - sources - The keys of the nodes the breadth-first searches start from, those not in the Cluster being ignored.
+/// # Return
+/// The estimated Closeness of the nodes of the Cluster, exact when every node is a source.
+pub fn closeness_from<K, N, C>(cluster: &C, sources: &[K]) -> Closeness<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+    let index: HashMap<&K, usize> = keys.iter().enumerate().map(|(i, k)| (k, i)).collect();
+    let sources = source_indices(&index, sources);
+    closeness_scores(&keys, sources, |source| {
+        let mut distances = vec![None; keys.len()];
+        distances[source] = Some(0.0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            let next = distances[v].map(|d| d + 1.0);
+            for w in &adj[v] {
+                if distances[*w].is_none() {
+                    distances[*w] = next;
+                    queue.push_back(*w);
+                }
+            }
+        }
+        distances
+    })
+}
+
+/// Compute the closeness and harmonic centrality of the nodes of a weighted Cluster, the length of a path being the sum of its weights,
+/// with the algorithm of Dijkstra from each node. Edges with a negative weight are ignored.
+/// # Parameter
+/// - cluster - The weighted Cluster to rank.
+/// # Return
+/// The Closeness of the nodes of the Cluster.
+pub fn weighted_closeness<K, N, C>(cluster: &C) -> Closeness<K>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Into<f64>,
+    C: WeightedCluster<K, N>,
+{
+    let keys: Vec<K> = cluster.keys().cloned().collect();
+    weighted_closeness_from(cluster, &keys)
+}
+
+/// Approximate the closeness and harmonic centrality of the nodes of a weighted Cluster, the length of a path being the sum of its weights,
+/// from the distances of a subset of sources only, their contributions being scaled up to the number of nodes.
+/// The sources should be drawn uniformly from the Cluster for the estimate to be unbiased. Edges with a negative weight are ignored.
+/// # Parameters
+/// - cluster - The weighted Cluster to rank.
+/// - sources - The keys of the nodes the searches start from, those not in the Cluster being ignored.
+/// # Return
+/// The estimated Closeness of the nodes of the Cluster, exact when every node is a source.
+pub fn weighted_closeness_from<K, N, C>(cluster: &C, sources: &[K]) -> Closeness<K>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Into<f64>,
+    C: WeightedCluster<K, N>,
+{
+    let graph = weighted_index(cluster);
+    let index: HashMap<&K, usize> = graph.keys.iter().enumerate().map(|(i, k)| (k, i)).collect();
+    let sources = source_indices(&index, sources);
+    closeness_scores(&graph.keys, sources, |source| {
+        let mut distances: Vec<Option<f64>> = vec![None; graph.keys.len()];
+        let mut settled = vec![false; graph.keys.len()];
+        distances[source] = Some(0.0);
+        let mut heap = BinaryHeap::from([MinScored(0.0, source)]);
+        while let Some(MinScored(d, v)) = heap.pop() {
+            if settled[v] {
+                continue;
+            }
+            settled[v] = true;
+            for (w, weight) in &graph.adj[v] {
+                let weight: f64 = (*weight).into();
+                if weight < 0.0 {
+                    continue;
+                }
+                let next = d + weight;
+                if distances[*w].is_none_or(|current| next < current) {
+                    distances[*w] = Some(next);
+                    heap.push(MinScored(next, *w));
+                }
+            }
+        }
+        distances
+    })
+}
+
+/// Get the distinct indices of the sources present in the index.
+fn source_indices<K: Hash + Eq>(index: &HashMap<&K, usize>, sources: &[K]) -> Vec<usize> {
+    let mut indices: Vec<usize> = sources
+        .iter()
+        .filter_map(|k| index.get(k).copied())
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
+/// Gather the distances from each source into the closeness and harmonic centrality of every node.
+fn closeness_scores<K, F>(keys: &[K], sources: Vec<usize>, distances: F) -> Closeness<K>
+where
+    K: Hash + Eq + Clone,
+    F: Fn(usize) -> Vec<Option<f64>>,
+{
+    let n = keys.len();
+    let scale = n as f64 / sources.len().max(1) as f64;
+    let mut reached = vec![0.0; n];
+    let mut totals = vec![0.0; n];
+    let mut harmonic = vec![0.0; n];
+    for source in sources {
+        for (v, distance) in distances(source).into_iter().enumerate() {
+            if let Some(d) = distance.filter(|_| v != source) {
+                reached[v] += scale;
+                totals[v] += scale * d;
+                if d > 0.0 {
+                    harmonic[v] += scale / d;
+                }
+            }
+        }
+    }
+    let closeness = (0..n).map(|v| {
+        if totals[v] > 0.0 {
+            reached[v] / totals[v] * reached[v] / (n - 1) as f64
+        } else {
+            0.0
+        }
+    });
+    Closeness {
+        closeness: keys.iter().cloned().zip(closeness).collect(),
+        harmonic: keys.iter().cloned().zip(harmonic).collect(),
+    }
+}
+
+/// Scale the values so that they sum to 1, leaving them untouched if they are all null.
+fn normalize(values: &mut [f64]) {
+    let total: f64 = values.iter().sum();
+    if total > 0.0 {
+        values.iter_mut().for_each(|v| *v /= total);
+    }
+}
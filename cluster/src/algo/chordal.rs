@@ -0,0 +1,88 @@
+//! Chordality of a Cluster, the direction of the edges being ignored.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Compute a perfect elimination ordering of a Cluster, the direction of the edges being ignored.
+/// In such an ordering, the neighbours of each node placed after it form a clique.
+/// The candidate ordering is the reverse of a lexicographic breadth-first search, and is then checked.
+/// # Parameter
+/// - cluster - The Cluster to order.
+/// # Return
+/// An Option containing the keys in a perfect elimination ordering, None if the Cluster is not chordal.
+pub fn perfect_elimination_ordering<K, N, C>(cluster: &C) -> Option<Vec<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let adj: Vec<HashSet<usize>> = graph
+        .adj
+        .iter()
+        .map(|n| n.iter().copied().collect())
+        .collect();
+    let order = lex_bfs(&adj);
+    let mut position = vec![0; order.len()];
+    for (i, v) in order.iter().enumerate() {
+        position[*v] = i;
+    }
+    for v in &order {
+        let earlier: Vec<usize> = adj[*v]
+            .iter()
+            .filter(|u| position[**u] < position[*v])
+            .copied()
+            .collect();
+        let Some(parent) = earlier.iter().max_by_key(|u| position[**u]).copied() else {
+            continue;
+        };
+        if earlier
+            .iter()
+            .any(|u| *u != parent && !adj[parent].contains(u))
+        {
+            return None;
+        }
+    }
+    Some(order.iter().rev().map(|v| graph.keys[*v].clone()).collect())
+}
+
+/// Check if a Cluster is chordal, the direction of the edges being ignored.
+/// A chordal Cluster has no cycle of four nodes or more without a chord.
+/// # Parameter
+/// - cluster - The Cluster to check.
+/// # Return
+/// True if the Cluster is chordal, false otherwise.
+pub fn is_chordal<K, N, C>(cluster: &C) -> bool
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    perfect_elimination_ordering(cluster).is_some()
+}
+
+/// Lexicographic breadth-first search by partition refinement, returning the nodes in visit order.
+fn lex_bfs(adj: &[HashSet<usize>]) -> Vec<usize> {
+    let mut classes: Vec<Vec<usize>> = vec![(0..adj.len()).collect()];
+    let mut order = Vec::with_capacity(adj.len());
+    while let Some(first) = classes.first_mut() {
+        let v = first.remove(0);
+        if first.is_empty() {
+            classes.remove(0);
+        }
+        order.push(v);
+        classes = classes
+            .into_iter()
+            .flat_map(|class| {
+                let (near, far): (Vec<usize>, Vec<usize>) =
+                    class.into_iter().partition(|u| adj[v].contains(u));
+                [near, far]
+            })
+            .filter(|class| !class.is_empty())
+            .collect();
+    }
+    order
+}
@@ -0,0 +1,193 @@
+//! Cliques of a Cluster, the direction of the edges being ignored.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Lazy enumeration of the maximal cliques of a Cluster with the Bron–Kerbosch algorithm.
+/// The search uses pivoting and processes the nodes in degeneracy order, which keeps it fast on sparse Clusters.
+/// Self-loops are ignored and each edge links both of its nodes.
+pub struct MaximalCliques<K> {
+    keys: Vec<K>,
+    adj: Vec<HashSet<usize>>,
+    order: Vec<usize>,
+    position: Vec<usize>,
+    next_root: usize,
+    stack: Vec<Frame>,
+}
+
+/// State of one level of the Bron–Kerbosch recursion.
+struct Frame {
+    clique: Vec<usize>,
+    candidates: HashSet<usize>,
+    excluded: HashSet<usize>,
+    branches: Vec<usize>,
+    index: usize,
+}
+
+impl<K: Hash + Eq + Clone> MaximalCliques<K> {
+    /// Create the enumeration of the maximal cliques of a Cluster.
+    /// # Parameter
+    /// - cluster - The Cluster to search the cliques in.
+    /// # Return
+    /// The newly created MaximalCliques.
+    pub fn new<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> MaximalCliques<K> {
+        let graph = IndexedGraph::undirected(cluster);
+        let adj: Vec<HashSet<usize>> = graph
+            .adj
+            .iter()
+            .map(|n| n.iter().copied().collect())
+            .collect();
+        let order = degeneracy_order(&graph.adj);
+        let mut position = vec![0; order.len()];
+        for (i, v) in order.iter().enumerate() {
+            position[*v] = i;
+        }
+        MaximalCliques {
+            keys: graph.keys,
+            adj,
+            order,
+            position,
+            next_root: 0,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Build the frame exploring the cliques containing clique, or yield it right away if it is maximal.
+    fn enter(
+        &mut self,
+        clique: Vec<usize>,
+        candidates: HashSet<usize>,
+        excluded: HashSet<usize>,
+    ) -> Option<Vec<K>> {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                return Some(clique.iter().map(|v| self.keys[*v].clone()).collect());
+            }
+            return None;
+        }
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|u| candidates.intersection(&self.adj[**u]).count())
+            .copied()
+            .unwrap();
+        let branches = candidates
+            .iter()
+            .filter(|v| !self.adj[pivot].contains(v))
+            .copied()
+            .collect();
+        self.stack.push(Frame {
+            clique,
+            candidates,
+            excluded,
+            branches,
+            index: 0,
+        });
+        None
+    }
+}
+
+impl<K: Hash + Eq + Clone> Iterator for MaximalCliques<K> {
+    type Item = Vec<K>;
+
+    fn next(&mut self) -> Option<Vec<K>> {
+        loop {
+            let Some(frame) = self.stack.last_mut() else {
+                let root = *self.order.get(self.next_root)?;
+                self.next_root += 1;
+                let (later, earlier) = self.adj[root]
+                    .iter()
+                    .partition(|v| self.position[**v] > self.position[root]);
+                if let Some(clique) = self.enter(vec![root], later, earlier) {
+                    return Some(clique);
+                }
+                continue;
+            };
+            let Some(v) = frame.branches.get(frame.index).copied() else {
+                self.stack.pop();
+                continue;
+            };
+            frame.index += 1;
+            let mut clique = frame.clique.clone();
+            clique.push(v);
+            let candidates = frame
+                .candidates
+                .intersection(&self.adj[v])
+                .copied()
+                .collect();
+            let excluded = frame.excluded.intersection(&self.adj[v]).copied().collect();
+            frame.candidates.remove(&v);
+            frame.excluded.insert(v);
+            if let Some(clique) = self.enter(clique, candidates, excluded) {
+                return Some(clique);
+            }
+        }
+    }
+}
+
+/// Enumerate the maximal cliques of a Cluster, the direction of the edges being ignored.
+/// # Parameter
+/// - cluster - The Cluster to search the cliques in.
+/// # Return
+/// A lazy iterator over the maximal cliques, each one given as the keys of its nodes.
+pub fn maximal_cliques<K, N, C>(cluster: &C) -> MaximalCliques<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    MaximalCliques::new(cluster)
+}
+
+/// Find a clique of maximum size in a Cluster, the direction of the edges being ignored.
+/// The maximal cliques are enumerated as by maximal_cliques, the largest one being kept.
+/// # Parameter
+/// - cluster - The Cluster to search the clique in.
+/// # Return
+/// The keys of the nodes of one of the largest cliques, empty if the Cluster has no node.
+pub fn max_clique<K, N, C>(cluster: &C) -> Vec<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    MaximalCliques::new(cluster)
+        .max_by_key(|clique| clique.len())
+        .unwrap_or_default()
+}
+
+/// Order the nodes by repeatedly removing the one of smallest remaining degree.
+pub(crate) fn degeneracy_order(adj: &[Vec<usize>]) -> Vec<usize> {
+    let mut degrees: Vec<usize> = adj.iter().map(|n| n.len()).collect();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); max_degree + 1];
+    for (v, d) in degrees.iter().enumerate() {
+        buckets[*d].push(v);
+    }
+    let mut removed = vec![false; adj.len()];
+    let mut order = Vec::with_capacity(adj.len());
+    let mut lowest = 0;
+    while order.len() < adj.len() {
+        lowest = lowest.min(max_degree);
+        while buckets[lowest].is_empty() {
+            lowest += 1;
+        }
+        let v = buckets[lowest].pop().unwrap();
+        if removed[v] || degrees[v] != lowest {
+            continue;
+        }
+        removed[v] = true;
+        order.push(v);
+        for u in &adj[v] {
+            if !removed[*u] {
+                degrees[*u] -= 1;
+                buckets[degrees[*u]].push(*u);
+                lowest = lowest.min(degrees[*u]);
+            }
+        }
+    }
+    order
+}
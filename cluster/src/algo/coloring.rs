@@ -0,0 +1,97 @@
+//! Heuristic colorings of a Cluster, giving linked nodes different colors.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Coloring of the nodes of a Cluster, linked nodes having different colors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coloring<K: Hash + Eq> {
+    /// The color of each key, from 0 to color_count - 1.
+    pub colors: HashMap<K, usize>,
+    /// The number of colors used.
+    pub color_count: usize,
+}
+
+/// Color a Cluster seen as a simple undirected graph with the greedy largest-first heuristic :
+/// the nodes are taken by decreasing degree, each getting the smallest color not used by its neighbours.
+/// Self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to color.
+/// # Return
+/// The Coloring of the Cluster, using at most one color more than its maximum degree.
+pub fn greedy_coloring<K, N, C>(cluster: &C) -> Coloring<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let mut order: Vec<usize> = (0..graph.keys.len()).collect();
+    order.sort_by_key(|v| Reverse(graph.adj[*v].len()));
+    let mut colors = vec![usize::MAX; graph.keys.len()];
+    for v in order {
+        colors[v] = smallest_free_color(&graph.adj[v], &colors);
+    }
+    into_coloring(graph.keys, colors)
+}
+
+/// Color a Cluster seen as a simple undirected graph with the DSATUR heuristic of Brélaz :
+/// the next node colored is the one whose neighbours already use the most distinct colors, the degree breaking ties,
+/// and it gets the smallest color not used by its neighbours. Bipartite Clusters get at most two colors.
+/// Self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to color.
+/// # Return
+/// The Coloring of the Cluster.
+pub fn dsatur_coloring<K, N, C>(cluster: &C) -> Coloring<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let n = graph.keys.len();
+    let mut colors = vec![usize::MAX; n];
+    let mut saturations: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    let mut heap: BinaryHeap<(usize, usize, Reverse<usize>)> = (0..n)
+        .map(|v| (0, graph.adj[v].len(), Reverse(v)))
+        .collect();
+    while let Some((saturation, _, Reverse(v))) = heap.pop() {
+        if colors[v] != usize::MAX || saturation != saturations[v].len() {
+            continue;
+        }
+        let color = smallest_free_color(&graph.adj[v], &colors);
+        colors[v] = color;
+        for w in &graph.adj[v] {
+            if colors[*w] == usize::MAX && saturations[*w].insert(color) {
+                heap.push((saturations[*w].len(), graph.adj[*w].len(), Reverse(*w)));
+            }
+        }
+    }
+    into_coloring(graph.keys, colors)
+}
+
+/// Get the smallest color not used by the colored neighbours of a node.
+fn smallest_free_color(neighbours: &[usize], colors: &[usize]) -> usize {
+    let mut used = vec![false; neighbours.len() + 1];
+    for w in neighbours {
+        if let Some(slot) = used.get_mut(colors[*w]) {
+            *slot = true;
+        }
+    }
+    used.iter()
+        .position(|used| !used)
+        .unwrap_or(neighbours.len())
+}
+
+fn into_coloring<K: Hash + Eq>(keys: Vec<K>, colors: Vec<usize>) -> Coloring<K> {
+    let color_count = colors.iter().map(|c| c + 1).max().unwrap_or(0);
+    Coloring {
+        colors: keys.into_iter().zip(colors).collect(),
+        color_count,
+    }
+}
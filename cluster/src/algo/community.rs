@@ -0,0 +1,496 @@
+//! Community detection : partitions of a Cluster into groups of nodes more linked to each other than to the rest.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::algo::shortest_path::weighted_index;
+#[cfg(feature = "rand")]
+use crate::random::shuffle;
+#[cfg(feature = "rand")]
+use crate::RandomSource;
+use crate::{Cluster, Node, WeightedCluster, WeightedNode};
+
+/// Detect the communities of a Cluster seen as a simple undirected graph by asynchronous label propagation (Raghavan et al.).
+/// Each node starts with a label of its own, then the nodes are visited in a random order, each one taking the label shared
+/// by most of its neighbours, ties being broken at random. A node keeps its label when it is among the most shared ones,
+/// so the passes stop once no label changes. Self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The Cluster to partition.
+/// - rng - The source of randomness, the same seed giving the same communities.
+/// # Return
+/// The community of each key of the Cluster, numbered from 0 in the order of the keys of the Cluster.
+#[cfg(feature = "rand")]
+pub fn label_propagation<K, N, C, R>(cluster: &C, rng: &mut R) -> HashMap<K, usize>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: RandomSource + ?Sized,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let n = graph.keys.len();
+    let mut labels: Vec<usize> = (0..n).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut counts = vec![0; n];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        shuffle(&mut order, rng);
+        for v in &order {
+            let neighbours = &graph.adj[*v];
+            if neighbours.is_empty() {
+                continue;
+            }
+            let mut best = 0;
+            for w in neighbours {
+                counts[labels[*w]] += 1;
+                best = best.max(counts[labels[*w]]);
+            }
+            let mut candidates: Vec<usize> = neighbours
+                .iter()
+                .map(|w| labels[*w])
+                .filter(|label| counts[*label] == best)
+                .collect();
+            let keep = counts[labels[*v]] == best;
+            for w in neighbours {
+                counts[labels[*w]] = 0;
+            }
+            if keep {
+                continue;
+            }
+            candidates.sort_unstable();
+            candidates.dedup();
+            labels[*v] = candidates[rng.below(candidates.len())];
+            changed = true;
+        }
+    }
+    renumber(graph.keys, &labels)
+}
+
+/// Successive partitions of a Cluster found by the Louvain method, each one grouping the communities of the previous one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommunityHierarchy<K: Hash + Eq> {
+    /// The community of each key at each level, from the finest partition to the final one.
+    pub levels: Vec<HashMap<K, usize>>,
+    /// The modularity of the final partition.
+    pub modularity: f64,
+}
+
+impl<K: Hash + Eq> CommunityHierarchy<K> {
+    /// Get the final partition of the hierarchy, the one of highest modularity.
+    /// # Return
+    /// An Option containing the community of each key, None if the Cluster has no node.
+    pub fn communities(&self) -> Option<&HashMap<K, usize>> {
+        self.levels.last()
+    }
+}
+
+/// Detect the communities of a weighted Cluster with the Louvain method of Blondel et al.
+/// Each level moves the nodes one by one to the neighbouring community bringing the largest gain of modularity until none moves,
+/// then aggregates each community into a single node, the next level working on the aggregated graph, until no node moves anymore.
+/// The Cluster is seen as undirected : in a directed Cluster, the weight of a link is the sum of the weights of both of its directions.
+/// Self-loops, edges with a negative weight and edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The weighted Cluster to partition.
+/// # Return
+/// The CommunityHierarchy of the Cluster, the communities of each level being numbered from 0 in the order of the keys of the Cluster.
+pub fn louvain<K, N, C>(cluster: &C) -> CommunityHierarchy<K>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Into<f64>,
+    C: WeightedCluster<K, N>,
+{
+    let (keys, mut graph) = Aggregate::weighted(cluster);
+    let n = keys.len();
+    let mut membership: Vec<usize> = (0..n).collect();
+    let mut levels = Vec::new();
+    loop {
+        let (communities, moved) = graph.local_moving();
+        if !moved && !levels.is_empty() {
+            break;
+        }
+        for community in membership.iter_mut() {
+            *community = communities[*community];
+        }
+        levels.push(renumber(keys.clone(), &membership));
+        if !moved {
+            break;
+        }
+        graph = graph.aggregate(&communities);
+    }
+    CommunityHierarchy {
+        levels,
+        modularity: graph.modularity(),
+    }
+}
+
+/// Compute the modularity of a partition of a Cluster seen as a simple undirected graph, as label_propagation and girvan_newman see it :
+/// the share of the edges falling inside the communities, minus the share expected if the edges were rewired at random keeping the degrees.
+/// # Parameters
+/// - cluster - The Cluster partitioned.
+/// - partition - The community of each key, the keys missing from it being each in a community of its own.
+/// # Return
+/// The modularity of the partition, between -1/2 and 1, 0 if the Cluster has no edge.
+pub fn modularity<K, N, C>(cluster: &C, partition: &HashMap<K, usize>) -> f64
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let aggregate = Aggregate {
+        links: graph
+            .adj
+            .iter()
+            .map(|dsts| dsts.iter().map(|w| (*w, 1.0)).collect())
+            .collect(),
+        loops: vec![0.0; graph.keys.len()],
+    };
+    aggregate.partition_modularity(&graph.keys, partition)
+}
+
+/// Compute the modularity of a partition of a weighted Cluster, as louvain sees it :
+/// the share of the weight falling inside the communities, minus the share expected if the edges were rewired at random keeping the strengths.
+/// The Cluster is seen as undirected : in a directed Cluster, the weight of a link is the sum of the weights of both of its directions.
+/// Self-loops, edges with a negative weight and edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The weighted Cluster partitioned.
+/// - partition - The community of each key, the keys missing from it being each in a community of its own.
+/// # Return
+/// The modularity of the partition, between -1/2 and 1, 0 if the Cluster has no edge of positive weight.
+pub fn weighted_modularity<K, N, C>(cluster: &C, partition: &HashMap<K, usize>) -> f64
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Into<f64>,
+    C: WeightedCluster<K, N>,
+{
+    let (keys, aggregate) = Aggregate::weighted(cluster);
+    aggregate.partition_modularity(&keys, partition)
+}
+
+/// Lazy dendrogram of the communities of a Cluster found by the Girvan–Newman algorithm.
+/// The edge of highest betweenness is removed again and again, the betweenness being recomputed after each removal,
+/// and a partition is yielded each time a connected component splits, until no edge is left.
+/// The Cluster is seen as a simple undirected graph, self-loops being ignored.
+pub struct GirvanNewman<K> {
+    keys: Vec<K>,
+    /// Neighbour and edge of each link of each node.
+    adj: Vec<Vec<(usize, usize)>>,
+    edges: Vec<(usize, usize)>,
+    alive: Vec<bool>,
+    remaining: usize,
+    components: usize,
+}
+
+impl<K: Hash + Eq + Clone> GirvanNewman<K> {
+    /// Create the dendrogram of the communities of a Cluster.
+    /// # Parameter
+    /// - cluster - The Cluster to partition.
+    /// # Return
+    /// The newly created GirvanNewman.
+    pub fn new<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> GirvanNewman<K> {
+        let graph = IndexedGraph::undirected(cluster);
+        let mut adj = vec![Vec::new(); graph.keys.len()];
+        let mut edges = Vec::new();
+        for (v, dsts) in graph.adj.iter().enumerate() {
+            for w in dsts.iter().filter(|w| **w > v) {
+                adj[v].push((*w, edges.len()));
+                adj[*w].push((v, edges.len()));
+                edges.push((v, *w));
+            }
+        }
+        let mut dendrogram = GirvanNewman {
+            keys: graph.keys,
+            adj,
+            alive: vec![true; edges.len()],
+            remaining: edges.len(),
+            edges,
+            components: 0,
+        };
+        dendrogram.components = dendrogram.labels().1;
+        dendrogram
+    }
+
+    /// Label each node with its connected component, the components being numbered in the order of their first node.
+    /// # Return
+    /// The label of each node and the number of components.
+    fn labels(&self) -> (Vec<usize>, usize) {
+        let n = self.keys.len();
+        let mut labels = vec![usize::MAX; n];
+        let mut count = 0;
+        for root in 0..n {
+            if labels[root] != usize::MAX {
+                continue;
+            }
+            labels[root] = count;
+            let mut queue = VecDeque::from([root]);
+            while let Some(v) = queue.pop_front() {
+                for (w, edge) in &self.adj[v] {
+                    if self.alive[*edge] && labels[*w] == usize::MAX {
+                        labels[*w] = count;
+                        queue.push_back(*w);
+                    }
+                }
+            }
+            count += 1;
+        }
+        (labels, count)
+    }
+
+    /// Compute the betweenness of each remaining edge with the algorithm of Brandes.
+    fn edge_betweenness(&self) -> Vec<f64> {
+        let n = self.keys.len();
+        let mut betweenness = vec![0.0; self.edges.len()];
+        for source in 0..n {
+            let mut distances = vec![usize::MAX; n];
+            let mut paths = vec![0.0; n];
+            let mut predecessors: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+            let mut order = Vec::new();
+            distances[source] = 0;
+            paths[source] = 1.0;
+            let mut queue = VecDeque::from([source]);
+            while let Some(v) = queue.pop_front() {
+                order.push(v);
+                for (w, edge) in &self.adj[v] {
+                    if !self.alive[*edge] {
+                        continue;
+                    }
+                    if distances[*w] == usize::MAX {
+                        distances[*w] = distances[v] + 1;
+                        queue.push_back(*w);
+                    }
+                    if distances[*w] == distances[v] + 1 {
+                        paths[*w] += paths[v];
+                        predecessors[*w].push((v, *edge));
+                    }
+                }
+            }
+            let mut dependencies = vec![0.0; n];
+            while let Some(w) = order.pop() {
+                for (v, edge) in &predecessors[w] {
+                    let share = paths[*v] / paths[w] * (1.0 + dependencies[w]);
+                    betweenness[*edge] += share;
+                    dependencies[*v] += share;
+                }
+            }
+        }
+        betweenness
+    }
+}
+
+impl<K: Hash + Eq + Clone> Iterator for GirvanNewman<K> {
+    type Item = HashMap<K, usize>;
+
+    fn next(&mut self) -> Option<HashMap<K, usize>> {
+        while self.remaining > 0 {
+            let betweenness = self.edge_betweenness();
+            let mut highest: Option<usize> = None;
+            for edge in (0..self.edges.len()).filter(|edge| self.alive[*edge]) {
+                if highest.is_none_or(|highest| betweenness[edge] > betweenness[highest]) {
+                    highest = Some(edge);
+                }
+            }
+            let edge = highest.expect("An edge remains.");
+            self.alive[edge] = false;
+            self.remaining -= 1;
+            let (labels, count) = self.labels();
+            if count > self.components {
+                self.components = count;
+                return Some(self.keys.iter().cloned().zip(labels).collect());
+            }
+        }
+        None
+    }
+}
+
+/// Build the dendrogram of the communities of a Cluster with the Girvan–Newman algorithm, seeing it as a simple undirected graph.
+/// Each betweenness computation takes O(nm) time, so it suits small and medium Clusters.
+/// # Parameter
+/// - cluster - The Cluster to partition.
+/// # Return
+/// A lazy iterator over the partitions, each one having one more community than the previous one,
+/// the communities being numbered from 0 in the order of the keys of the Cluster.
+pub fn girvan_newman<K, N, C>(cluster: &C) -> GirvanNewman<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    GirvanNewman::new(cluster)
+}
+
+/// Symmetric weighted graph on which the Louvain method works, the nodes being communities of the previous level.
+struct Aggregate {
+    /// Weight of the links toward the other nodes.
+    links: Vec<Vec<(usize, f64)>>,
+    /// Weight of the links inside each node, every link between two merged nodes being counted in both directions.
+    loops: Vec<f64>,
+}
+
+impl Aggregate {
+    /// Build the symmetric graph of a weighted Cluster, along with the key of each node.
+    fn weighted<K, N, C>(cluster: &C) -> (Vec<K>, Aggregate)
+    where
+        K: Hash + Eq + Clone,
+        N: WeightedNode<K>,
+        N::Weight: Into<f64>,
+        C: WeightedCluster<K, N>,
+    {
+        let index = weighted_index(cluster);
+        let n = index.keys.len();
+        let mut links: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
+        for (v, dsts) in index.adj.iter().enumerate() {
+            for (w, weight) in dsts {
+                let weight: f64 = (*weight).into();
+                if *w == v || weight < 0.0 {
+                    continue;
+                }
+                *links[v].entry(*w).or_default() += weight;
+                if cluster.is_directed() {
+                    *links[*w].entry(v).or_default() += weight;
+                }
+            }
+        }
+        let aggregate = Aggregate {
+            links: links.into_iter().map(|l| l.into_iter().collect()).collect(),
+            loops: vec![0.0; n],
+        };
+        (index.keys, aggregate)
+    }
+
+    /// Compute the modularity of a partition of the nodes, given by the community of their keys.
+    fn partition_modularity<K: Hash + Eq>(&self, keys: &[K], partition: &HashMap<K, usize>) -> f64 {
+        let mut ids: HashMap<usize, usize> = HashMap::new();
+        let mut count = 0;
+        let communities: Vec<usize> = keys
+            .iter()
+            .map(|key| {
+                let id = match partition.get(key) {
+                    Some(community) => *ids.entry(*community).or_insert(count),
+                    None => count,
+                };
+                if id == count {
+                    count += 1;
+                }
+                id
+            })
+            .collect();
+        self.aggregate(&communities).modularity()
+    }
+
+    fn degrees(&self) -> Vec<f64> {
+        self.links
+            .iter()
+            .zip(&self.loops)
+            .map(|(links, inner)| inner + links.iter().map(|(_, w)| w).sum::<f64>())
+            .collect()
+    }
+
+    /// Move each node to the neighbouring community of largest modularity gain until none moves.
+    /// # Return
+    /// The community of each node, numbered from 0 in the order of the nodes, and whether a node moved.
+    fn local_moving(&self) -> (Vec<usize>, bool) {
+        let n = self.links.len();
+        let degrees = self.degrees();
+        let total: f64 = degrees.iter().sum();
+        let mut communities: Vec<usize> = (0..n).collect();
+        let mut totals = degrees.clone();
+        let mut shared = vec![0.0; n];
+        let mut moved = false;
+        let mut improved = total > 0.0;
+        while improved {
+            improved = false;
+            for v in 0..n {
+                let current = communities[v];
+                totals[current] -= degrees[v];
+                let mut neighbours = vec![current];
+                for (w, weight) in &self.links[v] {
+                    let community = communities[*w];
+                    if shared[community] == 0.0 && community != current {
+                        neighbours.push(community);
+                    }
+                    shared[community] += weight;
+                }
+                let gain =
+                    |community: usize| shared[community] - totals[community] * degrees[v] / total;
+                let mut best = current;
+                let mut best_gain = gain(current);
+                for community in &neighbours[1..] {
+                    let candidate = gain(*community);
+                    if candidate > best_gain + 1e-12 {
+                        (best, best_gain) = (*community, candidate);
+                    }
+                }
+                for community in neighbours {
+                    shared[community] = 0.0;
+                }
+                totals[best] += degrees[v];
+                if best != current {
+                    communities[v] = best;
+                    improved = true;
+                    moved = true;
+                }
+            }
+        }
+        let mut ids = vec![usize::MAX; n];
+        let mut count = 0;
+        for community in communities.iter_mut() {
+            if ids[*community] == usize::MAX {
+                ids[*community] = count;
+                count += 1;
+            }
+            *community = ids[*community];
+        }
+        (communities, moved)
+    }
+
+    /// Merge the nodes of each community into a single node.
+    fn aggregate(&self, communities: &[usize]) -> Aggregate {
+        let count = communities.iter().map(|c| c + 1).max().unwrap_or(0);
+        let mut links: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        let mut loops = vec![0.0; count];
+        for (v, community) in communities.iter().enumerate() {
+            loops[*community] += self.loops[v];
+            for (w, weight) in &self.links[v] {
+                if communities[*w] == *community {
+                    loops[*community] += weight;
+                } else {
+                    *links[*community].entry(communities[*w]).or_default() += weight;
+                }
+            }
+        }
+        Aggregate {
+            links: links.into_iter().map(|l| l.into_iter().collect()).collect(),
+            loops,
+        }
+    }
+
+    /// Compute the modularity of the partition putting each node in a community of its own.
+    fn modularity(&self) -> f64 {
+        let degrees = self.degrees();
+        let total: f64 = degrees.iter().sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        self.loops
+            .iter()
+            .zip(&degrees)
+            .map(|(inner, degree)| inner / total - (degree / total).powi(2))
+            .sum()
+    }
+}
+
+/// Number the communities of the nodes from 0, in the order of their first node.
+fn renumber<K: Hash + Eq>(keys: Vec<K>, labels: &[usize]) -> HashMap<K, usize> {
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    keys.into_iter()
+        .zip(labels)
+        .map(|(key, label)| {
+            let next = ids.len();
+            (key, *ids.entry(*label).or_insert(next))
+        })
+        .collect()
+}
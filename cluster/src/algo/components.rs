@@ -0,0 +1,85 @@
+//! Connected components of a Cluster, the direction of the edges being ignored.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Label the connected components of a Cluster, each edge linking both of its nodes whatever its direction.
+/// Edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to split.
+/// # Return
+/// The component label of each key, labels going from 0 to the number of components excluded, and the number of components.
+pub fn connected_components<K, N, C>(cluster: &C) -> (HashMap<K, usize>, usize)
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let n = graph.keys.len();
+    let mut labels: Vec<Option<usize>> = vec![None; n];
+    let mut count = 0;
+    for root in 0..n {
+        if labels[root].is_some() {
+            continue;
+        }
+        labels[root] = Some(count);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            for w in &graph.adj[v] {
+                if labels[*w].is_none() {
+                    labels[*w] = Some(count);
+                    stack.push(*w);
+                }
+            }
+        }
+        count += 1;
+    }
+    let labels = graph
+        .keys
+        .into_iter()
+        .zip(labels.into_iter().flatten())
+        .collect();
+    (labels, count)
+}
+
+/// Check if every node of a Cluster can reach every other one, the direction of the edges being ignored.
+/// # Parameter
+/// - cluster - The Cluster to check.
+/// # Return
+/// True if the Cluster has at most one connected component, false otherwise.
+pub fn is_connected<K, N, C>(cluster: &C) -> bool
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    connected_components(cluster).1 <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster};
+
+    #[test]
+    fn edges_link_their_nodes_whatever_their_direction() {
+        let mut cluster: HashCluster<u32, BasicNode<u32>> =
+            [(0, 1), (2, 1), (3, 4)].into_iter().collect();
+        cluster.insert(5, BasicNode::new(()));
+        let (labels, count) = connected_components(&cluster);
+        assert_eq!(count, 3);
+        assert_eq!(labels[&0], labels[&2]);
+        assert_ne!(labels[&0], labels[&3]);
+        assert_ne!(labels[&4], labels[&5]);
+        assert!(labels.values().all(|label| *label < count));
+        assert!(!is_connected(&cluster));
+        cluster.add_edge(1, 3).unwrap();
+        cluster.add_edge(5, 0).unwrap();
+        assert!(is_connected(&cluster));
+        assert!(is_connected(&HashCluster::<u32, BasicNode<u32>>::new()));
+    }
+}
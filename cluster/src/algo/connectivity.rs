@@ -0,0 +1,369 @@
+//! Connectivity of a Cluster : weak points and queries maintained under edge insertions.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{BasicNode, Cluster, Node, UndirectedHashCluster};
+
+/// Find the bridges of a Cluster seen as a simple undirected graph, the edges whose removal disconnects their ends.
+/// # Parameter
+/// - cluster - The Cluster to analyse.
+/// # Return
+/// The (source, destination) pairs of the bridges, each bridge being reported once.
+pub fn bridges<K, N, C>(cluster: &C) -> Vec<(K, K)>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    low_links(&graph.adj)
+        .bridges
+        .into_iter()
+        .map(|(v, w)| (graph.keys[v].clone(), graph.keys[w].clone()))
+        .collect()
+}
+
+/// Find the articulation points of a Cluster seen as a simple undirected graph, the nodes whose removal disconnects the others.
+/// # Parameter
+/// - cluster - The Cluster to analyse.
+/// # Return
+/// The keys of the articulation points.
+pub fn articulation_points<K, N, C>(cluster: &C) -> HashSet<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    low_links(&graph.adj)
+        .cut_vertices
+        .into_iter()
+        .map(|v| graph.keys[v].clone())
+        .collect()
+}
+
+/// Group the edges of a Cluster seen as a simple undirected graph into its biconnected components.
+/// Two edges share a component when they lie on a common simple cycle, a bridge forming a component on its own.
+/// # Parameter
+/// - cluster - The Cluster to split.
+/// # Return
+/// The (source, destination) pairs of the edges of each component, each edge being reported once.
+pub fn biconnected_components<K, N, C>(cluster: &C) -> Vec<Vec<(K, K)>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    low_links(&graph.adj)
+        .blocks
+        .into_iter()
+        .map(|block| {
+            block
+                .into_iter()
+                .map(|(v, w)| (graph.keys[v].clone(), graph.keys[w].clone()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Node of a block-cut tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockCutNode<K> {
+    /// A biconnected component, holding the keys of its nodes.
+    Block(Vec<K>),
+    /// An articulation point, linked to every block it belongs to.
+    Cut(K),
+}
+
+/// Build the block-cut tree of a Cluster seen as a simple undirected graph.
+/// Each biconnected component and each articulation point becomes a node, a point being linked to the components holding it.
+/// The blocks are numbered first, in the order of biconnected_components, then the articulation points.
+/// The result is a forest, with one tree per connected component having at least one edge.
+/// # Parameter
+/// - cluster - The Cluster to decompose.
+/// # Return
+/// The block-cut tree, as an undirected Cluster.
+pub fn block_cut_tree<K, N, C>(
+    cluster: &C,
+) -> UndirectedHashCluster<usize, BasicNode<usize, BlockCutNode<K>>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let links = low_links(&graph.adj);
+    let mut tree = UndirectedHashCluster::new();
+    let mut members = Vec::with_capacity(links.blocks.len());
+    for block in &links.blocks {
+        let mut nodes: Vec<usize> = block.iter().flat_map(|(v, w)| [*v, *w]).collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+        let keys = nodes.iter().map(|v| graph.keys[*v].clone()).collect();
+        tree.insert(tree.len(), BasicNode::new(BlockCutNode::Block(keys)));
+        members.push(nodes);
+    }
+    for cut in links.cut_vertices {
+        let node = tree.len();
+        tree.insert(
+            node,
+            BasicNode::new(BlockCutNode::Cut(graph.keys[cut].clone())),
+        );
+        for (block, nodes) in members.iter().enumerate() {
+            if nodes.binary_search(&cut).is_ok() {
+                tree.add_edge(node, block)
+                    .expect("Both nodes of the tree exist.");
+            }
+        }
+    }
+    tree
+}
+
+/// Bridges, cut vertices and biconnected components of an indexed simple undirected graph.
+struct LowLinks {
+    bridges: Vec<(usize, usize)>,
+    cut_vertices: Vec<usize>,
+    blocks: Vec<Vec<(usize, usize)>>,
+}
+
+/// Tarjan's low-link depth-first search of a simple undirected graph.
+/// The recursion is replaced by an explicit stack so deep graphs do not overflow the call stack.
+fn low_links(adj: &[Vec<usize>]) -> LowLinks {
+    let n = adj.len();
+    let mut discovery: Vec<Option<usize>> = vec![None; n];
+    let mut low = vec![0; n];
+    let mut is_cut = vec![false; n];
+    let mut links = LowLinks {
+        bridges: Vec::new(),
+        cut_vertices: Vec::new(),
+        blocks: Vec::new(),
+    };
+    let mut edges = Vec::new();
+    let mut counter = 0;
+    for root in 0..n {
+        if discovery[root].is_some() {
+            continue;
+        }
+        discovery[root] = Some(counter);
+        low[root] = counter;
+        counter += 1;
+        let mut root_children = 0;
+        let mut calls: Vec<(usize, Option<usize>, usize)> = vec![(root, None, 0)];
+        while let Some((v, parent, edge)) = calls.last_mut() {
+            let (v, parent) = (*v, *parent);
+            if let Some(w) = adj[v].get(*edge).copied() {
+                *edge += 1;
+                match discovery[w] {
+                    None => {
+                        discovery[w] = Some(counter);
+                        low[w] = counter;
+                        counter += 1;
+                        if v == root {
+                            root_children += 1;
+                        }
+                        edges.push((v, w));
+                        calls.push((w, Some(v), 0));
+                    }
+                    Some(index) if Some(w) != parent && Some(index) < discovery[v] => {
+                        low[v] = low[v].min(index);
+                        edges.push((v, w));
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+            calls.pop();
+            let Some(parent) = parent else {
+                continue;
+            };
+            low[parent] = low[parent].min(low[v]);
+            if Some(low[v]) > discovery[parent] {
+                links.bridges.push((parent, v));
+            }
+            if Some(low[v]) >= discovery[parent] {
+                if parent != root {
+                    is_cut[parent] = true;
+                }
+                let mut block = Vec::new();
+                while let Some(edge) = edges.pop() {
+                    block.push(edge);
+                    if edge == (parent, v) {
+                        break;
+                    }
+                }
+                links.blocks.push(block);
+            }
+        }
+        if root_children > 1 {
+            is_cut[root] = true;
+        }
+    }
+    links.cut_vertices = (0..n).filter(|v| is_cut[*v]).collect();
+    links
+}
+
+/// Partition of keys into disjoint sets, merged as edges arrive, with path compression and union by rank.
+/// Answers "are a and b connected ?" in quasi-constant time without traversing a Cluster again.
+/// Sets can only be merged : removing an edge is not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisjointSet<K: Hash + Eq> {
+    index: HashMap<K, usize>,
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    set_count: usize,
+}
+
+impl<K: Hash + Eq + Clone> DisjointSet<K> {
+    /// Create an empty DisjointSet.
+    /// # Return
+    /// The newly created DisjointSet.
+    pub fn new() -> DisjointSet<K> {
+        DisjointSet {
+            index: HashMap::new(),
+            parents: Vec::new(),
+            ranks: Vec::new(),
+            set_count: 0,
+        }
+    }
+
+    /// Build the DisjointSet of a Cluster, each set holding the keys of a connected component.
+    /// The direction of the edges is ignored, edges toward keys not in the Cluster too.
+    /// # Parameter
+    /// - cluster - The Cluster whose components are computed.
+    /// # Return
+    /// The newly built DisjointSet.
+    pub fn from_cluster<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> DisjointSet<K> {
+        let mut sets = DisjointSet::new();
+        for key in cluster.keys() {
+            sets.insert(key.clone());
+        }
+        for src in cluster.keys() {
+            for dst in cluster.get_adj(src).into_iter().flatten() {
+                if cluster.contains_key(dst) {
+                    sets.union(src.clone(), dst.clone());
+                }
+            }
+        }
+        sets
+    }
+
+    /// Add a key in a set of its own.
+    /// # Parameter
+    /// - key - The key to add.
+    /// # Return
+    /// True if the key was added, false if it was already there.
+    pub fn insert(&mut self, key: K) -> bool {
+        if self.index.contains_key(&key) {
+            return false;
+        }
+        self.index.insert(key, self.parents.len());
+        self.parents.push(self.parents.len());
+        self.ranks.push(0);
+        self.set_count += 1;
+        true
+    }
+
+    /// Merge the sets holding a and b, the missing keys being added first.
+    /// # Parameters
+    /// - a - The key of the first node.
+    /// - b - The key of the second node.
+    /// # Return
+    /// True if two sets were merged, false if a and b were already in the same set.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.index_of(a);
+        let b = self.index_of(b);
+        let (a, b) = (self.root(a), self.root(b));
+        if a == b {
+            return false;
+        }
+        let (child, parent) = if self.ranks[a] < self.ranks[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[child] = parent;
+        if self.ranks[child] == self.ranks[parent] {
+            self.ranks[parent] += 1;
+        }
+        self.set_count -= 1;
+        true
+    }
+
+    /// Check if two keys are in the same set.
+    /// # Parameters
+    /// - a - The key of the first node.
+    /// - b - The key of the second node.
+    /// # Return
+    /// True if a and b are in the same set, false otherwise or if a key is unknown.
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.index.get(a).copied(), self.index.get(b).copied()) {
+            (Some(a), Some(b)) => self.root(a) == self.root(b),
+            _ => false,
+        }
+    }
+
+    /// Get the identifier of the set holding a key, shared by all the keys of the set until it is merged again.
+    /// # Parameter
+    /// - key - The key to look up.
+    /// # Return
+    /// An Option containing the identifier of the set, None if the key is unknown.
+    pub fn find(&mut self, key: &K) -> Option<usize> {
+        let index = *self.index.get(key)?;
+        Some(self.root(index))
+    }
+
+    /// Get the number of sets.
+    /// # Return
+    /// The number of disjoint sets, one per connected component.
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    /// Get the number of keys.
+    /// # Return
+    /// The number of keys in all the sets.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Check if the DisjointSet holds no key.
+    /// # Return
+    /// True if there is no key, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Get the index of a key, adding it in a set of its own if needed.
+    fn index_of(&mut self, key: K) -> usize {
+        match self.index.get(&key) {
+            Some(index) => *index,
+            None => {
+                self.insert(key);
+                self.parents.len() - 1
+            }
+        }
+    }
+
+    /// Find the root of the tree holding an index, pointing every index met directly to it.
+    fn root(&mut self, mut index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        while self.parents[index] != root {
+            let next = self.parents[index];
+            self.parents[index] = root;
+            index = next;
+        }
+        root
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for DisjointSet<K> {
+    fn default() -> DisjointSet<K> {
+        DisjointSet::new()
+    }
+}
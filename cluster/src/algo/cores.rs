@@ -0,0 +1,217 @@
+//! Dense cores and trusses of a Cluster, found by peeling its nodes of lowest degree or its edges of lowest support.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Compute the core number of each node of a Cluster seen as a simple undirected graph with the algorithm of Batagelj and Zaversnik :
+/// a node has coreness k if it belongs to the k-core, the largest subgraph whose nodes all have at least k neighbours in it, but not to the (k + 1)-core.
+/// The nodes are peeled by increasing degree from buckets, in O(n + m) time. Self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to decompose.
+/// # Return
+/// The core number of each key of the Cluster.
+pub fn core_numbers<K, N, C>(cluster: &C) -> HashMap<K, usize>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let cores = peel(&graph.adj);
+    graph.keys.into_iter().zip(cores).collect()
+}
+
+/// Extract the k-core of a Cluster seen as a simple undirected graph : the subgraph induced by the nodes of core number at least k.
+/// The Cluster is cloned and the other nodes are removed from the clone, along with the edges toward them.
+/// # Parameters
+/// - cluster - The Cluster to prune.
+/// - k - The minimum number of neighbours of the nodes kept, within the kept nodes.
+/// # Return
+/// The k-core of the Cluster, empty if no subgraph has all its degrees at least k.
+pub fn k_core<K, N, C>(cluster: &C, k: usize) -> C
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N> + Clone,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let cores = peel(&graph.adj);
+    let mut core = cluster.clone();
+    core.remove_many(
+        graph
+            .keys
+            .into_iter()
+            .zip(cores)
+            .filter(|(_, coreness)| *coreness < k)
+            .map(|(key, _)| key),
+    );
+    core
+}
+
+/// Compute the truss number of each edge of a Cluster seen as a simple undirected graph :
+/// an edge has truss number k if it belongs to the k-truss, the largest subgraph whose edges all close at least k - 2 triangles in it,
+/// but not to the (k + 1)-truss. Every edge belongs to the 2-truss.
+/// The edges are peeled by increasing number of triangles from buckets. Self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to decompose.
+/// # Return
+/// The (source, destination, truss number) triples of the edges, each edge being reported once.
+pub fn truss_numbers<K, N, C>(cluster: &C) -> Vec<(K, K, usize)>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let (edges, trusses) = peel_edges(&graph.adj);
+    edges
+        .into_iter()
+        .zip(trusses)
+        .map(|((v, w), truss)| (graph.keys[v].clone(), graph.keys[w].clone(), truss))
+        .collect()
+}
+
+/// Extract the k-truss of a Cluster seen as a simple undirected graph : the subgraph made of the edges of truss number at least k and of their ends.
+/// The Cluster is cloned, then the other edges between its nodes and the nodes left without any of the kept edges are removed from the clone.
+/// # Parameters
+/// - cluster - The Cluster to prune.
+/// - k - The minimum truss number of the edges kept, each of them closing at least k - 2 triangles within the kept edges.
+/// # Return
+/// The k-truss of the Cluster, empty if no edge has a truss number of at least k.
+pub fn k_truss<K, N, C>(cluster: &C, k: usize) -> C
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N> + Clone,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let (edges, trusses) = peel_edges(&graph.adj);
+    let mut kept: HashSet<(K, K)> = HashSet::new();
+    for ((v, w), truss) in edges.into_iter().zip(trusses) {
+        if truss >= k {
+            kept.insert((graph.keys[v].clone(), graph.keys[w].clone()));
+            kept.insert((graph.keys[w].clone(), graph.keys[v].clone()));
+        }
+    }
+    let mut truss = cluster.clone();
+    for key in &graph.keys {
+        if let Some(node) = truss.get_mut(key) {
+            node.retain_adj(|dst| {
+                !cluster.contains_key(dst) || kept.contains(&(key.clone(), dst.clone()))
+            });
+        }
+    }
+    let ends: HashSet<&K> = kept.iter().map(|(src, _)| src).collect();
+    let isolated: Vec<K> = graph
+        .keys
+        .iter()
+        .filter(|key| !ends.contains(key))
+        .cloned()
+        .collect();
+    truss.remove_many(isolated);
+    truss
+}
+
+/// Peel a simple undirected graph by removing the edge closing the fewest remaining triangles until none is left.
+/// # Return
+/// The edges of the graph, each one as (lower index, higher index), and the truss number of each of them.
+fn peel_edges(adj: &[Vec<usize>]) -> (Vec<(usize, usize)>, Vec<usize>) {
+    let mut neighbours: Vec<HashSet<usize>> =
+        adj.iter().map(|n| n.iter().copied().collect()).collect();
+    let mut edges = Vec::new();
+    let mut ids: HashMap<(usize, usize), usize> = HashMap::new();
+    for (v, dsts) in adj.iter().enumerate() {
+        for w in dsts.iter().filter(|w| **w > v) {
+            ids.insert((v, *w), edges.len());
+            edges.push((v, *w));
+        }
+    }
+    let common = |neighbours: &[HashSet<usize>], v: usize, w: usize| -> Vec<usize> {
+        let (small, large) = if neighbours[v].len() <= neighbours[w].len() {
+            (&neighbours[v], &neighbours[w])
+        } else {
+            (&neighbours[w], &neighbours[v])
+        };
+        small
+            .iter()
+            .filter(|u| large.contains(u))
+            .copied()
+            .collect()
+    };
+    let mut supports: Vec<usize> = edges
+        .iter()
+        .map(|(v, w)| common(&neighbours, *v, *w).len())
+        .collect();
+    let max_support = supports.iter().copied().max().unwrap_or(0);
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); max_support + 1];
+    for (edge, support) in supports.iter().enumerate() {
+        buckets[*support].push(edge);
+    }
+    let mut trusses = vec![usize::MAX; edges.len()];
+    let mut floor = 0;
+    let mut lowest = 0;
+    let mut removed = 0;
+    while removed < edges.len() {
+        while buckets[lowest].is_empty() {
+            lowest += 1;
+        }
+        let edge = buckets[lowest].pop().expect("The bucket is not empty.");
+        if trusses[edge] != usize::MAX || supports[edge] != lowest {
+            continue;
+        }
+        floor = floor.max(lowest);
+        trusses[edge] = floor + 2;
+        removed += 1;
+        let (v, w) = edges[edge];
+        for u in common(&neighbours, v, w) {
+            for side in [(u.min(v), u.max(v)), (u.min(w), u.max(w))] {
+                let other = ids[&side];
+                supports[other] -= 1;
+                buckets[supports[other]].push(other);
+                lowest = lowest.min(supports[other]);
+            }
+        }
+        neighbours[v].remove(&w);
+        neighbours[w].remove(&v);
+    }
+    (edges, trusses)
+}
+
+/// Peel a simple undirected graph by removing the node of lowest remaining degree until none is left.
+/// # Return
+/// The core number of each node, the highest degree floor reached when it was removed.
+fn peel(adj: &[Vec<usize>]) -> Vec<usize> {
+    let mut degrees: Vec<usize> = adj.iter().map(|n| n.len()).collect();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); max_degree + 1];
+    for (v, d) in degrees.iter().enumerate() {
+        buckets[*d].push(v);
+    }
+    let mut cores = vec![usize::MAX; adj.len()];
+    let mut floor = 0;
+    let mut lowest = 0;
+    let mut removed = 0;
+    while removed < adj.len() {
+        while buckets[lowest].is_empty() {
+            lowest += 1;
+        }
+        let v = buckets[lowest].pop().expect("The bucket is not empty.");
+        if cores[v] != usize::MAX || degrees[v] != lowest {
+            continue;
+        }
+        floor = floor.max(lowest);
+        cores[v] = floor;
+        removed += 1;
+        for u in &adj[v] {
+            if cores[*u] == usize::MAX {
+                degrees[*u] -= 1;
+                buckets[degrees[*u]].push(*u);
+                lowest = lowest.min(degrees[*u]);
+            }
+        }
+    }
+    cores
+}
@@ -0,0 +1,83 @@
+//! Extraction of the cycles of a Cluster.
+
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Find a directed cycle of a Cluster with a depth-first search, edges toward keys not in the Cluster being ignored.
+/// # Parameter
+/// - cluster - The Cluster to search.
+/// # Return
+/// An Option containing the keys of the cycle, each key having an edge toward the next one and the last toward the first, None if the Cluster is acyclic.
+pub fn find_cycle<K, N, C>(cluster: &C) -> Option<Vec<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    search(&graph, false)
+}
+
+/// Find a cycle of a Cluster seen as a simple undirected graph, each edge linking both of its nodes.
+/// Self-loops and edges going back and forth between two nodes do not form a cycle, the cycles found have at least three nodes.
+/// # Parameter
+/// - cluster - The Cluster to search.
+/// # Return
+/// An Option containing the keys of the cycle, each key being linked to the next one and the last to the first, None if the Cluster is a forest.
+pub fn find_undirected_cycle<K, N, C>(cluster: &C) -> Option<Vec<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    search(&graph, true)
+}
+
+/// Depth-first search stopping at the first edge going back to a node of the current path.
+/// In undirected mode, the edge toward the parent of a node is not taken back.
+fn search<K: Clone>(graph: &IndexedGraph<K>, undirected: bool) -> Option<Vec<K>> {
+    let n = graph.keys.len();
+    let mut on_path = vec![false; n];
+    let mut visited = vec![false; n];
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        on_path[root] = true;
+        let mut path = vec![root];
+        let mut next_edges = vec![0];
+        while let Some(&v) = path.last() {
+            let depth = path.len() - 1;
+            let Some(&w) = graph.adj[v].get(next_edges[depth]) else {
+                on_path[v] = false;
+                path.pop();
+                next_edges.pop();
+                continue;
+            };
+            next_edges[depth] += 1;
+            if on_path[w] {
+                if undirected && depth > 0 && path[depth - 1] == w {
+                    continue;
+                }
+                let start = path.iter().position(|u| *u == w).unwrap();
+                return Some(
+                    path[start..]
+                        .iter()
+                        .map(|u| graph.keys[*u].clone())
+                        .collect(),
+                );
+            }
+            if !visited[w] {
+                visited[w] = true;
+                on_path[w] = true;
+                path.push(w);
+                next_edges.push(0);
+            }
+        }
+    }
+    None
+}
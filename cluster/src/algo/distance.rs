@@ -0,0 +1,241 @@
+//! Eccentricity of the nodes of a Cluster, and the diameter and radius derived from it.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::weighted_index;
+use crate::{Cluster, Node, Weight, WeightedCluster, WeightedNode};
+
+/// Compute the eccentricity of each node of a Cluster, the length in edges of the longest shortest path leaving it,
+/// with a breadth-first search per node.
+/// # Parameter
+/// - cluster - The Cluster to measure.
+/// # Return
+/// An Option containing the eccentricity of each key of the Cluster, None if a node cannot reach every other node.
+pub fn eccentricities<K, N, C>(cluster: &C) -> Option<HashMap<K, usize>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    Metric::unweighted(cluster).eccentricities()
+}
+
+/// Compute the diameter of a Cluster, the largest eccentricity of its nodes in number of edges.
+/// On an undirected Cluster, the iFUB algorithm prunes most of the breadth-first searches :
+/// the nodes are visited from the farthest of a central node, and the search stops as soon as no remaining pair can be farther apart.
+/// # Parameter
+/// - cluster - The Cluster to measure.
+/// # Return
+/// An Option containing the diameter, None if the Cluster is empty or a node cannot reach every other node.
+pub fn diameter<K, N, C>(cluster: &C) -> Option<usize>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    Metric::unweighted(cluster).diameter()
+}
+
+/// Compute the radius of a Cluster, the smallest eccentricity of its nodes in number of edges.
+/// # Parameter
+/// - cluster - The Cluster to measure.
+/// # Return
+/// An Option containing the radius, None if the Cluster is empty or a node cannot reach every other node.
+pub fn radius<K, N, C>(cluster: &C) -> Option<usize>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    Metric::unweighted(cluster).radius()
+}
+
+/// Compute the eccentricity of each node of a weighted Cluster, the length of the longest shortest path leaving it,
+/// with the algorithm of Dijkstra from each node. Edges with a negative weight are ignored.
+/// # Parameter
+/// - cluster - The weighted Cluster to measure.
+/// # Return
+/// An Option containing the eccentricity of each key of the Cluster, None if a node cannot reach every other node.
+pub fn weighted_eccentricities<K, N, C>(cluster: &C) -> Option<HashMap<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    Metric::weighted(cluster).eccentricities()
+}
+
+/// Compute the diameter of a weighted Cluster, the largest eccentricity of its nodes.
+/// On an undirected Cluster, the iFUB algorithm prunes most of the searches, as for diameter.
+/// Edges with a negative weight are ignored.
+/// # Parameter
+/// - cluster - The weighted Cluster to measure.
+/// # Return
+/// An Option containing the diameter, None if the Cluster is empty or a node cannot reach every other node.
+pub fn weighted_diameter<K, N, C>(cluster: &C) -> Option<N::Weight>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    Metric::weighted(cluster).diameter()
+}
+
+/// Compute the radius of a weighted Cluster, the smallest eccentricity of its nodes.
+/// Edges with a negative weight are ignored.
+/// # Parameter
+/// - cluster - The weighted Cluster to measure.
+/// # Return
+/// An Option containing the radius, None if the Cluster is empty or a node cannot reach every other node.
+pub fn weighted_radius<K, N, C>(cluster: &C) -> Option<N::Weight>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    Metric::weighted(cluster).radius()
+}
+
+/// Snapshot of the edges of a Cluster with their length, each edge counting for 1 in an unweighted Cluster.
+struct Metric<K, W> {
+    keys: Vec<K>,
+    adj: Vec<Vec<(usize, W)>>,
+    unit: bool,
+    symmetric: bool,
+}
+
+impl<K: Hash + Eq + Clone> Metric<K, usize> {
+    /// Index the edges of a Cluster, each of them having a length of 1.
+    fn unweighted<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> Metric<K, usize> {
+        let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+        Metric {
+            keys,
+            adj: adj
+                .into_iter()
+                .map(|dsts| dsts.into_iter().map(|dst| (dst, 1)).collect())
+                .collect(),
+            unit: true,
+            symmetric: !cluster.is_directed(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, W: Weight> Metric<K, W> {
+    /// Index the edges of a weighted Cluster, dropping those with a negative weight.
+    fn weighted<N, C>(cluster: &C) -> Metric<K, W>
+    where
+        N: WeightedNode<K, Weight = W>,
+        C: WeightedCluster<K, N>,
+    {
+        let index = weighted_index(cluster);
+        Metric {
+            keys: index.keys,
+            adj: index
+                .adj
+                .into_iter()
+                .map(|dsts| dsts.into_iter().filter(|(_, w)| *w >= W::zero()).collect())
+                .collect(),
+            unit: false,
+            symmetric: !cluster.is_directed(),
+        }
+    }
+
+    /// Compute the distances from a node to all the others, None for the unreachable ones.
+    fn distances(&self, source: usize) -> Vec<Option<W>> {
+        let mut distances = vec![None; self.keys.len()];
+        distances[source] = Some(W::zero());
+        if self.unit {
+            let mut queue = VecDeque::from([source]);
+            while let Some(v) = queue.pop_front() {
+                let next = distances[v].map(|d| d + W::one());
+                for (w, _) in &self.adj[v] {
+                    if distances[*w].is_none() {
+                        distances[*w] = next;
+                        queue.push_back(*w);
+                    }
+                }
+            }
+        } else {
+            let mut settled = vec![false; self.keys.len()];
+            let mut heap = BinaryHeap::from([MinScored(W::zero(), source)]);
+            while let Some(MinScored(d, v)) = heap.pop() {
+                if settled[v] {
+                    continue;
+                }
+                settled[v] = true;
+                for (w, weight) in &self.adj[v] {
+                    let next = d + *weight;
+                    if distances[*w].is_none_or(|current| next < current) {
+                        distances[*w] = Some(next);
+                        heap.push(MinScored(next, *w));
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    /// Compute the eccentricity of a node, None if it cannot reach every other node.
+    fn eccentricity(&self, source: usize) -> Option<W> {
+        self.distances(source)
+            .into_iter()
+            .try_fold(W::zero(), |far, d| d.map(|d| larger(far, d)))
+    }
+
+    /// Compute the eccentricity of every node, None if one of them cannot reach every other node.
+    fn eccentricities(&self) -> Option<HashMap<K, W>> {
+        (0..self.keys.len())
+            .map(|v| self.eccentricity(v).map(|e| (self.keys[v].clone(), e)))
+            .collect()
+    }
+
+    /// Compute the smallest eccentricity, None if the Cluster is empty or not strongly connected.
+    fn radius(&self) -> Option<W> {
+        let mut eccentricities = (0..self.keys.len()).map(|v| self.eccentricity(v));
+        let first = eccentricities.next()??;
+        eccentricities.try_fold(first, |near, e| e.map(|e| if e < near { e } else { near }))
+    }
+
+    /// Compute the largest eccentricity, with the iFUB pruning when the distances are symmetric.
+    fn diameter(&self) -> Option<W> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        if !self.symmetric {
+            return (0..self.keys.len()).try_fold(W::zero(), |far, v| {
+                self.eccentricity(v).map(|e| larger(far, e))
+            });
+        }
+        let center = (0..self.keys.len()).max_by_key(|v| self.adj[*v].len())?;
+        let from_center: Vec<W> = self.distances(center).into_iter().collect::<Option<_>>()?;
+        let mut order: Vec<usize> = (0..self.keys.len()).collect();
+        order.sort_by(|a, b| {
+            from_center[*b]
+                .partial_cmp(&from_center[*a])
+                .unwrap_or(Ordering::Equal)
+        });
+        let mut lower = from_center.iter().fold(W::zero(), |far, d| larger(far, *d));
+        for v in order {
+            // Every pair of nodes not processed yet is at most twice this distance apart.
+            let bound = from_center[v];
+            if lower >= bound + bound {
+                break;
+            }
+            lower = larger(lower, self.eccentricity(v)?);
+        }
+        Some(lower)
+    }
+}
+
+/// Get the larger of two distances.
+fn larger<W: PartialOrd>(a: W, b: W) -> W {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
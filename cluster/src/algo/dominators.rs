@@ -0,0 +1,219 @@
+//! Dominator tree of a Cluster seen as a control-flow graph : the nodes every path from an entry goes through.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Marker of a missing index.
+const NONE: usize = usize::MAX;
+
+/// Dominator tree of the nodes reachable from an entry node, answering dominance queries in constant time.
+/// A node dominates another when every path from the entry to the latter goes through it, a node dominating itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dominators<K: Hash + Eq> {
+    keys: Vec<K>,
+    index: HashMap<K, usize>,
+    idom: Vec<usize>,
+    enter: Vec<usize>,
+    exit: Vec<usize>,
+}
+
+/// Compute the dominator tree of a Cluster from an entry node with the semi-NCA algorithm,
+/// a variant of Lengauer–Tarjan computing the semi-dominators then walking the tree to their nearest common ancestors.
+/// The nodes which cannot be reached from the entry are left out. Edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The Cluster, usually the control-flow graph of a function.
+/// - entry - The key of the node every path starts from.
+/// # Return
+/// An Option containing the Dominators of the reachable nodes, None if entry is not in the Cluster.
+pub fn dominators<K, N, C>(cluster: &C, entry: &K) -> Option<Dominators<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+    let root = keys.iter().position(|k| k == entry)?;
+
+    // Number the reachable nodes in depth-first preorder, the rest of the computation working on these numbers.
+    let mut order = vec![NONE; keys.len()];
+    let mut vertices = vec![root];
+    let mut parent = vec![NONE];
+    order[root] = 0;
+    let mut calls = vec![(root, 0)];
+    while let Some((v, edge)) = calls.last_mut() {
+        let v = *v;
+        match adj[v].get(*edge).copied() {
+            Some(w) => {
+                *edge += 1;
+                if order[w] == NONE {
+                    order[w] = vertices.len();
+                    vertices.push(w);
+                    parent.push(order[v]);
+                    calls.push((w, 0));
+                }
+            }
+            None => {
+                calls.pop();
+            }
+        }
+    }
+    let n = vertices.len();
+    let mut preds = vec![Vec::new(); n];
+    for (i, v) in vertices.iter().enumerate() {
+        for w in &adj[*v] {
+            preds[order[*w]].push(i);
+        }
+    }
+
+    let mut semi: Vec<usize> = (0..n).collect();
+    let mut label: Vec<usize> = (0..n).collect();
+    let mut ancestor = vec![NONE; n];
+    for w in (1..n).rev() {
+        for v in &preds[w] {
+            let candidate = semi[eval(*v, &mut ancestor, &mut label, &semi)];
+            semi[w] = semi[w].min(candidate);
+        }
+        ancestor[w] = parent[w];
+    }
+    let mut idom = parent;
+    for w in 1..n {
+        let mut dominator = idom[w];
+        while dominator > semi[w] {
+            dominator = idom[dominator];
+        }
+        idom[w] = dominator;
+    }
+
+    let mut children = vec![Vec::new(); n];
+    for w in 1..n {
+        children[idom[w]].push(w);
+    }
+    let mut enter = vec![0; n];
+    let mut exit = vec![0; n];
+    let mut clock = 0;
+    let mut stack = vec![(0, false)];
+    while let Some((v, done)) = stack.pop() {
+        clock += 1;
+        if done {
+            exit[v] = clock;
+            continue;
+        }
+        enter[v] = clock;
+        stack.push((v, true));
+        stack.extend(children[v].iter().map(|w| (*w, false)));
+    }
+
+    let keys: Vec<K> = vertices.into_iter().map(|v| keys[v].clone()).collect();
+    Some(Dominators {
+        index: keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.clone(), i))
+            .collect(),
+        keys,
+        idom,
+        enter,
+        exit,
+    })
+}
+
+/// Find the node of smallest semi-dominator above a node in the forest of the processed nodes, compressing the path to it.
+fn eval(v: usize, ancestor: &mut [usize], label: &mut [usize], semi: &[usize]) -> usize {
+    if ancestor[v] == NONE {
+        return v;
+    }
+    let mut path = Vec::new();
+    let mut current = v;
+    while ancestor[ancestor[current]] != NONE {
+        path.push(current);
+        current = ancestor[current];
+    }
+    while let Some(x) = path.pop() {
+        let a = ancestor[x];
+        if semi[label[a]] < semi[label[x]] {
+            label[x] = label[a];
+        }
+        ancestor[x] = ancestor[a];
+    }
+    label[v]
+}
+
+impl<K: Hash + Eq + Clone> Dominators<K> {
+    /// Get the entry node the dominators were computed from.
+    /// # Return
+    /// The key of the entry node, root of the dominator tree.
+    pub fn root(&self) -> &K {
+        &self.keys[0]
+    }
+
+    /// Check if a node can be reached from the entry node.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// True if the node is in the dominator tree, false otherwise.
+    pub fn is_reachable(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Get the immediate dominator of a node, the closest of the nodes strictly dominating it.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the key of the immediate dominator, None for the entry node and the unreachable nodes.
+    pub fn immediate_dominator(&self, key: &K) -> Option<&K> {
+        let v = *self.index.get(key)?;
+        (v != 0).then(|| &self.keys[self.idom[v]])
+    }
+
+    /// Get the immediate dominator of every reachable node but the entry.
+    /// # Return
+    /// The map from each key to the key of its immediate dominator.
+    pub fn immediate_dominators(&self) -> HashMap<K, K> {
+        (1..self.keys.len())
+            .map(|v| (self.keys[v].clone(), self.keys[self.idom[v]].clone()))
+            .collect()
+    }
+
+    /// Check if a node dominates another, every path from the entry to the second going through the first.
+    /// # Parameters
+    /// - dominator - The key of the node which may dominate.
+    /// - dominated - The key of the node which may be dominated.
+    /// # Return
+    /// True if both nodes are reachable and dominator dominates dominated, including when they are the same node.
+    pub fn dominates(&self, dominator: &K, dominated: &K) -> bool {
+        match (self.index.get(dominator), self.index.get(dominated)) {
+            (Some(a), Some(b)) => {
+                self.enter[*a] <= self.enter[*b] && self.exit[*b] <= self.exit[*a]
+            }
+            _ => false,
+        }
+    }
+
+    /// Check if a node strictly dominates another, dominating it while being a different node.
+    /// # Parameters
+    /// - dominator - The key of the node which may dominate.
+    /// - dominated - The key of the node which may be dominated.
+    /// # Return
+    /// True if dominator dominates dominated and they are distinct.
+    pub fn strictly_dominates(&self, dominator: &K, dominated: &K) -> bool {
+        dominator != dominated && self.dominates(dominator, dominated)
+    }
+
+    /// Get all the dominators of a node, walking up the dominator tree.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the keys of the dominators from the node itself up to the entry, None if the node is unreachable.
+    pub fn dominators_of(&self, key: &K) -> Option<Vec<K>> {
+        let mut v = *self.index.get(key)?;
+        let mut chain = vec![self.keys[v].clone()];
+        while v != 0 {
+            v = self.idom[v];
+            chain.push(self.keys[v].clone());
+        }
+        Some(chain)
+    }
+}
@@ -0,0 +1,171 @@
+//! Eulerian paths and circuits, going through every edge of a Cluster exactly once.
+
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, ClusterError, Node, Result};
+
+/// Find an Eulerian circuit of a Cluster with the algorithm of Hierholzer, in time linear in the number of edges.
+/// The circuit goes through every edge exactly once, an undirected edge in either direction, and ends where it started.
+/// Edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to go through.
+/// # Return
+/// The edges of the circuit as source and destination, each starting where the previous one ends, empty if the Cluster has no edges.
+/// An error if a node has an odd degree, or unbalanced incoming and outgoing edges in a directed Cluster, or if the edges are not all connected.
+pub fn eulerian_circuit<K, N, C>(cluster: &C) -> Result<Vec<(K, K)>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = Incidence::new(cluster);
+    let unbalanced = (0..graph.len()).filter(|v| graph.excess(*v) != 0).count();
+    if unbalanced > 0 {
+        let detail = if graph.directed {
+            format!("An Eulerian circuit requires every node to have as many incoming as outgoing edges, {unbalanced} nodes do not.")
+        } else {
+            format!("An Eulerian circuit requires every node to have an even degree, {unbalanced} nodes have an odd degree.")
+        };
+        return Err(ClusterError::detailled(&detail).into());
+    }
+    let start = (0..graph.len()).find(|v| !graph.edges[*v].is_empty());
+    graph.walk(start, "circuit")
+}
+
+/// Find an Eulerian path of a Cluster with the algorithm of Hierholzer, in time linear in the number of edges.
+/// The path goes through every edge exactly once, an undirected edge in either direction.
+/// It starts at the node of odd degree, or with one more outgoing than incoming edge in a directed Cluster, if there is such a node.
+/// Edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to go through.
+/// # Return
+/// The edges of the path as source and destination, each starting where the previous one ends, empty if the Cluster has no edges.
+/// An error if more than two nodes have an odd degree, or an unbalanced pair of incoming and outgoing edges in a directed Cluster,
+/// or if the edges are not all connected.
+pub fn eulerian_path<K, N, C>(cluster: &C) -> Result<Vec<(K, K)>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = Incidence::new(cluster);
+    let excesses: Vec<i64> = (0..graph.len()).map(|v| graph.excess(v)).collect();
+    let unbalanced = excesses.iter().filter(|e| **e != 0).count();
+    let start = if graph.directed {
+        let sources = excesses.iter().filter(|e| **e == 1).count();
+        let sinks = excesses.iter().filter(|e| **e == -1).count();
+        if unbalanced > 2 || sources != sinks || sources + sinks != unbalanced {
+            return Err(ClusterError::detailled(&format!(
+                "An Eulerian path requires every node but a source and a sink to have as many incoming as outgoing edges, {unbalanced} nodes do not."
+            ))
+            .into());
+        }
+        excesses.iter().position(|e| *e == 1)
+    } else {
+        if unbalanced > 2 {
+            return Err(ClusterError::detailled(&format!(
+                "An Eulerian path requires at most two nodes of odd degree, {unbalanced} found."
+            ))
+            .into());
+        }
+        excesses.iter().position(|e| *e != 0)
+    };
+    let start = start.or_else(|| (0..graph.len()).find(|v| !graph.edges[*v].is_empty()));
+    graph.walk(start, "path")
+}
+
+/// Edges of a Cluster listed at both of their ends when undirected, each edge being designed by its index.
+struct Incidence<K> {
+    keys: Vec<K>,
+    edges: Vec<Vec<(usize, usize)>>,
+    count: usize,
+    directed: bool,
+    incoming: Vec<usize>,
+}
+
+impl<K: Hash + Eq + Clone> Incidence<K> {
+    /// Index the edges of a Cluster, an undirected edge being stored once in its Cluster but reachable from both ends.
+    fn new<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> Incidence<K> {
+        let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+        let directed = cluster.is_directed();
+        let mut edges = vec![Vec::new(); keys.len()];
+        let mut incoming = vec![0; keys.len()];
+        let mut count = 0;
+        for (src, dsts) in adj.iter().enumerate() {
+            for dst in dsts.iter().copied() {
+                if directed {
+                    edges[src].push((dst, count));
+                    incoming[dst] += 1;
+                } else if src < dst {
+                    edges[src].push((dst, count));
+                    edges[dst].push((src, count));
+                } else if src == dst {
+                    edges[src].push((src, count));
+                    edges[src].push((src, count));
+                } else {
+                    continue;
+                }
+                count += 1;
+            }
+        }
+        Incidence {
+            keys,
+            edges,
+            count,
+            directed,
+            incoming,
+        }
+    }
+
+    /// Get the number of nodes.
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Get the outgoing minus incoming edges of a node when directed, the parity of its degree otherwise.
+    fn excess(&self, v: usize) -> i64 {
+        if self.directed {
+            self.edges[v].len() as i64 - self.incoming[v] as i64
+        } else {
+            (self.edges[v].len() % 2) as i64
+        }
+    }
+
+    /// Go through every edge from the start with the algorithm of Hierholzer, failing if some edges are left unreached.
+    fn walk(&self, start: Option<usize>, kind: &str) -> Result<Vec<(K, K)>> {
+        let Some(start) = start else {
+            return Ok(Vec::new());
+        };
+        let mut used = vec![false; self.count];
+        let mut next = vec![0; self.len()];
+        let mut stack: Vec<(usize, Option<usize>)> = vec![(start, None)];
+        let mut trail = Vec::with_capacity(self.count);
+        while let Some((v, from)) = stack.last().copied() {
+            let edges = &self.edges[v];
+            while next[v] < edges.len() && used[edges[next[v]].1] {
+                next[v] += 1;
+            }
+            match edges.get(next[v]) {
+                Some((w, edge)) => {
+                    used[*edge] = true;
+                    stack.push((*w, Some(v)));
+                }
+                None => {
+                    stack.pop();
+                    if let Some(from) = from {
+                        trail.push((self.keys[from].clone(), self.keys[v].clone()));
+                    }
+                }
+            }
+        }
+        if trail.len() < self.count {
+            return Err(ClusterError::detailled(&format!(
+                "The edges of the Cluster are not all connected, so no Eulerian {kind} goes through all of them."
+            ))
+            .into());
+        }
+        trail.reverse();
+        Ok(trail)
+    }
+}
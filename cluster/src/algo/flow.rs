@@ -0,0 +1,852 @@
+//! Flows through the weighted edges of a Cluster, the weights being the capacities.
+
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Mul;
+
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::weighted_index;
+use crate::{Weight, WeightedCluster, WeightedNode};
+
+/// Maximum flow from a source to a sink, along with the flow going through each edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow<K: Hash + Eq, W> {
+    /// The total flow leaving the source.
+    pub value: W,
+    /// The flow going through each edge carrying some, keyed by (source, destination).
+    pub flows: HashMap<(K, K), W>,
+}
+
+/// Compute a maximum flow from a source to a sink with Dinic's algorithm, the weight of each edge being its capacity.
+/// Each phase builds the level graph of the shortest paths in the residual network, then saturates it with a blocking flow.
+/// In an undirected Cluster, each edge can carry its capacity in both directions, only the net flow being reported.
+/// Edges with a negative capacity, self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The weighted Cluster the flow goes through.
+/// - source - The key of the node the flow leaves.
+/// - sink - The key of the node the flow reaches.
+/// # Return
+/// An Option containing the MaxFlow, None if the source or the sink is not in the Cluster.
+pub fn max_flow<K, N, C>(cluster: &C, source: &K, sink: &K) -> Option<MaxFlow<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let mut network = Residual::new(cluster, source, sink)?;
+    let mut value = N::Weight::zero();
+    if network.source != network.sink {
+        while network.levels() {
+            while let Some(flow) = network.augment() {
+                value = value + flow;
+            }
+        }
+    }
+    Some(MaxFlow {
+        value,
+        flows: network.flows(),
+    })
+}
+
+/// Maximum flow from a source to a sink along with a minimum cut separating them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinCut<K: Hash + Eq, W> {
+    /// The maximum flow, whose value is the capacity of the cut.
+    pub flow: MaxFlow<K, W>,
+    /// The nodes still reachable from the source once the flow is pushed, the sink not being one of them.
+    pub source_side: HashSet<K>,
+    /// The (source, destination, capacity) triples of the edges going from the source side to the other side.
+    pub edges: Vec<(K, K, W)>,
+}
+
+/// Compute a maximum flow and a minimum cut from a source to a sink with the Edmonds–Karp algorithm,
+/// the weight of each edge being its capacity. The flow is pushed along shortest augmenting paths found by breadth-first searches.
+/// The source side of the cut is made of the nodes reachable from the source in the final residual network.
+/// In an undirected Cluster, each edge can carry its capacity in both directions, only the net flow being reported.
+/// Edges with a negative capacity, self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The weighted Cluster the flow goes through.
+/// - source - The key of the node the flow leaves.
+/// - sink - The key of the node the flow reaches.
+/// # Return
+/// An Option containing the MinCut, None if the source or the sink is not in the Cluster or if they are the same node.
+pub fn edmonds_karp<K, N, C>(cluster: &C, source: &K, sink: &K) -> Option<MinCut<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let mut network = Residual::new(cluster, source, sink)?;
+    if network.source == network.sink {
+        return None;
+    }
+    let mut value = N::Weight::zero();
+    while let Some(flow) = network.shortest_augment() {
+        value = value + flow;
+    }
+    network.levels();
+    let reached = |v: usize| network.level[v] != usize::MAX;
+    let source_side = (0..network.keys.len())
+        .filter(|v| reached(*v))
+        .map(|v| network.keys[v].clone())
+        .collect();
+    let edges = (0..network.arcs.len())
+        .step_by(2)
+        .filter(|arc| reached(network.arcs[arc ^ 1].0) && !reached(network.arcs[*arc].0))
+        .filter(|arc| network.capacities[*arc] > N::Weight::zero())
+        .map(|arc| {
+            (
+                network.keys[network.arcs[arc ^ 1].0].clone(),
+                network.keys[network.arcs[arc].0].clone(),
+                network.capacities[arc],
+            )
+        })
+        .collect();
+    Some(MinCut {
+        flow: MaxFlow {
+            value,
+            flows: network.flows(),
+        },
+        source_side,
+        edges,
+    })
+}
+
+/// Flow from a source to a sink of minimum total cost among the flows of its value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinCostFlow<K: Hash + Eq, W> {
+    /// The total flow leaving the source.
+    pub value: W,
+    /// The sum over the edges of their flow times their cost.
+    pub cost: W,
+    /// The flow going through each edge carrying some, keyed by (source, destination).
+    pub flows: HashMap<(K, K), W>,
+}
+
+/// Compute a flow of minimum cost from a source to a sink, by successive shortest augmenting paths.
+/// The weight of each edge is its cost per unit of flow and its capacity is given by a function.
+/// The shortest paths are searched by Dijkstra on costs reduced by node potentials, initialized by Bellman–Ford
+/// so that negative costs are supported as long as the Cluster has no cycle of negative cost.
+/// Assignment and transportation problems are solved by linking a super source to the supplies and the demands to a super sink.
+/// Edges with a negative capacity, self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The weighted Cluster the flow goes through.
+/// - source - The key of the node the flow leaves.
+/// - sink - The key of the node the flow reaches.
+/// - capacity - The function giving the capacity of the edge between two keys.
+/// - limit - The value of the flow to send, None to send the maximum flow.
+/// # Return
+/// An Option containing the MinCostFlow, whose value is below the limit if the network cannot carry it,
+/// None if the source or the sink is not in the Cluster or if they are the same node.
+pub fn min_cost_flow<K, N, C, F>(
+    cluster: &C,
+    source: &K,
+    sink: &K,
+    capacity: F,
+    limit: Option<N::Weight>,
+) -> Option<MinCostFlow<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Mul<Output = N::Weight>,
+    C: WeightedCluster<K, N>,
+    F: Fn(&K, &K) -> N::Weight,
+{
+    let mut network =
+        Residual::with_capacities(cluster, source, sink, |src, dst, _| capacity(src, dst))?;
+    if network.source == network.sink {
+        return None;
+    }
+    let zero = N::Weight::zero();
+    let n = network.keys.len();
+    let mut potentials: Vec<Option<N::Weight>> = vec![None; n];
+    potentials[network.source] = Some(zero);
+    for _ in 0..n {
+        let mut changed = false;
+        for arc in (0..network.arcs.len()).step_by(2) {
+            let (src, dst) = (network.arcs[arc ^ 1].0, network.arcs[arc].0);
+            let Some(potential) = potentials[src] else {
+                continue;
+            };
+            let distance = potential + network.weights[arc / 2];
+            if network.capacities[arc] > zero && potentials[dst].is_none_or(|d| distance < d) {
+                potentials[dst] = Some(distance);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let mut value = zero;
+    while limit.is_none_or(|limit| value < limit) {
+        let mut distances: Vec<Option<N::Weight>> = vec![None; n];
+        let mut parents = vec![usize::MAX; n];
+        distances[network.source] = Some(zero);
+        let mut heap = BinaryHeap::from([MinScored(zero, network.source)]);
+        while let Some(MinScored(distance, v)) = heap.pop() {
+            if distances[v].is_some_and(|d| d < distance) {
+                continue;
+            }
+            let Some(from) = potentials[v] else {
+                continue;
+            };
+            for arc in &network.out[v] {
+                let (dst, residual) = network.arcs[*arc];
+                let Some(to) = potentials[dst].filter(|_| residual > zero) else {
+                    continue;
+                };
+                let cost = network.weights[arc / 2];
+                let reduced = if arc % 2 == 0 {
+                    (cost + from) - to
+                } else {
+                    from - (cost + to)
+                };
+                let next = distance + reduced;
+                if distances[dst].is_none_or(|d| next < d) {
+                    distances[dst] = Some(next);
+                    parents[dst] = *arc;
+                    heap.push(MinScored(next, dst));
+                }
+            }
+        }
+        if distances[network.sink].is_none() {
+            break;
+        }
+        for (potential, distance) in potentials.iter_mut().zip(&distances) {
+            if let (Some(potential), Some(distance)) = (potential.as_mut(), distance) {
+                *potential = *potential + *distance;
+            }
+        }
+        let mut path = Vec::new();
+        let mut v = network.sink;
+        while v != network.source {
+            path.push(parents[v]);
+            v = network.arcs[parents[v] ^ 1].0;
+        }
+        let Some(flow) = network.push(&path, limit.map(|limit| limit - value)) else {
+            break;
+        };
+        value = value + flow;
+    }
+    let cost = (0..network.arcs.len())
+        .step_by(2)
+        .map(|arc| (network.capacities[arc] - network.arcs[arc].1) * network.weights[arc / 2])
+        .fold(zero, |total, cost| total + cost);
+    Some(MinCostFlow {
+        value,
+        cost,
+        flows: network.flows(),
+    })
+}
+
+/// Global minimum cut of a Cluster : the lightest set of edges whose removal disconnects it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalMinCut<K: Hash + Eq, W> {
+    /// The total weight of the edges crossing the cut.
+    pub weight: W,
+    /// The nodes on one side of the cut, the other side being made of the remaining ones.
+    pub side: HashSet<K>,
+}
+
+/// Compute a global minimum cut of a Cluster with the Stoer–Wagner algorithm, no source nor sink being chosen.
+/// Each phase orders the nodes by maximum adjacency, the cut isolating the last one being a candidate,
+/// then merges the last two nodes, until a single one remains.
+/// The Cluster is seen as undirected : in a directed Cluster, the weight of a link is the sum of the weights of both of its directions.
+/// A disconnected Cluster has a cut of weight zero between its components.
+/// Edges with a negative weight, self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The weighted Cluster to cut.
+/// # Return
+/// An Option containing the GlobalMinCut, None if the Cluster has less than two nodes.
+pub fn stoer_wagner<K, N, C>(cluster: &C) -> Option<GlobalMinCut<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let graph = weighted_index(cluster);
+    let n = graph.keys.len();
+    if n < 2 {
+        return None;
+    }
+    let zero = N::Weight::zero();
+    let mut links: Vec<HashMap<usize, N::Weight>> = vec![HashMap::new(); n];
+    for (v, dsts) in graph.adj.iter().enumerate() {
+        for (w, weight) in dsts {
+            if *w == v || *weight < zero {
+                continue;
+            }
+            let link = links[v].entry(*w).or_insert(zero);
+            *link = *link + *weight;
+            if cluster.is_directed() {
+                let link = links[*w].entry(v).or_insert(zero);
+                *link = *link + *weight;
+            }
+        }
+    }
+    let mut groups: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    let mut active: Vec<usize> = (0..n).collect();
+    let mut best: Option<N::Weight> = None;
+    let mut best_side = Vec::new();
+    while active.len() > 1 {
+        let mut added = vec![false; n];
+        let mut connection = vec![zero; n];
+        let (mut previous, mut last) = (active[0], active[0]);
+        for _ in 0..active.len() {
+            let mut next = None;
+            for v in &active {
+                if !added[*v] && next.is_none_or(|u: usize| connection[*v] > connection[u]) {
+                    next = Some(*v);
+                }
+            }
+            let v = next.expect("A node is left to add in each step.");
+            added[v] = true;
+            for (w, weight) in &links[v] {
+                if !added[*w] {
+                    connection[*w] = connection[*w] + *weight;
+                }
+            }
+            (previous, last) = (last, v);
+        }
+        if best.is_none_or(|weight| connection[last] < weight) {
+            best = Some(connection[last]);
+            best_side = groups[last].clone();
+        }
+        let merged = std::mem::take(&mut links[last]);
+        for (w, weight) in merged {
+            links[w].remove(&last);
+            if w == previous {
+                continue;
+            }
+            for (a, b) in [(previous, w), (w, previous)] {
+                let link = links[a].entry(b).or_insert(zero);
+                *link = *link + weight;
+            }
+        }
+        let members = std::mem::take(&mut groups[last]);
+        groups[previous].extend(members);
+        active.retain(|v| *v != last);
+    }
+    Some(GlobalMinCut {
+        weight: best?,
+        side: best_side
+            .into_iter()
+            .map(|v| graph.keys[v].clone())
+            .collect(),
+    })
+}
+
+/// Gomory–Hu tree of a Cluster : a weighted tree on its nodes where the minimum cut between any two nodes
+/// is the lightest edge of the path linking them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GomoryHuTree<K: Hash + Eq, W> {
+    keys: Vec<K>,
+    index: HashMap<K, usize>,
+    /// Parent of each node along with the weight of the edge toward it, None for the root.
+    parents: Vec<Option<(usize, W)>>,
+    depths: Vec<usize>,
+}
+
+impl<K: Hash + Eq + Clone, W: Weight> GomoryHuTree<K, W> {
+    /// Get the weight of a minimum cut separating two nodes, walking the path between them in the tree.
+    /// # Parameters
+    /// - a - The key of the first node.
+    /// - b - The key of the second node.
+    /// # Return
+    /// An Option containing the weight of the cut, None if a key is not in the tree or if both are the same.
+    pub fn min_cut(&self, a: &K, b: &K) -> Option<W> {
+        let (mut a, mut b) = (*self.index.get(a)?, *self.index.get(b)?);
+        let mut cut = None;
+        while a != b {
+            if self.depths[a] < self.depths[b] {
+                (a, b) = (b, a);
+            }
+            let (parent, weight) = self.parents[a]?;
+            if cut.is_none_or(|cut| weight < cut) {
+                cut = Some(weight);
+            }
+            a = parent;
+        }
+        cut
+    }
+
+    /// Get the edges of the tree, each node but the root being linked to its parent.
+    /// # Return
+    /// The (node, parent, weight) triples of the edges of the tree.
+    pub fn edges(&self) -> Vec<(K, K, W)> {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter_map(|(v, parent)| {
+                let (parent, weight) = (*parent)?;
+                Some((self.keys[v].clone(), self.keys[parent].clone(), weight))
+            })
+            .collect()
+    }
+}
+
+/// Build the Gomory–Hu tree of a Cluster with Gusfield's algorithm, computing one maximum flow per node but the first
+/// instead of contracting the Cluster. The flows are computed by Dinic's algorithm on a single residual network reset between them.
+/// The Cluster is seen as undirected : in a directed Cluster, each edge can carry its capacity in both directions.
+/// Edges with a negative weight, self-loops and edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The weighted Cluster to summarize.
+/// # Return
+/// The GomoryHuTree of the Cluster, spanning all of its nodes, its components being linked by edges of weight zero.
+pub fn gomory_hu_tree<K, N, C>(cluster: &C) -> GomoryHuTree<K, N::Weight>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let keys: Vec<K> = cluster.keys().cloned().collect();
+    let Some(first) = keys.first() else {
+        return GomoryHuTree {
+            keys,
+            index: HashMap::new(),
+            parents: Vec::new(),
+            depths: Vec::new(),
+        };
+    };
+    let mut network =
+        Residual::new(cluster, first, first).expect("The first key is in the Cluster.");
+    if cluster.is_directed() {
+        for arc in (0..network.arcs.len()).step_by(2) {
+            network.capacities[arc ^ 1] = network.capacities[arc];
+        }
+    }
+    let n = network.keys.len();
+    let zero = N::Weight::zero();
+    let mut parents: Vec<Option<(usize, N::Weight)>> = vec![Some((0, zero)); n];
+    parents[0] = None;
+    for s in 1..n {
+        let Some((t, _)) = parents[s] else {
+            continue;
+        };
+        let value = network.max_flow_between(s, t);
+        network.levels();
+        let on_side = |v: usize| network.level[v] != usize::MAX;
+        parents[s] = Some((t, value));
+        for (v, parent) in parents.iter_mut().enumerate() {
+            match parent {
+                Some((parent, _)) if v != s && *parent == t && on_side(v) => *parent = s,
+                _ => {}
+            }
+        }
+        if let Some((grandparent, weight)) = parents[t].filter(|(parent, _)| on_side(*parent)) {
+            parents[s] = Some((grandparent, weight));
+            parents[t] = Some((s, value));
+        }
+    }
+    let mut depths = vec![usize::MAX; n];
+    for v in 0..n {
+        let mut path = Vec::new();
+        let mut u = v;
+        while depths[u] == usize::MAX {
+            match parents[u] {
+                Some((parent, _)) => {
+                    path.push(u);
+                    u = parent;
+                }
+                None => depths[u] = 0,
+            }
+        }
+        while let Some(w) = path.pop() {
+            depths[w] = depths[u] + 1;
+            u = w;
+        }
+    }
+    let index = network
+        .keys
+        .iter()
+        .enumerate()
+        .map(|(i, k)| (k.clone(), i))
+        .collect();
+    GomoryHuTree {
+        keys: network.keys,
+        index,
+        parents,
+        depths,
+    }
+}
+
+/// Residual network of a Cluster, each arc being stored next to its reverse arc.
+pub(crate) struct Residual<K, W> {
+    pub(crate) keys: Vec<K>,
+    pub(crate) source: usize,
+    pub(crate) sink: usize,
+    /// Destination and remaining capacity of each arc, arc i ^ 1 being the reverse of arc i.
+    pub(crate) arcs: Vec<(usize, W)>,
+    /// Capacity of each forward arc, the reverse arcs having none.
+    pub(crate) capacities: Vec<W>,
+    /// Weight of the edge of each pair of arcs, indexed by arc / 2.
+    pub(crate) weights: Vec<W>,
+    /// Arcs leaving each node.
+    pub(crate) out: Vec<Vec<usize>>,
+    level: Vec<usize>,
+    next: Vec<usize>,
+}
+
+impl<K: Hash + Eq + Clone, W: Weight> Residual<K, W> {
+    pub(crate) fn new<N, C>(cluster: &C, source: &K, sink: &K) -> Option<Residual<K, W>>
+    where
+        N: WeightedNode<K, Weight = W>,
+        C: WeightedCluster<K, N>,
+    {
+        Self::with_capacities(cluster, source, sink, |_, _, weight| weight)
+    }
+
+    /// Build the residual network, the capacity of each edge being computed from its ends and its weight.
+    pub(crate) fn with_capacities<N, C, F>(
+        cluster: &C,
+        source: &K,
+        sink: &K,
+        capacity: F,
+    ) -> Option<Residual<K, W>>
+    where
+        N: WeightedNode<K, Weight = W>,
+        C: WeightedCluster<K, N>,
+        F: Fn(&K, &K, W) -> W,
+    {
+        let index = weighted_index(cluster);
+        let source = *index.index.get(source)?;
+        let sink = *index.index.get(sink)?;
+        let n = index.keys.len();
+        let mut arcs = Vec::new();
+        let mut capacities = Vec::new();
+        let mut weights = Vec::new();
+        let mut out = vec![Vec::new(); n];
+        for (src, dsts) in index.adj.iter().enumerate() {
+            for (dst, weight) in dsts {
+                let capacity = capacity(&index.keys[src], &index.keys[*dst], *weight);
+                if *dst == src || capacity < W::zero() {
+                    continue;
+                }
+                out[src].push(arcs.len());
+                arcs.push((*dst, capacity));
+                capacities.push(capacity);
+                out[*dst].push(arcs.len());
+                arcs.push((src, W::zero()));
+                capacities.push(W::zero());
+                weights.push(*weight);
+            }
+        }
+        Some(Residual {
+            keys: index.keys,
+            source,
+            sink,
+            arcs,
+            capacities,
+            weights,
+            out,
+            level: vec![usize::MAX; n],
+            next: vec![0; n],
+        })
+    }
+
+    /// Compute the value of a maximum flow between two nodes with Dinic's algorithm, the flow previously pushed being cleared first.
+    fn max_flow_between(&mut self, source: usize, sink: usize) -> W {
+        for (arc, capacity) in self.arcs.iter_mut().zip(&self.capacities) {
+            arc.1 = *capacity;
+        }
+        self.source = source;
+        self.sink = sink;
+        let mut value = W::zero();
+        while self.levels() {
+            while let Some(flow) = self.augment() {
+                value = value + flow;
+            }
+        }
+        value
+    }
+
+    /// Compute the distance of each node from the source in the residual network.
+    /// # Return
+    /// True if the sink can still be reached, false otherwise.
+    pub(crate) fn levels(&mut self) -> bool {
+        self.level.fill(usize::MAX);
+        self.level[self.source] = 0;
+        let mut queue = VecDeque::from([self.source]);
+        while let Some(v) = queue.pop_front() {
+            for arc in &self.out[v] {
+                let (dst, residual) = self.arcs[*arc];
+                if residual > W::zero() && self.level[dst] == usize::MAX {
+                    self.level[dst] = self.level[v] + 1;
+                    queue.push_back(dst);
+                }
+            }
+        }
+        self.next.fill(0);
+        self.level[self.sink] != usize::MAX
+    }
+
+    /// Push flow along a path of the level graph, the arcs leading to dead ends being skipped for the rest of the phase.
+    /// # Return
+    /// An Option containing the flow pushed, None if the level graph is saturated.
+    fn augment(&mut self) -> Option<W> {
+        let mut path: Vec<usize> = Vec::new();
+        let mut v = self.source;
+        while v != self.sink {
+            let advance = self.out[v][self.next[v]..].iter().position(|arc| {
+                let (dst, residual) = self.arcs[*arc];
+                residual > W::zero() && self.level[dst] == self.level[v] + 1
+            });
+            match advance {
+                Some(offset) => {
+                    self.next[v] += offset;
+                    let arc = self.out[v][self.next[v]];
+                    path.push(arc);
+                    v = self.arcs[arc].0;
+                }
+                None => {
+                    self.next[v] = self.out[v].len();
+                    let arc = path.pop()?;
+                    v = self.arcs[arc ^ 1].0;
+                    self.next[v] += 1;
+                }
+            }
+        }
+        self.push(&path, None)
+    }
+
+    /// Push flow along a shortest path of the residual network, found by a breadth-first search.
+    /// # Return
+    /// An Option containing the flow pushed, None if the sink cannot be reached anymore.
+    fn shortest_augment(&mut self) -> Option<W> {
+        let mut parent_arc = vec![usize::MAX; self.keys.len()];
+        let mut queue = VecDeque::from([self.source]);
+        while let Some(v) = queue.pop_front() {
+            if v == self.sink {
+                break;
+            }
+            for arc in &self.out[v] {
+                let (dst, residual) = self.arcs[*arc];
+                if residual > W::zero() && dst != self.source && parent_arc[dst] == usize::MAX {
+                    parent_arc[dst] = *arc;
+                    queue.push_back(dst);
+                }
+            }
+        }
+        let mut path = Vec::new();
+        let mut v = self.sink;
+        while v != self.source {
+            let arc = parent_arc[v];
+            if arc == usize::MAX {
+                return None;
+            }
+            path.push(arc);
+            v = self.arcs[arc ^ 1].0;
+        }
+        self.push(&path, None)
+    }
+
+    /// Push the bottleneck capacity of a path along its arcs, up to a limit.
+    /// # Return
+    /// An Option containing the flow pushed, None if the path is empty.
+    fn push(&mut self, path: &[usize], limit: Option<W>) -> Option<W> {
+        let flow = path
+            .iter()
+            .map(|arc| self.arcs[*arc].1)
+            .chain(limit)
+            .reduce(|a, b| if b < a { b } else { a })?;
+        for arc in path {
+            self.arcs[*arc].1 = self.arcs[*arc].1 - flow;
+            self.arcs[arc ^ 1].1 = self.arcs[arc ^ 1].1 + flow;
+        }
+        Some(flow)
+    }
+
+    /// Collect the net flow going through each edge, the flows going both ways between two nodes cancelling out.
+    pub(crate) fn flows(&self) -> HashMap<(K, K), W> {
+        let mut flows: HashMap<(usize, usize), W> = HashMap::new();
+        for arc in (0..self.arcs.len()).step_by(2) {
+            let flow = self.capacities[arc] - self.arcs[arc].1;
+            if flow > W::zero() {
+                let total = flows
+                    .entry((self.arcs[arc ^ 1].0, self.arcs[arc].0))
+                    .or_insert(W::zero());
+                *total = *total + flow;
+            }
+        }
+        let pairs: Vec<(usize, usize)> = flows.keys().copied().collect();
+        for (src, dst) in pairs {
+            let (Some(forward), Some(backward)) = (flows.get(&(src, dst)), flows.get(&(dst, src)))
+            else {
+                continue;
+            };
+            let (forward, backward) = (*forward, *backward);
+            if backward < forward {
+                flows.insert((src, dst), forward - backward);
+                flows.remove(&(dst, src));
+            } else if forward < backward {
+                flows.insert((dst, src), backward - forward);
+                flows.remove(&(src, dst));
+            } else {
+                flows.remove(&(src, dst));
+                flows.remove(&(dst, src));
+            }
+        }
+        flows
+            .into_iter()
+            .map(|((src, dst), flow)| ((self.keys[src].clone(), self.keys[dst].clone()), flow))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashCluster, UndirectedHashCluster, WeightedBasicNode};
+
+    fn directed(
+        n: u32,
+        edges: &[(u32, u32, i64)],
+    ) -> HashCluster<u32, WeightedBasicNode<u32, i64>> {
+        let mut cluster = HashCluster::new();
+        for key in 0..n {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in edges {
+            cluster.add_weighted_edge(*src, *dst, *weight).unwrap();
+        }
+        cluster
+    }
+
+    fn undirected(
+        n: u32,
+        edges: &[(u32, u32, i64)],
+    ) -> UndirectedHashCluster<u32, WeightedBasicNode<u32, i64>> {
+        let mut cluster = UndirectedHashCluster::new();
+        for key in 0..n {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in edges {
+            cluster.add_weighted_edge(*src, *dst, *weight).unwrap();
+        }
+        cluster
+    }
+
+    const NETWORK: [(u32, u32, i64); 9] = [
+        (0, 1, 16),
+        (0, 2, 13),
+        (1, 3, 12),
+        (2, 1, 4),
+        (2, 4, 14),
+        (3, 2, 9),
+        (3, 5, 20),
+        (4, 3, 7),
+        (4, 5, 4),
+    ];
+
+    #[test]
+    fn max_flow_respects_capacities_and_conservation() {
+        let cluster = directed(6, &NETWORK);
+        let flow = max_flow(&cluster, &0, &5).unwrap();
+        assert_eq!(flow.value, 23);
+        for ((src, dst), carried) in &flow.flows {
+            assert!(*carried > 0 && *carried <= cluster.weight(src, dst).unwrap());
+        }
+        for key in 1..5 {
+            let inflow: i64 = flow
+                .flows
+                .iter()
+                .filter(|((_, dst), _)| *dst == key)
+                .map(|(_, f)| f)
+                .sum();
+            let outflow: i64 = flow
+                .flows
+                .iter()
+                .filter(|((src, _), _)| *src == key)
+                .map(|(_, f)| f)
+                .sum();
+            assert_eq!(inflow, outflow);
+        }
+        assert_eq!(max_flow(&cluster, &5, &0).unwrap().value, 0);
+        assert!(max_flow(&cluster, &0, &9).is_none());
+    }
+
+    #[test]
+    fn the_min_cut_weighs_the_max_flow() {
+        let cluster = directed(6, &NETWORK);
+        let cut = edmonds_karp(&cluster, &0, &5).unwrap();
+        assert_eq!(cut.flow.value, 23);
+        assert!(cut.source_side.contains(&0) && !cut.source_side.contains(&5));
+        assert_eq!(
+            cut.edges.iter().map(|(_, _, weight)| weight).sum::<i64>(),
+            23
+        );
+        for (src, dst, _) in &cut.edges {
+            assert!(cut.source_side.contains(src) && !cut.source_side.contains(dst));
+        }
+        assert!(edmonds_karp(&cluster, &0, &0).is_none());
+    }
+
+    #[test]
+    fn min_cost_flow_fills_the_cheap_path_first() {
+        let cluster = directed(4, &[(0, 1, 1), (1, 3, 1), (0, 2, 2), (2, 3, 2)]);
+        let capacity = |src: &u32, dst: &u32| if *src == 1 || *dst == 1 { 2 } else { 3 };
+        let partial = min_cost_flow(&cluster, &0, &3, capacity, Some(4)).unwrap();
+        assert_eq!((partial.value, partial.cost), (4, 12));
+        assert_eq!(partial.flows.get(&(0, 1)), Some(&2));
+        let full = min_cost_flow(&cluster, &0, &3, capacity, None).unwrap();
+        assert_eq!((full.value, full.cost), (5, 16));
+    }
+
+    #[test]
+    fn stoer_wagner_cuts_the_bridge() {
+        let cluster = undirected(
+            6,
+            &[
+                (0, 1, 3),
+                (1, 2, 3),
+                (2, 0, 3),
+                (3, 4, 3),
+                (4, 5, 3),
+                (5, 3, 3),
+                (2, 3, 1),
+            ],
+        );
+        let cut = stoer_wagner(&cluster).unwrap();
+        assert_eq!(cut.weight, 1);
+        let mut side: Vec<u32> = cut.side.into_iter().collect();
+        side.sort();
+        assert!(side == [0, 1, 2] || side == [3, 4, 5]);
+        assert_eq!(
+            stoer_wagner(&undirected(3, &[(0, 1, 2)])).unwrap().weight,
+            0
+        );
+        assert!(stoer_wagner(&undirected(1, &[])).is_none());
+    }
+
+    #[test]
+    fn gomory_hu_tree_gives_every_pairwise_max_flow() {
+        let cluster = undirected(
+            6,
+            &[
+                (0, 1, 1),
+                (0, 2, 7),
+                (1, 2, 1),
+                (1, 3, 3),
+                (1, 4, 2),
+                (2, 4, 4),
+                (3, 4, 1),
+                (3, 5, 6),
+                (4, 5, 2),
+            ],
+        );
+        let tree = gomory_hu_tree(&cluster);
+        assert_eq!(tree.edges().len(), 5);
+        for a in 0..6 {
+            for b in 0..6 {
+                if a != b {
+                    assert_eq!(
+                        tree.min_cut(&a, &b),
+                        Some(max_flow(&cluster, &a, &b).unwrap().value)
+                    );
+                }
+            }
+        }
+        assert_eq!(tree.min_cut(&0, &9), None);
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Cluster, Node};
+
+/// Snapshot of a Cluster where each node is designed by its index, used by the algorithms doing heavy bookkeeping.
+pub(crate) struct IndexedGraph<K> {
+    pub(crate) keys: Vec<K>,
+    pub(crate) adj: Vec<Vec<usize>>,
+}
+
+impl<K: Hash + Eq + Clone> IndexedGraph<K> {
+    /// Index the edges of a Cluster as they are, edges toward keys not in the Cluster being ignored.
+    pub(crate) fn directed<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> IndexedGraph<K> {
+        let keys: Vec<K> = cluster.keys().cloned().collect();
+        let index: HashMap<K, usize> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.clone(), i))
+            .collect();
+        let adj = keys
+            .iter()
+            .map(|k| {
+                cluster
+                    .get_adj(k)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|dst| index.get(dst).copied())
+                    .collect()
+            })
+            .collect();
+        IndexedGraph { keys, adj }
+    }
+
+    /// Index the Cluster as a simple undirected graph : each edge links both of its nodes,
+    /// each neighbour appears once and self-loops are dropped.
+    pub(crate) fn undirected<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> IndexedGraph<K> {
+        let mut graph = Self::directed(cluster);
+        let mut adj = vec![Vec::new(); graph.keys.len()];
+        for (src, dsts) in graph.adj.iter().enumerate() {
+            for dst in dsts.iter().copied().filter(|dst| *dst != src) {
+                adj[src].push(dst);
+                adj[dst].push(src);
+            }
+        }
+        for neighbours in adj.iter_mut() {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+        }
+        graph.adj = adj;
+        graph
+    }
+}
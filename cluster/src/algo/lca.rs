@@ -0,0 +1,161 @@
+//! Lowest common ancestors in a rooted tree view of a Cluster.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Index answering lowest common ancestor queries in constant time, built on an Euler tour of a rooted tree and a sparse table.
+/// The tree is the breadth-first search tree of a Cluster from a root, which is the Cluster itself when it is a tree;
+/// in a directed acyclic Cluster, each node keeps a single parent, one of its closest to the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LcaIndex<K: Hash + Eq> {
+    keys: Vec<K>,
+    index: HashMap<K, usize>,
+    parents: Vec<usize>,
+    depths: Vec<usize>,
+    first: Vec<usize>,
+    tour: Vec<usize>,
+    table: Vec<Vec<usize>>,
+}
+
+impl<K: Hash + Eq + Clone> LcaIndex<K> {
+    /// Build the LcaIndex of the tree of a Cluster rooted at a node, following the edges from the root.
+    /// The nodes which cannot be reached from the root are left out, edges toward keys not in the Cluster are ignored.
+    /// # Parameters
+    /// - cluster - The Cluster holding the tree.
+    /// - root - The key of the root of the tree.
+    /// # Return
+    /// An Option containing the newly built LcaIndex, None if root is not in the Cluster.
+    pub fn new<N: Node<K>, C: Cluster<K, N>>(cluster: &C, root: &K) -> Option<LcaIndex<K>> {
+        let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+        let root = keys.iter().position(|k| k == root)?;
+        let mut order = vec![usize::MAX; keys.len()];
+        let mut vertices = vec![root];
+        let mut parents = vec![0];
+        let mut depths = vec![0];
+        let mut children: Vec<Vec<usize>> = vec![Vec::new()];
+        order[root] = 0;
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            for w in &adj[v] {
+                if order[*w] == usize::MAX {
+                    order[*w] = vertices.len();
+                    children[order[v]].push(vertices.len());
+                    parents.push(order[v]);
+                    depths.push(depths[order[v]] + 1);
+                    vertices.push(*w);
+                    children.push(Vec::new());
+                    queue.push_back(*w);
+                }
+            }
+        }
+        let n = vertices.len();
+        let mut first = vec![0; n];
+        let mut tour = Vec::with_capacity(2 * n - 1);
+        let mut stack = vec![(0, 0)];
+        while let Some((v, child)) = stack.last_mut() {
+            let v = *v;
+            if *child == 0 {
+                first[v] = tour.len();
+            }
+            tour.push(v);
+            match children[v].get(*child).copied() {
+                Some(w) => {
+                    *child += 1;
+                    stack.push((w, 0));
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+        let mut table = vec![tour.clone()];
+        let mut width = 1;
+        while 2 * width <= tour.len() {
+            let previous = &table[table.len() - 1];
+            let row = (0..=tour.len() - 2 * width)
+                .map(|i| shallower(&depths, previous[i], previous[i + width]))
+                .collect();
+            table.push(row);
+            width *= 2;
+        }
+        let keys: Vec<K> = vertices.into_iter().map(|v| keys[v].clone()).collect();
+        Some(LcaIndex {
+            index: keys
+                .iter()
+                .enumerate()
+                .map(|(i, k)| (k.clone(), i))
+                .collect(),
+            keys,
+            parents,
+            depths,
+            first,
+            tour,
+            table,
+        })
+    }
+
+    /// Get the root of the tree.
+    /// # Return
+    /// The key of the root.
+    pub fn root(&self) -> &K {
+        &self.keys[0]
+    }
+
+    /// Get the parent of a node in the tree.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the key of the parent, None for the root and the nodes not in the tree.
+    pub fn parent(&self, key: &K) -> Option<&K> {
+        let v = *self.index.get(key)?;
+        (v != 0).then(|| &self.keys[self.parents[v]])
+    }
+
+    /// Get the depth of a node in the tree, the number of edges between it and the root.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the depth, None if the node is not in the tree.
+    pub fn depth(&self, key: &K) -> Option<usize> {
+        self.index.get(key).map(|v| self.depths[*v])
+    }
+
+    /// Get the lowest common ancestor of two nodes, the deepest node of the tree having both of them below it.
+    /// # Parameters
+    /// - a - The key of the first node.
+    /// - b - The key of the second node.
+    /// # Return
+    /// An Option containing the key of the lowest common ancestor, a node being an ancestor of itself, None if a node is not in the tree.
+    pub fn lca(&self, a: &K, b: &K) -> Option<&K> {
+        let a = self.first[*self.index.get(a)?];
+        let b = self.first[*self.index.get(b)?];
+        let (start, end) = (a.min(b), a.max(b) + 1);
+        let level = (usize::BITS - 1 - (end - start).leading_zeros()) as usize;
+        let row = &self.table[level];
+        let ancestor = shallower(&self.depths, row[start], row[end - (1 << level)]);
+        Some(&self.keys[ancestor])
+    }
+
+    /// Get the number of edges of the path of the tree between two nodes, going through their lowest common ancestor.
+    /// # Parameters
+    /// - a - The key of the first node.
+    /// - b - The key of the second node.
+    /// # Return
+    /// An Option containing the distance, None if a node is not in the tree.
+    pub fn distance(&self, a: &K, b: &K) -> Option<usize> {
+        let ancestor = self.lca(a, b)?;
+        Some(self.depth(a)? + self.depth(b)? - 2 * self.depth(ancestor)?)
+    }
+}
+
+/// Get the shallower of two nodes.
+fn shallower(depths: &[usize], a: usize, b: usize) -> usize {
+    if depths[b] < depths[a] {
+        b
+    } else {
+        a
+    }
+}
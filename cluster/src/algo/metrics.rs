@@ -0,0 +1,234 @@
+//! Opt-in measurement of the cost of the algorithms.
+//!
+//! Wrapping a Cluster in an Instrumented Cluster counts the node lookups and adjacency entries read by whatever algorithm runs on it.
+//! The allocations are only counted when the CountingAllocator is installed as the global allocator of the program.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::{Cluster, Node, Result, ReverseAdjacency};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+/// Costs of a single algorithm call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Metrics {
+    /// Number of node lookups made on the Cluster.
+    pub nodes_visited: usize,
+    /// Number of adjacency entries read from the looked up nodes.
+    pub edges_relaxed: usize,
+    /// Number of allocations made by the whole program during the call, None if the CountingAllocator is not installed.
+    pub allocations: Option<usize>,
+    /// Wall time of the call.
+    pub elapsed: Duration,
+}
+
+impl Metrics {
+    /// Render the metrics as a JSON object, the wall time being given in nanoseconds.
+    /// # Return
+    /// The JSON rendering of the metrics.
+    pub fn to_json(&self) -> String {
+        let allocations = self
+            .allocations
+            .map_or(String::from("null"), |count| count.to_string());
+        format!(
+            "{{\"nodes_visited\":{},\"edges_relaxed\":{},\"allocations\":{},\"elapsed_ns\":{}}}",
+            self.nodes_visited,
+            self.edges_relaxed,
+            allocations,
+            self.elapsed.as_nanos()
+        )
+    }
+}
+
+/// Global allocator counting the allocations and reallocations it forwards to the system allocator.
+/// Install it with `#[global_allocator] static ALLOCATOR: CountingAllocator = CountingAllocator;` to fill Metrics::allocations.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn count_allocation() {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    COUNTING.store(true, Ordering::Relaxed);
+}
+
+/// Cluster counting the accesses made to the Cluster it wraps.
+/// It is a Cluster itself, so any algorithm runs on it unchanged.
+#[derive(Debug, Default)]
+pub struct Instrumented<C> {
+    inner: C,
+    nodes_visited: Cell<usize>,
+    edges_relaxed: Cell<usize>,
+}
+
+impl<C> Instrumented<C> {
+    /// Wrap a Cluster to count the accesses made to it.
+    /// # Parameter
+    /// - cluster - The Cluster to instrument.
+    /// # Return
+    /// The newly created Instrumented Cluster.
+    pub fn new(cluster: C) -> Instrumented<C> {
+        Instrumented {
+            inner: cluster,
+            nodes_visited: Cell::new(0),
+            edges_relaxed: Cell::new(0),
+        }
+    }
+
+    /// Get the wrapped Cluster, the accesses made through it being not counted.
+    /// # Return
+    /// A reference to the wrapped Cluster.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the Cluster.
+    /// # Return
+    /// The wrapped Cluster.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Run an algorithm on the Cluster and measure it.
+    /// # Parameter
+    /// - algorithm - The callback running the algorithm on the Instrumented Cluster.
+    /// # Return
+    /// The result of the algorithm and the Metrics of the call.
+    pub fn measure<R, F: FnOnce(&Self) -> R>(&self, algorithm: F) -> (R, Metrics) {
+        self.nodes_visited.set(0);
+        self.edges_relaxed.set(0);
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let result = algorithm(self);
+        let elapsed = start.elapsed();
+        let metrics = Metrics {
+            nodes_visited: self.nodes_visited.get(),
+            edges_relaxed: self.edges_relaxed.get(),
+            allocations: COUNTING
+                .load(Ordering::Relaxed)
+                .then(|| ALLOCATIONS.load(Ordering::Relaxed) - allocations),
+            elapsed,
+        };
+        (result, metrics)
+    }
+
+    fn visit<K, N: Node<K>>(&self, node: Option<&N>) {
+        if let Some(node) = node {
+            self.nodes_visited.set(self.nodes_visited.get() + 1);
+            self.edges_relaxed
+                .set(self.edges_relaxed.get() + node.adj().len());
+        }
+    }
+}
+
+impl<K, N, C> Cluster<K, N> for Instrumented<C>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    fn remove(&mut self, key: &K) -> Option<N> {
+        self.inner.remove(key)
+    }
+
+    fn remove_many(&mut self, keys: impl IntoIterator<Item = K>) -> Vec<N>
+    where
+        K: Hash + Eq,
+    {
+        self.inner.remove_many(keys)
+    }
+
+    fn keys<'clu>(&'clu self) -> impl Iterator<Item = &'clu K>
+    where
+        K: 'clu,
+    {
+        self.inner.keys()
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    fn get(&self, key: &K) -> Option<&N> {
+        let node = self.inner.get(key);
+        self.visit(node);
+        node
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut N> {
+        let node = self.inner.get_mut(key);
+        if let Some(node) = &node {
+            *self.nodes_visited.get_mut() += 1;
+            *self.edges_relaxed.get_mut() += node.adj().len();
+        }
+        node
+    }
+
+    fn new_key(&self) -> K {
+        self.inner.new_key()
+    }
+
+    fn add(&mut self, node: N) -> K {
+        self.inner.add(node)
+    }
+
+    fn try_add(&mut self, node: N) -> Result<K> {
+        self.inner.try_add(node)
+    }
+
+    fn key_index(key: &K) -> Option<usize> {
+        C::key_index(key)
+    }
+
+    fn is_directed(&self) -> bool {
+        self.inner.is_directed()
+    }
+
+    fn add_edge(&mut self, src: K, dst: K) -> Result<()> {
+        self.inner.add_edge(src, dst)
+    }
+
+    fn remove_edge(&mut self, src: &K, dst: &K) -> Result<()> {
+        self.inner.remove_edge(src, dst)
+    }
+}
+
+impl<K, C: ReverseAdjacency<K>> ReverseAdjacency<K> for Instrumented<C> {
+    fn reverse_adj(&self, key: &K) -> Option<&[K]> {
+        let predecessors = self.inner.reverse_adj(key);
+        if let Some(predecessors) = predecessors {
+            self.nodes_visited.set(self.nodes_visited.get() + 1);
+            self.edges_relaxed
+                .set(self.edges_relaxed.get() + predecessors.len());
+        }
+        predecessors
+    }
+}
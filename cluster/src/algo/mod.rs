@@ -0,0 +1,35 @@
+//! Graph algorithms working on any Cluster.
+
+pub mod assignment;
+pub mod bipartite;
+pub mod canonical;
+pub mod centrality;
+pub mod chordal;
+pub mod clique;
+pub mod coloring;
+pub mod community;
+pub mod components;
+pub mod connectivity;
+pub mod cores;
+pub mod cycle;
+pub mod distance;
+pub mod dominators;
+pub mod euler;
+pub mod flow;
+pub(crate) mod indexed;
+pub mod lca;
+pub mod metrics;
+pub mod reachability;
+#[cfg(feature = "rand")]
+pub mod rewire;
+pub mod scc;
+pub mod schedule;
+pub(crate) mod scored;
+pub mod shortest_path;
+pub mod simple_paths;
+pub mod spanning;
+pub mod temporal;
+pub mod topo;
+pub mod tour;
+pub mod treewidth;
+pub mod two_sat;
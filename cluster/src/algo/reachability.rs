@@ -0,0 +1,286 @@
+//! Reachability queries between the nodes of a directed Cluster.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::algo::scc::tarjan;
+use crate::algo::topo::{toposort, CycleError};
+use crate::{Cluster, Node};
+
+/// Transitive closure of a Cluster answering "can a reach b ?" in constant time.
+/// The strongly connected components are condensed, then each component stores the set of components it reaches as a bitset.
+/// Adding edges and nodes updates the index in place, removing them invalidates it until it is rebuilt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReachabilityIndex<K: Hash + Eq> {
+    components: HashMap<K, usize>,
+    reach: Vec<Vec<u64>>,
+    valid: bool,
+}
+
+impl<K: Hash + Eq + Clone> ReachabilityIndex<K> {
+    /// Build the reachability index of a Cluster.
+    /// # Parameter
+    /// - cluster - The Cluster to index.
+    /// # Return
+    /// The newly built ReachabilityIndex.
+    pub fn new<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> ReachabilityIndex<K> {
+        let graph = IndexedGraph::directed(cluster);
+        let sccs = tarjan(&graph.adj);
+        let mut component = vec![0; graph.keys.len()];
+        for (c, members) in sccs.iter().enumerate() {
+            for v in members {
+                component[*v] = c;
+            }
+        }
+        let words = sccs.len().div_ceil(64);
+        let mut reach = vec![vec![0u64; words]; sccs.len()];
+        for (c, members) in sccs.iter().enumerate() {
+            set_bit(&mut reach[c], c);
+            for v in members {
+                for w in &graph.adj[*v] {
+                    let target = component[*w];
+                    if target != c {
+                        let (current, reached) = split_pair(&mut reach, c, target);
+                        union(current, reached);
+                    }
+                }
+            }
+        }
+        ReachabilityIndex {
+            components: graph.keys.into_iter().zip(component).collect(),
+            reach,
+            valid: true,
+        }
+    }
+
+    /// Check if there is a path from src to dst, a node always reaching itself.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing true if dst is reachable from src, false if it is not or a key is not indexed, None if the index has been invalidated.
+    pub fn is_reachable(&self, src: &K, dst: &K) -> Option<bool> {
+        if !self.valid {
+            return None;
+        }
+        let (Some(&src), Some(&dst)) = (self.components.get(src), self.components.get(dst)) else {
+            return Some(false);
+        };
+        Some(has_bit(&self.reach[src], dst))
+    }
+
+    /// Check if the index still matches the Cluster.
+    /// # Return
+    /// False if a removal invalidated the index, true otherwise.
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    /// Rebuild the index from the current state of the Cluster.
+    /// # Parameter
+    /// - cluster - The Cluster to index.
+    pub fn rebuild<N: Node<K>, C: Cluster<K, N>>(&mut self, cluster: &C) {
+        *self = Self::new(cluster);
+    }
+
+    /// Record a node added to the Cluster, reaching nothing but itself.
+    /// # Parameter
+    /// - key - The key of the new node.
+    pub fn node_added(&mut self, key: K) {
+        if self.components.contains_key(&key) {
+            return;
+        }
+        let c = self.reach.len();
+        self.components.insert(key, c);
+        let words = (c + 1).div_ceil(64);
+        for reached in self.reach.iter_mut() {
+            reached.resize(words, 0);
+        }
+        self.reach.push(vec![0; words]);
+        set_bit(&mut self.reach[c], c);
+    }
+
+    /// Record an edge added to the Cluster : every node reaching src now reaches what dst reaches.
+    /// # Parameters
+    /// - src - The key of the source of the edge.
+    /// - dst - The key of the destination of the edge.
+    pub fn edge_added(&mut self, src: &K, dst: &K) {
+        let (Some(&src), Some(&dst)) = (self.components.get(src), self.components.get(dst)) else {
+            return;
+        };
+        if has_bit(&self.reach[src], dst) {
+            return;
+        }
+        let reached = self.reach[dst].clone();
+        for current in self
+            .reach
+            .iter_mut()
+            .filter(|current| has_bit(current, src))
+        {
+            union(current, &reached);
+        }
+    }
+
+    /// Record a node or an edge removed from the Cluster, which invalidates the index until it is rebuilt.
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
+}
+
+/// Compute the transitive reduction of a directed acyclic Cluster : the same Cluster without the edges implied by longer paths.
+/// An edge from a to b is redundant when another path leads from a to b, so that removing it keeps the reachability of every node.
+/// Parallel edges are merged, edges toward keys not in the Cluster are kept.
+/// # Parameter
+/// - cluster - The Cluster to reduce.
+/// # Return
+/// A copy of the Cluster with only the edges of its transitive reduction, or a CycleError designing a node on a cycle.
+pub fn transitive_reduction<K, N, C>(cluster: &C) -> Result<C, CycleError<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N> + Clone,
+{
+    let mut reduced = cluster.clone();
+    transitive_reduction_in_place(&mut reduced)?;
+    Ok(reduced)
+}
+
+/// Remove the edges of a directed acyclic Cluster implied by longer paths, leaving its transitive reduction.
+/// The nodes are visited in reverse topological order, each one keeping its successors by increasing topological order
+/// but the ones already reached through a previous successor, in O(nm / 64) time.
+/// Parallel edges are merged, edges toward keys not in the Cluster are kept.
+/// # Parameter
+/// - cluster - The Cluster to reduce, left untouched if it has a cycle.
+/// # Return
+/// The number of edges removed, or a CycleError designing a node on a cycle.
+pub fn transitive_reduction_in_place<K, N, C>(cluster: &mut C) -> Result<usize, CycleError<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let order = toposort(cluster)?;
+    let position: HashMap<K, usize> = order
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(i, k)| (k, i))
+        .collect();
+    let words = order.len().div_ceil(64);
+    let mut reach = vec![vec![0u64; words]; order.len()];
+    let mut removed = 0;
+    for (v, key) in order.iter().enumerate().rev() {
+        let mut successors: Vec<usize> = cluster
+            .get_adj(key)
+            .into_iter()
+            .flatten()
+            .filter_map(|k| position.get(k).copied())
+            .collect();
+        successors.sort_unstable();
+        successors.dedup();
+        let mut reached = vec![0u64; words];
+        let mut direct = HashSet::new();
+        for w in successors {
+            if !has_bit(&reached, w) {
+                direct.insert(w);
+                set_bit(&mut reached, w);
+                union(&mut reached, &reach[w]);
+            }
+        }
+        reach[v] = reached;
+        if let Some(node) = cluster.get_mut(key) {
+            let before = node.adj().len();
+            node.retain_adj(|k| position.get(k).is_none_or(|w| direct.remove(w)));
+            removed += before - node.adj().len();
+        }
+    }
+    Ok(removed)
+}
+
+/// Set the bit of a bitset.
+fn set_bit(bits: &mut [u64], bit: usize) {
+    bits[bit / 64] |= 1 << (bit % 64);
+}
+
+/// Check the bit of a bitset.
+fn has_bit(bits: &[u64], bit: usize) -> bool {
+    bits[bit / 64] & (1 << (bit % 64)) != 0
+}
+
+/// Add the bits of other to bits.
+fn union(bits: &mut [u64], other: &[u64]) {
+    for (word, other) in bits.iter_mut().zip(other) {
+        *word |= *other;
+    }
+}
+
+/// Borrow two distinct rows, the first one mutably.
+fn split_pair(rows: &mut [Vec<u64>], first: usize, second: usize) -> (&mut Vec<u64>, &Vec<u64>) {
+    if first < second {
+        let (low, high) = rows.split_at_mut(second);
+        (&mut low[first], &high[0])
+    } else {
+        let (low, high) = rows.split_at_mut(first);
+        (&mut high[0], &low[second])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster};
+
+    fn dag() -> HashCluster<u32, BasicNode<u32>> {
+        [(0, 1), (1, 2), (0, 2), (2, 3), (0, 3), (1, 3), (4, 3)]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn implied_edges_are_removed() {
+        let cluster = dag();
+        let reduced = transitive_reduction(&cluster).unwrap();
+        let expected: HashCluster<u32, BasicNode<u32>> =
+            [(0, 1), (1, 2), (2, 3), (4, 3)].into_iter().collect();
+        crate::assert_graph_eq!(reduced, expected);
+        let mut in_place = cluster.clone();
+        assert_eq!(transitive_reduction_in_place(&mut in_place).unwrap(), 3);
+        crate::assert_graph_eq!(in_place, reduced);
+    }
+
+    #[test]
+    fn cycles_leave_the_cluster_untouched() {
+        let mut cluster = dag();
+        cluster.add_edge(3, 0).unwrap();
+        let before = cluster.clone();
+        assert!(transitive_reduction(&cluster).is_err());
+        assert!(transitive_reduction_in_place(&mut cluster).is_err());
+        crate::assert_graph_eq!(cluster, before);
+    }
+
+    #[test]
+    fn the_index_follows_the_added_edges() {
+        let mut cluster = dag();
+        let mut index = ReachabilityIndex::new(&cluster);
+        assert_eq!(index.is_reachable(&0, &3), Some(true));
+        assert_eq!(index.is_reachable(&3, &0), Some(false));
+        assert_eq!(index.is_reachable(&2, &2), Some(true));
+        cluster.insert(5, BasicNode::new(()));
+        index.node_added(5);
+        cluster.add_edge(3, 5).unwrap();
+        index.edge_added(&3, &5);
+        assert_eq!(index.is_reachable(&4, &5), Some(true));
+        let rebuilt = ReachabilityIndex::new(&cluster);
+        for src in 0..6 {
+            for dst in 0..6 {
+                assert_eq!(
+                    index.is_reachable(&src, &dst),
+                    rebuilt.is_reachable(&src, &dst)
+                );
+            }
+        }
+        index.invalidate();
+        assert_eq!(index.is_reachable(&0, &3), None);
+    }
+}
@@ -0,0 +1,98 @@
+//! Degree-preserving randomization of a Cluster.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Cluster, Node, RandomSource};
+
+/// Shuffle the edges of a Cluster by swapping the ends of random pairs of edges, keeping the degree of every node.
+/// Two edges a -> b and c -> d become a -> d and c -> b, the swap being skipped when it would create a self-loop
+/// or an edge that already exists. In an undirected Cluster, the second edge is taken in a random direction.
+/// The out-degrees and in-degrees are both kept in a directed Cluster. Self-loops and edges toward keys not in the Cluster are left untouched.
+/// Only the adjacency lists are changed : the weights stored in weighted nodes do not follow the edges.
+/// # Parameters
+/// - cluster - The Cluster to rewire.
+/// - iterations - The number of swaps to attempt.
+/// - rng - The source of randomness.
+/// # Return
+/// The number of swaps actually made.
+pub fn rewire<K, N, C, R>(cluster: &mut C, iterations: usize, rng: &mut R) -> usize
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: RandomSource + ?Sized,
+{
+    let directed = cluster.is_directed();
+    let index: HashMap<K, usize> = cluster
+        .keys()
+        .enumerate()
+        .map(|(i, key)| (key.clone(), i))
+        .collect();
+    let mut edges: Vec<(K, K)> = Vec::new();
+    for src in cluster.keys() {
+        for dst in cluster.get_adj(src).into_iter().flatten() {
+            let Some(position) = index.get(dst) else {
+                continue;
+            };
+            if *position == index[src] || (!directed && *position < index[src]) {
+                continue;
+            }
+            edges.push((src.clone(), dst.clone()));
+        }
+    }
+    if edges.len() < 2 {
+        return 0;
+    }
+    let mut swaps = 0;
+    for _ in 0..iterations {
+        let first = rng.below(edges.len());
+        let second = rng.below(edges.len());
+        if first == second {
+            continue;
+        }
+        let (a, b) = edges[first].clone();
+        let (mut c, mut d) = edges[second].clone();
+        if !directed && rng.below(2) == 1 {
+            std::mem::swap(&mut c, &mut d);
+        }
+        if a == d || c == b || has_edge(cluster, &a, &d) || has_edge(cluster, &c, &b) {
+            continue;
+        }
+        move_edge(cluster, &a, &b, &d);
+        move_edge(cluster, &c, &d, &b);
+        if !directed {
+            move_edge(cluster, &b, &a, &c);
+            move_edge(cluster, &d, &c, &a);
+        }
+        edges[first] = (a, d);
+        edges[second] = (c, b);
+        swaps += 1;
+    }
+    swaps
+}
+
+fn has_edge<K, N, C>(cluster: &C, src: &K, dst: &K) -> bool
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    cluster.get_adj(src).is_some_and(|adj| adj.contains(dst))
+}
+
+/// Replace the edge src -> from by src -> to, keeping its place in the adjacency of src.
+fn move_edge<K, N, C>(cluster: &mut C, src: &K, from: &K, to: &K)
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let Some(node) = cluster.get_mut(src) else {
+        return;
+    };
+    if let Some(index) = node.adj().iter().position(|k| k == from) {
+        node.retain_adj(|k| k != from);
+        node.adj_mut().insert(index, to.clone());
+    }
+}
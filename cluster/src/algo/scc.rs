@@ -0,0 +1,245 @@
+//! Strongly connected components of a directed Cluster.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{BasicNode, Cluster, HashCluster, Node, ReverseAdjacency};
+
+/// Split a Cluster into its strongly connected components with Tarjan's algorithm, edges toward keys not in the Cluster being ignored.
+/// The search is iterative, so deep Clusters do not overflow the call stack.
+/// # Parameter
+/// - cluster - The Cluster to split.
+/// # Return
+/// The keys of each component, in reverse topological order : no edge goes from a component to a later one.
+pub fn tarjan_scc<K, N, C>(cluster: &C) -> Vec<Vec<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    tarjan(&graph.adj)
+        .into_iter()
+        .map(|members| members.into_iter().map(|v| graph.keys[v].clone()).collect())
+        .collect()
+}
+
+/// Split a Cluster into its strongly connected components with Kosaraju's algorithm.
+/// The Cluster must be able to list the predecessors of its nodes, as an undirected Cluster does.
+/// # Parameter
+/// - cluster - The Cluster to split.
+/// # Return
+/// The keys of each component, in topological order : no edge goes from a component to an earlier one.
+pub fn kosaraju_scc<K, N, C>(cluster: &C) -> Vec<Vec<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N> + ReverseAdjacency<K>,
+{
+    kosaraju_scc_with(cluster, cluster)
+}
+
+/// Split a Cluster into its strongly connected components with Kosaraju's algorithm.
+/// A first depth-first search orders the nodes by finishing time, a second one on the reversed edges collects the components.
+/// Both searches are iterative, so deep Clusters do not overflow the call stack.
+/// # Parameters
+/// - cluster - The Cluster to split.
+/// - reverse - The reverse adjacency of the Cluster, a ReverseIndex for instance.
+/// # Return
+/// The keys of each component, in topological order : no edge goes from a component to an earlier one.
+pub fn kosaraju_scc_with<K, N, C, R>(cluster: &C, reverse: &R) -> Vec<Vec<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: ReverseAdjacency<K>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    let n = graph.keys.len();
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut calls = vec![(root, 0)];
+        while let Some((v, edge)) = calls.last_mut() {
+            let v = *v;
+            if let Some(w) = graph.adj[v].get(*edge).copied() {
+                *edge += 1;
+                if !visited[w] {
+                    visited[w] = true;
+                    calls.push((w, 0));
+                }
+                continue;
+            }
+            calls.pop();
+            order.push(v);
+        }
+    }
+    let index: HashMap<&K, usize> = graph.keys.iter().enumerate().map(|(i, k)| (k, i)).collect();
+    let mut assigned = vec![false; n];
+    let mut components = Vec::new();
+    for root in order.into_iter().rev() {
+        if assigned[root] {
+            continue;
+        }
+        assigned[root] = true;
+        let mut component = Vec::new();
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            component.push(graph.keys[v].clone());
+            for previous in reverse.reverse_adj(&graph.keys[v]).into_iter().flatten() {
+                if let Some(&u) = index.get(previous) {
+                    if !assigned[u] {
+                        assigned[u] = true;
+                        stack.push(u);
+                    }
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Build the condensation of a Cluster : each strongly connected component becomes a single node, the result being acyclic.
+/// The components are numbered in topological order, so every edge goes from a component to a higher numbered one.
+/// # Parameter
+/// - cluster - The Cluster to condense.
+/// # Return
+/// The condensed Cluster, each node holding the keys of its component.
+pub fn condensation<K, N, C>(cluster: &C) -> HashCluster<usize, BasicNode<usize, Vec<K>>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    let mut components = tarjan(&graph.adj);
+    components.reverse();
+    let mut component = vec![0; graph.keys.len()];
+    for (c, members) in components.iter().enumerate() {
+        for v in members {
+            component[*v] = c;
+        }
+    }
+    let mut condensed = HashCluster::new();
+    for members in &components {
+        let keys = members.iter().map(|v| graph.keys[*v].clone()).collect();
+        condensed.insert(condensed.len(), BasicNode::new(keys));
+    }
+    for (v, dsts) in graph.adj.iter().enumerate() {
+        for w in dsts {
+            let (src, dst) = (component[v], component[*w]);
+            if src != dst {
+                if let Some(adj) = condensed.get_adj_mut(&src) {
+                    if !adj.contains(&dst) {
+                        adj.push(dst);
+                    }
+                }
+            }
+        }
+    }
+    condensed
+}
+
+/// Strongly connected components of an indexed graph with Tarjan's algorithm, in reverse topological order.
+/// The recursion is replaced by an explicit stack so deep graphs do not overflow the call stack.
+pub(crate) fn tarjan(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = adj.len();
+    let mut indexes: Vec<Option<usize>> = vec![None; n];
+    let mut lowlinks = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut calls: Vec<(usize, usize)> = Vec::new();
+    let mut components = Vec::new();
+    let mut counter = 0;
+    for root in 0..n {
+        if indexes[root].is_some() {
+            continue;
+        }
+        indexes[root] = Some(counter);
+        lowlinks[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        calls.push((root, 0));
+        while let Some((v, edge)) = calls.last_mut() {
+            let v = *v;
+            if let Some(w) = adj[v].get(*edge).copied() {
+                *edge += 1;
+                match indexes[w] {
+                    None => {
+                        indexes[w] = Some(counter);
+                        lowlinks[w] = counter;
+                        counter += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        calls.push((w, 0));
+                    }
+                    Some(index) if on_stack[w] => lowlinks[v] = lowlinks[v].min(index),
+                    Some(_) => {}
+                }
+                continue;
+            }
+            calls.pop();
+            if let Some((parent, _)) = calls.last() {
+                lowlinks[*parent] = lowlinks[*parent].min(lowlinks[v]);
+            }
+            if Some(lowlinks[v]) == indexes[v] {
+                let mut component = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReverseIndex;
+
+    fn chain() -> HashCluster<u32, BasicNode<u32>> {
+        [(0, 1), (1, 0), (1, 2), (2, 3), (3, 2), (3, 4), (0, 4)]
+            .into_iter()
+            .collect()
+    }
+
+    fn sorted(mut components: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+        components.iter_mut().for_each(|component| component.sort());
+        components
+    }
+
+    #[test]
+    fn tarjan_and_kosaraju_find_the_same_components() {
+        let cluster = chain();
+        let tarjan = sorted(tarjan_scc(&cluster));
+        assert_eq!(tarjan, vec![vec![4], vec![2, 3], vec![0, 1]]);
+        let mut kosaraju = sorted(kosaraju_scc_with(&cluster, &ReverseIndex::new(&cluster)));
+        kosaraju.reverse();
+        assert_eq!(kosaraju, tarjan);
+    }
+
+    #[test]
+    fn the_condensation_is_the_chain_of_the_components() {
+        let condensed = condensation(&chain());
+        let expected: HashCluster<usize, BasicNode<usize>> =
+            [(0, 1), (1, 2), (0, 2)].into_iter().collect();
+        crate::assert_graph_eq!(condensed, expected);
+        let mut first = condensed.get(&0).unwrap().value().clone();
+        first.sort();
+        assert_eq!(first, vec![0, 1]);
+        assert_eq!(condensed.get(&2).unwrap().value(), &vec![4]);
+    }
+}
@@ -0,0 +1,111 @@
+//! Scheduling of a directed acyclic Cluster with the critical path method.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::algo::shortest_path::weighted_index;
+use crate::algo::topo::{toposort, CycleError};
+use crate::{Weight, WeightedCluster, WeightedNode};
+
+/// Start times of a task in a schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Schedule<W> {
+    /// Earliest time the task can start, every task before it starting as early as possible.
+    pub earliest: W,
+    /// Latest time the task can start without delaying the end of the project.
+    pub latest: W,
+    /// Time the task can be delayed by without delaying the end of the project, null on a critical path.
+    pub slack: W,
+}
+
+/// Critical path of a project, the longest chain of tasks fixing its total duration, along with the schedule of every task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriticalPath<K: Hash + Eq, W> {
+    /// Keys of the tasks of a critical path in order, each one having an edge toward the next one.
+    pub path: Vec<K>,
+    /// Total duration of the project, the length of the critical path.
+    pub length: W,
+    /// Schedule of each task.
+    pub schedule: HashMap<K, Schedule<W>>,
+}
+
+/// Compute the critical path of a directed acyclic weighted Cluster, its longest path, with the start times and slack of every node.
+/// Each node is a task, and the weight of an edge is the time between the starts of its source and its destination,
+/// usually the duration of the source. The tasks without predecessors start at 0, the project ends when its last task starts.
+/// Edges toward keys not in the Cluster are ignored, the heaviest of parallel edges prevails.
+/// # Parameter
+/// - cluster - The weighted Cluster of the tasks and their dependencies.
+/// # Return
+/// The CriticalPath of the Cluster, or a CycleError designing a node on a cycle.
+pub fn critical_path<K, N, C>(cluster: &C) -> Result<CriticalPath<K, N::Weight>, CycleError<K>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let order = toposort(cluster)?;
+    let graph = weighted_index(cluster);
+    let order: Vec<usize> = order.iter().map(|k| graph.index[k]).collect();
+    let n = graph.keys.len();
+    let mut preds: Vec<Vec<(usize, N::Weight)>> = vec![Vec::new(); n];
+    for (src, dsts) in graph.adj.iter().enumerate() {
+        for (dst, weight) in dsts {
+            preds[*dst].push((src, *weight));
+        }
+    }
+
+    let mut earliest: Vec<Option<N::Weight>> = vec![None; n];
+    for v in order.iter().copied() {
+        let start = preds[v]
+            .iter()
+            .filter_map(|(u, weight)| earliest[*u].map(|e| e + *weight))
+            .fold(None, |best: Option<N::Weight>, e| match best {
+                Some(best) if best >= e => Some(best),
+                _ => Some(e),
+            });
+        earliest[v] = Some(start.unwrap_or(N::Weight::zero()));
+    }
+    let earliest: Vec<N::Weight> = earliest.into_iter().flatten().collect();
+    let end = (0..n).fold(None, |best: Option<usize>, v| match best {
+        Some(best) if earliest[best] >= earliest[v] => Some(best),
+        _ => Some(v),
+    });
+    let length = end.map_or(N::Weight::zero(), |end| earliest[end]);
+
+    let mut latest = vec![length; n];
+    for v in order.iter().rev().copied() {
+        for (w, weight) in &graph.adj[v] {
+            let start = latest[*w] - *weight;
+            if start < latest[v] {
+                latest[v] = start;
+            }
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut current = end;
+    while let Some(v) = current {
+        path.push(graph.keys[v].clone());
+        current = preds[v]
+            .iter()
+            .find(|(u, weight)| earliest[*u] + *weight == earliest[v])
+            .map(|(u, _)| *u);
+    }
+    path.reverse();
+
+    let schedule = (0..n)
+        .map(|v| {
+            let times = Schedule {
+                earliest: earliest[v],
+                latest: latest[v],
+                slack: latest[v] - earliest[v],
+            };
+            (graph.keys[v].clone(), times)
+        })
+        .collect();
+    Ok(CriticalPath {
+        path,
+        length,
+        schedule,
+    })
+}
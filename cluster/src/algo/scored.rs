@@ -0,0 +1,25 @@
+use std::cmp::Ordering;
+
+/// Entry of a priority queue, ordered so that a BinaryHeap pops the smallest score first.
+/// Incomparable scores, such as NaN, are considered equal.
+pub(crate) struct MinScored<S, T>(pub(crate) S, pub(crate) T);
+
+impl<S: PartialOrd, T> PartialEq for MinScored<S, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: PartialOrd, T> Eq for MinScored<S, T> {}
+
+impl<S: PartialOrd, T> PartialOrd for MinScored<S, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: PartialOrd, T> Ord for MinScored<S, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Path, Weight, WeightedCluster, WeightedNode};
+
+/// Shortest distances between all the pairs of nodes of a Cluster, stored as a matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix<K: Hash + Eq, W> {
+    pub(super) keys: Vec<K>,
+    pub(super) index: HashMap<K, usize>,
+    pub(super) distances: Vec<Option<W>>,
+    pub(super) next_hops: Option<Vec<Option<usize>>>,
+}
+
+impl<K: Hash + Eq + Clone, W: Weight> DistanceMatrix<K, W> {
+    /// Get the keys of the nodes of the matrix, in the order of its rows.
+    /// # Return
+    /// The keys of the nodes of the matrix.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Get the shortest distance from src to dst.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing the distance, None if dst is not reachable from src.
+    pub fn distance(&self, src: &K, dst: &K) -> Option<W> {
+        let (i, j) = (*self.index.get(src)?, *self.index.get(dst)?);
+        self.distances[i * self.keys.len() + j]
+    }
+
+    /// Get the node following src on a shortest path from src to dst.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing the key of the next hop, None if dst is not reachable or the next hops were not computed.
+    pub fn next_hop(&self, src: &K, dst: &K) -> Option<&K> {
+        let (i, j) = (*self.index.get(src)?, *self.index.get(dst)?);
+        let hop = self.next_hops.as_ref()?[i * self.keys.len() + j]?;
+        Some(&self.keys[hop])
+    }
+
+    /// Get a shortest path from src to dst by following the next hops.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing the keys of the path, None if dst is not reachable or the next hops were not computed.
+    pub fn path(&self, src: &K, dst: &K) -> Option<Path<K>> {
+        self.distance(src, dst)?;
+        let mut path = vec![src.clone()];
+        while path.last() != Some(dst) {
+            if path.len() > self.keys.len() {
+                return None;
+            }
+            path.push(self.next_hop(path.last()?, dst)?.clone());
+        }
+        Some(Path::new(path))
+    }
+
+    /// Check if the Cluster contains a cycle of negative total weight, in which case the distances are meaningless.
+    /// # Return
+    /// True if there is a negative cycle, false otherwise.
+    pub fn has_negative_cycle(&self) -> bool {
+        let n = self.keys.len();
+        (0..n).any(|i| self.distances[i * n + i].is_some_and(|d| d < W::zero()))
+    }
+}
+
+/// Compute the shortest distances between all the pairs of nodes with the Floyd–Warshall algorithm.
+/// It runs in cubic time whatever the number of edges, which suits dense Clusters best.
+/// Negative weights are supported, negative cycles are reported by DistanceMatrix::has_negative_cycle.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - with_paths - True to also compute the next hop matrix, needed to rebuild the paths.
+/// # Return
+/// The DistanceMatrix of the Cluster.
+pub fn floyd_warshall<K, N, C>(cluster: &C, with_paths: bool) -> DistanceMatrix<K, N::Weight>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let keys: Vec<K> = cluster.keys().cloned().collect();
+    let mut matrix = DistanceMatrix::from_edges(cluster, keys, with_paths);
+    for k in 0..matrix.keys.len() {
+        matrix.relax_through(k);
+    }
+    matrix
+}
+
+impl<K: Hash + Eq + Clone, W: Weight> DistanceMatrix<K, W> {
+    /// Build the matrix of the direct edges between the given keys, the keys not in the Cluster having no edge.
+    pub(super) fn from_edges<N, C>(cluster: &C, keys: Vec<K>, with_paths: bool) -> Self
+    where
+        N: WeightedNode<K, Weight = W>,
+        C: WeightedCluster<K, N>,
+    {
+        let index: HashMap<K, usize> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.clone(), i))
+            .collect();
+        let n = keys.len();
+        let mut distances: Vec<Option<W>> = vec![None; n * n];
+        let mut next_hops: Vec<Option<usize>> = vec![None; if with_paths { n * n } else { 0 }];
+        for (i, key) in keys.iter().enumerate() {
+            distances[i * n + i] = Some(W::zero());
+            if with_paths {
+                next_hops[i * n + i] = Some(i);
+            }
+            for (dst, weight) in cluster.weighted_adj(key).into_iter().flatten() {
+                let Some(j) = index.get(&dst).copied() else {
+                    continue;
+                };
+                if distances[i * n + j].is_none_or(|d| weight < d) {
+                    distances[i * n + j] = Some(weight);
+                    if with_paths {
+                        next_hops[i * n + j] = Some(j);
+                    }
+                }
+            }
+        }
+        DistanceMatrix {
+            keys,
+            index,
+            distances,
+            next_hops: with_paths.then_some(next_hops),
+        }
+    }
+
+    /// Run the iteration k of Floyd–Warshall, allowing the paths to go through the node k.
+    pub(super) fn relax_through(&mut self, k: usize) {
+        let n = self.keys.len();
+        let distances = &mut self.distances;
+        for i in 0..n {
+            let Some(ik) = distances[i * n + k] else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(kj) = distances[k * n + j] else {
+                    continue;
+                };
+                if distances[i * n + j].is_none_or(|d| ik + kj < d) {
+                    distances[i * n + j] = Some(ik + kj);
+                    if let Some(next_hops) = self.next_hops.as_mut() {
+                        next_hops[i * n + j] = next_hops[i * n + k];
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::shortest_path::dijkstra;
+    use crate::{HashCluster, WeightedBasicNode};
+
+    fn network(edges: &[(u32, u32, i64)]) -> HashCluster<u32, WeightedBasicNode<u32, i64>> {
+        let mut cluster = HashCluster::new();
+        for key in 0..5 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in edges {
+            cluster.add_weighted_edge(*src, *dst, *weight).unwrap();
+        }
+        cluster
+    }
+
+    #[test]
+    fn distances_match_dijkstra_from_every_node() {
+        let cluster = network(&[
+            (0, 1, 4),
+            (0, 2, 1),
+            (2, 1, 2),
+            (1, 3, 1),
+            (2, 3, 5),
+            (3, 0, 2),
+        ]);
+        let matrix = floyd_warshall(&cluster, true);
+        for src in 0..5 {
+            let paths = dijkstra(&cluster, &src);
+            for dst in 0..5 {
+                assert_eq!(matrix.distance(&src, &dst), paths.distance(&dst));
+                assert_eq!(matrix.path(&src, &dst), paths.path_to(&dst));
+            }
+        }
+        assert!(!matrix.has_negative_cycle());
+    }
+
+    #[test]
+    fn paths_need_the_next_hops() {
+        let cluster = network(&[(0, 1, 1)]);
+        let matrix = floyd_warshall(&cluster, false);
+        assert_eq!(matrix.distance(&0, &1), Some(1));
+        assert_eq!(matrix.next_hop(&0, &1), None);
+        assert_eq!(matrix.path(&0, &1), None);
+    }
+
+    #[test]
+    fn negative_cycles_show_on_the_diagonal() {
+        let cluster = network(&[(0, 1, 1), (1, 2, -3), (2, 0, 1)]);
+        assert!(floyd_warshall(&cluster, false).has_negative_cycle());
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::{weighted_index, DistanceMatrix, ShortestPaths, WeightedIndex};
+use crate::{Weight, WeightedCluster, WeightedNode};
+
+/// Error raised when a Cluster contains a cycle of negative total weight, making some distances unbounded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle<K> {
+    /// The key of a node whose distance keeps decreasing, because it is on or after the negative cycle.
+    pub key: K,
+}
+
+impl<K: Debug> Display for NegativeCycle<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Negative cycle reaching the node {:?}.", self.key)
+    }
+}
+
+impl<K: Debug> Error for NegativeCycle<K> {}
+
+/// Compute the shortest paths from src to every reachable node with the Bellman–Ford algorithm.
+/// Unlike Dijkstra's algorithm, negative weights are supported.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - src - The key of the node the paths start from.
+/// # Return
+/// The ShortestPaths from src, or a NegativeCycle error if a negative cycle is reachable from src.
+pub fn bellman_ford<K, N, C>(
+    cluster: &C,
+    src: &K,
+) -> std::result::Result<ShortestPaths<K, N::Weight>, NegativeCycle<K>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let WeightedIndex { keys, index, adj } = weighted_index(cluster);
+    let mut paths = ShortestPaths {
+        source: src.clone(),
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+    };
+    let Some(start) = index.get(src).copied() else {
+        return Ok(paths);
+    };
+    let mut distances = vec![None; keys.len()];
+    distances[start] = Some(N::Weight::zero());
+    let mut predecessors = vec![None; keys.len()];
+    relax_all(&adj, &mut distances, &mut predecessors).map_err(|v| NegativeCycle {
+        key: keys[v].clone(),
+    })?;
+    for (v, distance) in distances.into_iter().enumerate() {
+        if let Some(distance) = distance {
+            paths.distances.insert(keys[v].clone(), distance);
+        }
+        if let Some(previous) = predecessors[v] {
+            paths
+                .predecessors
+                .insert(keys[v].clone(), keys[previous].clone());
+        }
+    }
+    Ok(paths)
+}
+
+/// Compute the shortest distances between all the pairs of nodes with Johnson's algorithm.
+/// The weights are first made non negative thanks to potentials computed by Bellman–Ford,
+/// then Dijkstra's algorithm runs from every node, which is faster than Floyd–Warshall on sparse Clusters.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - with_paths - True to also compute the next hop matrix, needed to rebuild the paths.
+/// # Return
+/// The DistanceMatrix of the Cluster, or a NegativeCycle error if the Cluster contains a negative cycle.
+pub fn johnson<K, N, C>(
+    cluster: &C,
+    with_paths: bool,
+) -> std::result::Result<DistanceMatrix<K, N::Weight>, NegativeCycle<K>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let WeightedIndex { keys, index, adj } = weighted_index(cluster);
+    let n = keys.len();
+    let mut potentials = vec![Some(N::Weight::zero()); n];
+    relax_all(&adj, &mut potentials, &mut vec![None; n]).map_err(|v| NegativeCycle {
+        key: keys[v].clone(),
+    })?;
+    let potentials: Vec<N::Weight> = potentials.into_iter().flatten().collect();
+    let mut distances = vec![None; n * n];
+    let mut next_hops = vec![None; if with_paths { n * n } else { 0 }];
+    for src in 0..n {
+        let mut reduced = vec![None; n];
+        let mut hops: Vec<Option<usize>> = vec![None; n];
+        let mut settled = vec![false; n];
+        reduced[src] = Some(N::Weight::zero());
+        hops[src] = Some(src);
+        let mut frontier = BinaryHeap::from([MinScored(N::Weight::zero(), src)]);
+        while let Some(MinScored(distance, v)) = frontier.pop() {
+            if std::mem::replace(&mut settled[v], true) {
+                continue;
+            }
+            distances[src * n + v] = Some(distance - potentials[src] + potentials[v]);
+            for (w, weight) in &adj[v] {
+                let candidate = distance + (*weight + potentials[v] - potentials[*w]);
+                if !settled[*w] && reduced[*w].is_none_or(|current| candidate < current) {
+                    reduced[*w] = Some(candidate);
+                    hops[*w] = if v == src { Some(*w) } else { hops[v] };
+                    frontier.push(MinScored(candidate, *w));
+                }
+            }
+        }
+        if with_paths {
+            next_hops[src * n..(src + 1) * n].copy_from_slice(&hops);
+        }
+    }
+    Ok(DistanceMatrix {
+        keys,
+        index,
+        distances,
+        next_hops: with_paths.then_some(next_hops),
+    })
+}
+
+/// Relax every edge until the distances stop decreasing.
+/// Returns the index of a node still improving after as many rounds as there are nodes, proving a negative cycle.
+fn relax_all<W: Weight>(
+    adj: &[Vec<(usize, W)>],
+    distances: &mut [Option<W>],
+    predecessors: &mut [Option<usize>],
+) -> std::result::Result<(), usize> {
+    let mut improved = None;
+    for _ in 0..=adj.len() {
+        improved = None;
+        for (v, edges) in adj.iter().enumerate() {
+            let Some(distance) = distances[v] else {
+                continue;
+            };
+            for (w, weight) in edges {
+                let candidate = distance + *weight;
+                if distances[*w].is_none_or(|current| candidate < current) {
+                    distances[*w] = Some(candidate);
+                    predecessors[*w] = Some(v);
+                    improved = Some(*w);
+                }
+            }
+        }
+        if improved.is_none() {
+            return Ok(());
+        }
+    }
+    improved.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashCluster, Path, WeightedBasicNode};
+
+    fn network(edges: &[(u32, u32, i64)]) -> HashCluster<u32, WeightedBasicNode<u32, i64>> {
+        let mut cluster = HashCluster::new();
+        for key in 0..5 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in edges {
+            cluster.add_weighted_edge(*src, *dst, *weight).unwrap();
+        }
+        cluster
+    }
+
+    const EDGES: [(u32, u32, i64); 4] = [(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 2)];
+
+    #[test]
+    fn negative_edges_shorten_the_paths() {
+        let cluster = network(&EDGES);
+        let paths = bellman_ford(&cluster, &0).unwrap();
+        assert_eq!(paths.distance(&1), Some(2));
+        assert_eq!(paths.distance(&3), Some(4));
+        assert_eq!(paths.distance(&4), None);
+        assert_eq!(paths.path_to(&3), Some(Path::new(vec![0, 2, 1, 3])));
+    }
+
+    #[test]
+    fn negative_cycles_are_reported() {
+        let mut edges = EDGES.to_vec();
+        edges.push((3, 2, -5));
+        let cluster = network(&edges);
+        let cycle = bellman_ford(&cluster, &0).unwrap_err();
+        assert!([1, 2, 3].contains(&cycle.key));
+        assert!(johnson(&cluster, false).is_err());
+        assert!(bellman_ford(&cluster, &4).is_ok());
+    }
+
+    #[test]
+    fn johnson_computes_every_pair() {
+        let cluster = network(&EDGES);
+        let matrix = johnson(&cluster, true).unwrap();
+        assert_eq!(matrix.distance(&2, &3), Some(-1));
+        assert_eq!(matrix.distance(&0, &3), Some(4));
+        assert_eq!(matrix.distance(&3, &0), None);
+        assert_eq!(matrix.path(&0, &3), Some(Path::new(vec![0, 2, 1, 3])));
+        for src in 0..5 {
+            let paths = bellman_ford(&cluster, &src).unwrap();
+            for dst in 0..5 {
+                assert_eq!(matrix.distance(&src, &dst), paths.distance(&dst));
+            }
+        }
+    }
+}
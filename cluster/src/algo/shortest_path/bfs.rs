@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Cluster, Node, Path, ReverseAdjacency};
+
+/// Shortest path in number of edges between src and dst, searched from both ends at the same time.
+/// The Cluster must be able to list the predecessors of its nodes, as an undirected Cluster does.
+/// # Parameters
+/// - cluster - The Cluster to search.
+/// - src - The key of the first node of the path.
+/// - dst - The key of the last node of the path.
+/// # Return
+/// An Option containing the keys of a shortest path from src to dst, None if dst is not reachable from src.
+pub fn bidirectional_bfs<K, N, C>(cluster: &C, src: &K, dst: &K) -> Option<Path<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N> + ReverseAdjacency<K>,
+{
+    bidirectional_bfs_with(cluster, cluster, src, dst)
+}
+
+/// Shortest path in number of edges between src and dst, searched from both ends at the same time.
+/// # Parameters
+/// - cluster - The Cluster to search.
+/// - reverse - The reverse adjacency of the Cluster, a ReverseIndex for instance.
+/// - src - The key of the first node of the path.
+/// - dst - The key of the last node of the path.
+/// # Return
+/// An Option containing the keys of a shortest path from src to dst, None if dst is not reachable from src.
+pub fn bidirectional_bfs_with<K, N, C, R>(
+    cluster: &C,
+    reverse: &R,
+    src: &K,
+    dst: &K,
+) -> Option<Path<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: ReverseAdjacency<K>,
+{
+    if !cluster.contains_key(src) || !cluster.contains_key(dst) {
+        return None;
+    }
+    if src == dst {
+        return Some(Path::new(vec![src.clone()]));
+    }
+    let mut forward = Frontier::new(src);
+    let mut backward = Frontier::new(dst);
+    while !forward.layer.is_empty() && !backward.layer.is_empty() {
+        let meeting = if forward.layer.len() <= backward.layer.len() {
+            forward.expand(&backward, |k| cluster.get_adj(k))
+        } else {
+            backward.expand(&forward, |k| reverse.reverse_adj(k))
+        };
+        if let Some(meeting) = meeting {
+            let mut path = forward.path_to(&meeting);
+            path.reverse();
+            path.extend(backward.path_to(&meeting).into_iter().skip(1));
+            return Some(Path::new(path));
+        }
+    }
+    None
+}
+
+/// One side of a bidirectional breadth-first search.
+struct Frontier<K> {
+    depths: HashMap<K, (usize, Option<K>)>,
+    layer: Vec<K>,
+}
+
+impl<K: Hash + Eq + Clone> Frontier<K> {
+    fn new(start: &K) -> Frontier<K> {
+        Frontier {
+            depths: HashMap::from([(start.clone(), (0, None))]),
+            layer: vec![start.clone()],
+        }
+    }
+
+    /// Expand the whole current layer, returning the best key met by the other side.
+    fn expand<'a, F>(&mut self, other: &Frontier<K>, neighbours: F) -> Option<K>
+    where
+        F: Fn(&K) -> Option<&'a [K]>,
+        K: 'a,
+    {
+        let mut best: Option<(usize, K)> = None;
+        let mut next_layer = Vec::new();
+        for key in std::mem::take(&mut self.layer) {
+            let depth = self.depths[&key].0;
+            for next in neighbours(&key).into_iter().flatten() {
+                if self.depths.contains_key(next) {
+                    continue;
+                }
+                self.depths
+                    .insert(next.clone(), (depth + 1, Some(key.clone())));
+                if let Some((other_depth, _)) = other.depths.get(next) {
+                    let length = depth + 1 + other_depth;
+                    if best.as_ref().is_none_or(|(l, _)| length < *l) {
+                        best = Some((length, next.clone()));
+                    }
+                }
+                next_layer.push(next.clone());
+            }
+        }
+        self.layer = next_layer;
+        best.map(|(_, key)| key)
+    }
+
+    /// Get the keys from the given key back to the start of the frontier.
+    fn path_to(&self, key: &K) -> Vec<K> {
+        let mut path = vec![key.clone()];
+        while let Some((_, Some(parent))) = self.depths.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster, ReverseIndex, UndirectedHashCluster};
+
+    #[test]
+    fn the_shortcut_is_found_from_both_ends() {
+        let mut cluster: UndirectedHashCluster<u32, BasicNode<u32>> = UndirectedHashCluster::new();
+        for key in 0..7 {
+            cluster.insert(key, BasicNode::new(()));
+        }
+        for (src, dst) in [(0, 1), (1, 2), (2, 3), (3, 4), (0, 5), (5, 4)] {
+            cluster.add_edge(src, dst).unwrap();
+        }
+        assert_eq!(
+            bidirectional_bfs(&cluster, &0, &4),
+            Some(Path::new(vec![0, 5, 4]))
+        );
+        assert_eq!(
+            bidirectional_bfs(&cluster, &2, &2),
+            Some(Path::new(vec![2]))
+        );
+        assert_eq!(bidirectional_bfs(&cluster, &0, &6), None);
+        assert_eq!(bidirectional_bfs(&cluster, &0, &9), None);
+    }
+
+    #[test]
+    fn directed_clusters_search_backward_with_a_reverse_index() {
+        let cluster: HashCluster<u32, BasicNode<u32>> =
+            [(0, 1), (1, 2), (2, 3), (3, 0)].into_iter().collect();
+        let reverse = ReverseIndex::new(&cluster);
+        assert_eq!(
+            bidirectional_bfs_with(&cluster, &reverse, &0, &3),
+            Some(Path::new(vec![0, 1, 2, 3]))
+        );
+        assert_eq!(
+            bidirectional_bfs_with(&cluster, &reverse, &3, &0),
+            Some(Path::new(vec![3, 0]))
+        );
+    }
+}
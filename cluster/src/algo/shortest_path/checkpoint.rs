@@ -0,0 +1,293 @@
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+
+use crate::{ClusterError, Result, Weight, WeightedCluster, WeightedNode};
+
+use super::DistanceMatrix;
+
+/// Header of a Floyd–Warshall checkpoint, followed by its version.
+const MAGIC: &[u8; 4] = b"CLFW";
+const VERSION: u8 = 2;
+/// Size of the header of a checkpoint.
+const HEADER_SIZE: usize = 39;
+/// Next hop of a pair without path in a checkpoint.
+const NO_HOP: u32 = u32::MAX;
+
+/// Fixed size little endian encoding of a weight, used to save the state of long computations.
+pub trait WeightCodec: Sized {
+    /// Number of bytes of an encoded weight.
+    const SIZE: usize;
+
+    /// Append the encoding of the weight to a buffer.
+    /// # Parameter
+    /// - buffer - The buffer receiving SIZE bytes.
+    fn encode(&self, buffer: &mut Vec<u8>);
+
+    /// Decode a weight.
+    /// # Parameter
+    /// - bytes - The SIZE bytes of the encoded weight.
+    /// # Return
+    /// The decoded weight.
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_weight_codec {
+    ($($t:ty),*) => {
+        $(
+            impl WeightCodec for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn encode(&self, buffer: &mut Vec<u8>) {
+                    buffer.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(bytes: &[u8]) -> Self {
+                    <$t>::from_le_bytes(bytes.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_codec!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl WeightCodec for usize {
+    const SIZE: usize = 8;
+
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        (*self as u64).encode(buffer);
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        u64::decode(bytes) as usize
+    }
+}
+
+impl WeightCodec for isize {
+    const SIZE: usize = 8;
+
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        (*self as i64).encode(buffer);
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        i64::decode(bytes) as isize
+    }
+}
+
+/// FNV-1a hasher, whose output does not change between runs unlike the one of HashMap.
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100_0000_01b3);
+        }
+    }
+}
+
+/// Floyd–Warshall computation running one intermediate node at a time, whose state can be saved and resumed.
+/// The nodes are sorted by key, so that a checkpoint can be resumed from another run over the same Cluster.
+/// A checkpoint records the number of edges and a hash of the keys and edges, to be rejected by a changed Cluster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloydWarshallRun<K: Hash + Eq, W> {
+    matrix: DistanceMatrix<K, W>,
+    next: usize,
+    edges: u64,
+    fingerprint: u64,
+}
+
+impl<K, W> FloydWarshallRun<K, W>
+where
+    K: Hash + Eq + Ord + Clone,
+    W: Weight + WeightCodec,
+{
+    /// Start a new computation over a Cluster.
+    /// # Parameters
+    /// - cluster - The weighted Cluster to search.
+    /// - with_paths - True to also compute the next hop matrix, needed to rebuild the paths.
+    /// # Return
+    /// The FloydWarshallRun, no node having been processed yet.
+    pub fn new<N, C>(cluster: &C, with_paths: bool) -> FloydWarshallRun<K, W>
+    where
+        N: WeightedNode<K, Weight = W>,
+        C: WeightedCluster<K, N>,
+    {
+        let mut keys: Vec<K> = cluster.keys().cloned().collect();
+        keys.sort();
+        let edges = keys
+            .iter()
+            .map(|key| cluster.get_adj(key).map_or(0, <[K]>::len) as u64)
+            .sum();
+        let matrix = DistanceMatrix::from_edges(cluster, keys, with_paths);
+        // The initial distances hold the lightest edge between each pair, the key hashes telling the nodes apart.
+        let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+        matrix.keys.iter().for_each(|key| key.hash(&mut hasher));
+        let mut buffer = Vec::with_capacity(W::SIZE);
+        for distance in &matrix.distances {
+            buffer.clear();
+            buffer.push(u8::from(distance.is_some()));
+            distance
+                .iter()
+                .for_each(|distance| distance.encode(&mut buffer));
+            hasher.write(&buffer);
+        }
+        FloydWarshallRun {
+            matrix,
+            next: 0,
+            edges,
+            fingerprint: hasher.finish(),
+        }
+    }
+
+    /// Resume a computation from a checkpoint written by save.
+    /// # Parameters
+    /// - cluster - The weighted Cluster the checkpoint was taken on, which must not have changed since.
+    /// - reader - The reader holding the checkpoint.
+    /// # Return
+    /// The FloydWarshallRun as it was saved, an error if the checkpoint is invalid or does not match the Cluster.
+    pub fn resume<N, C, R>(cluster: &C, reader: &mut R) -> Result<FloydWarshallRun<K, W>>
+    where
+        N: WeightedNode<K, Weight = W>,
+        C: WeightedCluster<K, N>,
+        R: Read,
+    {
+        let mut header = [0; HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC || header[4] != VERSION {
+            return Err(ClusterError::detailled("The reader does not hold a checkpoint.").into());
+        }
+        let n = u64::from_le_bytes(header[5..13].try_into().unwrap()) as usize;
+        let next = u64::from_le_bytes(header[13..21].try_into().unwrap()) as usize;
+        let with_paths = header[21] == 1;
+        if header[22] as usize != W::SIZE {
+            return Err(
+                ClusterError::detailled("The checkpoint holds another type of weight.").into(),
+            );
+        }
+        let edges = u64::from_le_bytes(header[23..31].try_into().unwrap());
+        let fingerprint = u64::from_le_bytes(header[31..39].try_into().unwrap());
+        let mut run = Self::new(cluster, with_paths);
+        if n != run.matrix.keys.len() || next > n {
+            return Err(ClusterError::detailled(
+                "The checkpoint does not match the number of nodes of the Cluster.",
+            )
+            .into());
+        }
+        if edges != run.edges || fingerprint != run.fingerprint {
+            return Err(ClusterError::detailled(
+                "The Cluster has changed since the checkpoint was taken.",
+            )
+            .into());
+        }
+        let mut entry = vec![0; 1 + W::SIZE];
+        for distance in run.matrix.distances.iter_mut() {
+            reader.read_exact(&mut entry)?;
+            *distance = (entry[0] == 1).then(|| W::decode(&entry[1..]));
+        }
+        if let Some(next_hops) = run.matrix.next_hops.as_mut() {
+            let mut hop = [0; 4];
+            for next_hop in next_hops.iter_mut() {
+                reader.read_exact(&mut hop)?;
+                let hop = u32::from_le_bytes(hop);
+                if hop != NO_HOP && hop as usize >= n {
+                    return Err(ClusterError::detailled(
+                        "The checkpoint refers to an unknown node.",
+                    )
+                    .into());
+                }
+                *next_hop = (hop != NO_HOP).then_some(hop as usize);
+            }
+        }
+        run.next = next;
+        Ok(run)
+    }
+
+    /// Write the state of the computation, to be resumed later.
+    /// # Parameter
+    /// - writer - The writer receiving the checkpoint.
+    /// # Return
+    /// Nothing if the checkpoint has been written, an error otherwise.
+    pub fn save<T: Write>(&self, writer: &mut T) -> Result<()> {
+        let n = self.matrix.keys.len();
+        let mut buffer = Vec::with_capacity(HEADER_SIZE + n * n * (5 + W::SIZE));
+        buffer.extend_from_slice(MAGIC);
+        buffer.push(VERSION);
+        buffer.extend_from_slice(&(n as u64).to_le_bytes());
+        buffer.extend_from_slice(&(self.next as u64).to_le_bytes());
+        buffer.push(u8::from(self.matrix.next_hops.is_some()));
+        buffer.push(W::SIZE as u8);
+        buffer.extend_from_slice(&self.edges.to_le_bytes());
+        buffer.extend_from_slice(&self.fingerprint.to_le_bytes());
+        for distance in &self.matrix.distances {
+            match distance {
+                Some(distance) => {
+                    buffer.push(1);
+                    distance.encode(&mut buffer);
+                }
+                None => buffer.extend(std::iter::repeat_n(0, 1 + W::SIZE)),
+            }
+        }
+        for hop in self.matrix.next_hops.iter().flatten() {
+            let hop = hop.map_or(NO_HOP, |hop| hop as u32);
+            buffer.extend_from_slice(&hop.to_le_bytes());
+        }
+        writer.write_all(&buffer)?;
+        Ok(())
+    }
+
+    /// Get the progress of the computation.
+    /// # Return
+    /// The number of nodes already processed and the total number of nodes.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.next, self.matrix.keys.len())
+    }
+
+    /// Check if all the nodes have been processed.
+    /// # Return
+    /// True if the distances are final, false otherwise.
+    pub fn is_done(&self) -> bool {
+        self.next == self.matrix.keys.len()
+    }
+
+    /// Process the next node, allowing the paths to go through it.
+    /// # Return
+    /// True if a node has been processed, false if the computation was already done.
+    pub fn step(&mut self) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        self.matrix.relax_through(self.next);
+        self.next += 1;
+        true
+    }
+
+    /// Run the computation to its end, handing the run to a checkpoint callback at regular intervals.
+    /// # Parameters
+    /// - every - The number of nodes processed between two checkpoints, 0 to never call the callback.
+    /// - checkpoint - The callback saving the run, typically by calling save on a fresh file.
+    /// # Return
+    /// Nothing if the computation is done, the error of the callback otherwise.
+    pub fn run_with_checkpoints<F>(&mut self, every: usize, mut checkpoint: F) -> Result<()>
+    where
+        F: FnMut(&Self) -> Result<()>,
+    {
+        while self.step() {
+            if every > 0 && self.next.is_multiple_of(every) && !self.is_done() {
+                checkpoint(self)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the distances once the computation is done.
+    /// # Return
+    /// An Option containing the DistanceMatrix of the Cluster, None if some nodes have not been processed yet.
+    pub fn finish(self) -> Option<DistanceMatrix<K, W>> {
+        self.is_done().then_some(self.matrix)
+    }
+}
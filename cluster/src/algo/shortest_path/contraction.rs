@@ -0,0 +1,349 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::weighted_index;
+use crate::{Path, Weight, WeightedCluster, WeightedNode};
+
+/// Maximum number of nodes settled by a witness search before giving up and adding the shortcut.
+const WITNESS_SETTLE_LIMIT: usize = 256;
+
+/// Contraction hierarchy of a static weighted Cluster, answering shortest path queries much faster than Dijkstra.
+/// The nodes are contracted one by one, shortcut edges preserving the distances between the remaining nodes.
+/// A query then only climbs the hierarchy from both ends, exploring a small part of the Cluster.
+/// The index is not updated when the Cluster is modified afterward, the weights of the edges must not be negative.
+#[derive(Debug, Clone)]
+pub struct RoutingIndex<K, W> {
+    keys: Vec<K>,
+    index: HashMap<K, usize>,
+    up: Vec<Vec<(usize, W)>>,
+    down: Vec<Vec<(usize, W)>>,
+    edges: HashMap<(usize, usize), (W, Option<usize>)>,
+}
+
+impl<K: Hash + Eq + Clone, W: Weight> RoutingIndex<K, W> {
+    /// Preprocess a Cluster, ordering its nodes by importance and adding the shortcut edges.
+    /// # Parameter
+    /// - cluster - The weighted Cluster to index.
+    /// # Return
+    /// The newly built RoutingIndex.
+    pub fn new<N, C>(cluster: &C) -> RoutingIndex<K, W>
+    where
+        N: WeightedNode<K, Weight = W>,
+        C: WeightedCluster<K, N>,
+    {
+        let graph = weighted_index(cluster);
+        let n = graph.keys.len();
+        let mut contraction = Contraction {
+            outgoing: vec![HashMap::new(); n],
+            incoming: vec![HashMap::new(); n],
+            contracted: vec![false; n],
+            contracted_neighbours: vec![0; n],
+            edges: HashMap::new(),
+        };
+        for (src, adj) in graph.adj.iter().enumerate() {
+            for &(dst, weight) in adj {
+                if src != dst {
+                    contraction.insert_edge(src, dst, weight, None);
+                }
+            }
+        }
+        let mut queue: BinaryHeap<MinScored<isize, usize>> = (0..n)
+            .map(|node| {
+                let shortcuts = contraction.shortcuts(node).len();
+                MinScored(contraction.priority(node, shortcuts), node)
+            })
+            .collect();
+        let mut rank = vec![0; n];
+        let mut next_rank = 0;
+        while let Some(MinScored(priority, node)) = queue.pop() {
+            let shortcuts = contraction.shortcuts(node);
+            let current = contraction.priority(node, shortcuts.len());
+            if current > priority && queue.peek().is_some_and(|top| current > top.0) {
+                queue.push(MinScored(current, node));
+                continue;
+            }
+            rank[node] = next_rank;
+            next_rank += 1;
+            contraction.contract(node, shortcuts);
+        }
+        let mut up = vec![Vec::new(); n];
+        let mut down = vec![Vec::new(); n];
+        for (&(src, dst), &(weight, _)) in &contraction.edges {
+            if rank[src] < rank[dst] {
+                up[src].push((dst, weight));
+            } else {
+                down[dst].push((src, weight));
+            }
+        }
+        RoutingIndex {
+            keys: graph.keys,
+            index: graph.index,
+            up,
+            down,
+            edges: contraction.edges,
+        }
+    }
+
+    /// Get the number of shortcut edges added by the preprocessing.
+    /// # Return
+    /// The number of shortcuts of the hierarchy.
+    pub fn shortcut_count(&self) -> usize {
+        self.edges
+            .values()
+            .filter(|(_, middle)| middle.is_some())
+            .count()
+    }
+
+    /// Compute the length of a shortest path from src to dst.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing the length of the path, None if dst is not reachable from src.
+    pub fn distance(&self, src: &K, dst: &K) -> Option<W> {
+        self.search(src, dst).map(|(length, _, _, _)| length)
+    }
+
+    /// Compute a shortest path from src to dst, the shortcuts being unpacked into the edges of the Cluster.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing the length of the path and its keys, None if dst is not reachable from src.
+    pub fn shortest_path(&self, src: &K, dst: &K) -> Option<(W, Path<K>)> {
+        let (length, meeting, forward, backward) = self.search(src, dst)?;
+        let mut hierarchy_path = vec![meeting];
+        while let Some(&previous) = forward.get(hierarchy_path.last().unwrap()) {
+            hierarchy_path.push(previous);
+        }
+        hierarchy_path.reverse();
+        while let Some(&next) = backward.get(hierarchy_path.last().unwrap()) {
+            hierarchy_path.push(next);
+        }
+        let mut path = vec![hierarchy_path[0]];
+        for pair in hierarchy_path.windows(2) {
+            self.unpack(pair[0], pair[1], &mut path);
+        }
+        Some((
+            length,
+            path.into_iter().map(|i| self.keys[i].clone()).collect(),
+        ))
+    }
+
+    /// Search the hierarchy upward from both ends.
+    /// Returns the length, the node where both searches met, and the parent of each node in both searches.
+    #[allow(clippy::type_complexity)]
+    fn search(
+        &self,
+        src: &K,
+        dst: &K,
+    ) -> Option<(W, usize, HashMap<usize, usize>, HashMap<usize, usize>)> {
+        let (&src, &dst) = (self.index.get(src)?, self.index.get(dst)?);
+        let mut sides = [UpwardSearch::new(src), UpwardSearch::new(dst)];
+        let mut best: Option<(W, usize)> = (src == dst).then_some((W::zero(), src));
+        loop {
+            let tops = [sides[0].peek(), sides[1].peek()];
+            let side = match tops {
+                [Some(forward), Some(backward)] => usize::from(backward < forward),
+                [Some(_), None] => 0,
+                [None, Some(_)] => 1,
+                [None, None] => break,
+            };
+            let top = tops[side].unwrap();
+            if best.is_some_and(|(length, _)| top >= length) {
+                sides[side].frontier.clear();
+                continue;
+            }
+            let adj = if side == 0 { &self.up } else { &self.down };
+            let (searching, other) = if side == 0 {
+                let [a, b] = &mut sides;
+                (a, &*b)
+            } else {
+                let [a, b] = &mut sides;
+                (b, &*a)
+            };
+            searching.step(other, adj, &mut best);
+        }
+        let (length, meeting) = best?;
+        let [forward, backward] = sides;
+        Some((length, meeting, forward.parents, backward.parents))
+    }
+
+    /// Append to path the nodes of the edge going from src to dst, the shortcuts being replaced by the edges they skip.
+    fn unpack(&self, src: usize, dst: usize, path: &mut Vec<usize>) {
+        let mut stack = vec![(src, dst)];
+        while let Some((src, dst)) = stack.pop() {
+            match self.edges[&(src, dst)].1 {
+                Some(middle) => {
+                    stack.push((middle, dst));
+                    stack.push((src, middle));
+                }
+                None => path.push(dst),
+            }
+        }
+    }
+}
+
+/// Graph being contracted, holding the edges between the nodes not contracted yet.
+struct Contraction<W> {
+    outgoing: Vec<HashMap<usize, W>>,
+    incoming: Vec<HashMap<usize, W>>,
+    contracted: Vec<bool>,
+    contracted_neighbours: Vec<isize>,
+    edges: HashMap<(usize, usize), (W, Option<usize>)>,
+}
+
+impl<W: Weight> Contraction<W> {
+    /// Add an edge, or lower the weight of the existing one.
+    fn insert_edge(&mut self, src: usize, dst: usize, weight: W, middle: Option<usize>) {
+        if self.outgoing[src]
+            .get(&dst)
+            .is_some_and(|current| *current <= weight)
+        {
+            return;
+        }
+        self.outgoing[src].insert(dst, weight);
+        self.incoming[dst].insert(src, weight);
+        self.edges.insert((src, dst), (weight, middle));
+    }
+
+    /// Get the shortcuts that contracting node would require.
+    fn shortcuts(&self, node: usize) -> Vec<(usize, usize, W)> {
+        let mut shortcuts = Vec::new();
+        for (&src, &first) in &self.incoming[node] {
+            let targets: Vec<(usize, W)> = self.outgoing[node]
+                .iter()
+                .filter(|(dst, _)| **dst != src)
+                .map(|(&dst, &second)| (dst, first + second))
+                .collect();
+            let Some(limit) =
+                targets
+                    .iter()
+                    .map(|(_, length)| *length)
+                    .reduce(|a, b| if b > a { b } else { a })
+            else {
+                continue;
+            };
+            let witnesses = self.witness_search(src, node, limit);
+            for (dst, length) in targets {
+                if witnesses.get(&dst).is_none_or(|witness| *witness > length) {
+                    shortcuts.push((src, dst, length));
+                }
+            }
+        }
+        shortcuts
+    }
+
+    /// Dijkstra's algorithm from src among the remaining nodes but node, bounded by limit.
+    fn witness_search(&self, src: usize, node: usize, limit: W) -> HashMap<usize, W> {
+        let mut distances = HashMap::from([(src, W::zero())]);
+        let mut frontier = BinaryHeap::from([MinScored(W::zero(), src)]);
+        let mut settled = 0;
+        while let Some(MinScored(distance, current)) = frontier.pop() {
+            if distance > limit || settled == WITNESS_SETTLE_LIMIT {
+                break;
+            }
+            if distances.get(&current).is_some_and(|best| *best < distance) {
+                continue;
+            }
+            settled += 1;
+            for (&next, &weight) in &self.outgoing[current] {
+                if next == node {
+                    continue;
+                }
+                let candidate = distance + weight;
+                if distances.get(&next).is_none_or(|best| candidate < *best) {
+                    distances.insert(next, candidate);
+                    frontier.push(MinScored(candidate, next));
+                }
+            }
+        }
+        distances
+    }
+
+    /// Get the contraction priority of node, the nodes adding few shortcuts being contracted first.
+    fn priority(&self, node: usize, shortcuts: usize) -> isize {
+        let removed = self.incoming[node].len() + self.outgoing[node].len();
+        shortcuts as isize - removed as isize + self.contracted_neighbours[node]
+    }
+
+    /// Remove node from the remaining graph, adding the shortcuts keeping the distances unchanged.
+    fn contract(&mut self, node: usize, shortcuts: Vec<(usize, usize, W)>) {
+        for (src, dst, length) in shortcuts {
+            self.insert_edge(src, dst, length, Some(node));
+        }
+        self.contracted[node] = true;
+        for (src, _) in std::mem::take(&mut self.incoming[node]) {
+            self.outgoing[src].remove(&node);
+            self.contracted_neighbours[src] += 1;
+        }
+        for (dst, _) in std::mem::take(&mut self.outgoing[node]) {
+            self.incoming[dst].remove(&node);
+            self.contracted_neighbours[dst] += 1;
+        }
+    }
+}
+
+/// One side of a query, climbing the hierarchy.
+struct UpwardSearch<W> {
+    distances: HashMap<usize, W>,
+    parents: HashMap<usize, usize>,
+    frontier: BinaryHeap<MinScored<W, usize>>,
+}
+
+impl<W: Weight> UpwardSearch<W> {
+    fn new(start: usize) -> UpwardSearch<W> {
+        UpwardSearch {
+            distances: HashMap::from([(start, W::zero())]),
+            parents: HashMap::new(),
+            frontier: BinaryHeap::from([MinScored(W::zero(), start)]),
+        }
+    }
+
+    /// Get the distance of the next node to settle, dropping the stale entries of the frontier.
+    fn peek(&mut self) -> Option<W> {
+        while let Some(MinScored(distance, node)) = self.frontier.peek() {
+            if self
+                .distances
+                .get(node)
+                .is_some_and(|best| *best < *distance)
+            {
+                self.frontier.pop();
+                continue;
+            }
+            return Some(*distance);
+        }
+        None
+    }
+
+    /// Settle the next node, updating the best path met by the other side.
+    fn step(
+        &mut self,
+        other: &UpwardSearch<W>,
+        adj: &[Vec<(usize, W)>],
+        best: &mut Option<(W, usize)>,
+    ) {
+        let Some(MinScored(distance, node)) = self.frontier.pop() else {
+            return;
+        };
+        if let Some(remaining) = other.distances.get(&node) {
+            let length = distance + *remaining;
+            if best.is_none_or(|(current, _)| length < current) {
+                *best = Some((length, node));
+            }
+        }
+        for &(next, weight) in &adj[node] {
+            let candidate = distance + weight;
+            if self
+                .distances
+                .get(&next)
+                .is_none_or(|current| candidate < *current)
+            {
+                self.distances.insert(next, candidate);
+                self.parents.insert(next, node);
+                self.frontier.push(MinScored(candidate, next));
+            }
+        }
+    }
+}
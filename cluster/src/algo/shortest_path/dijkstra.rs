@@ -0,0 +1,430 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::{reconstruct_path, ShortestPaths};
+use crate::traversal::{Control, KeyMap, VisitMap};
+use crate::{Cluster, Node, Path, ReverseAdjacency, Weight, WeightedCluster, WeightedNode};
+
+/// Compute the shortest paths from src to every reachable node with Dijkstra's algorithm.
+/// The weights of the edges must not be negative.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - src - The key of the node the paths start from.
+/// # Return
+/// The ShortestPaths from src, empty if src is not in the Cluster.
+pub fn dijkstra<K, N, C>(cluster: &C, src: &K) -> ShortestPaths<K, N::Weight>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    dijkstra_search::<K, N, C, (), _>(cluster, src, |_, _| Control::Continue).0
+}
+
+/// Compute a shortest path from src to dst with Dijkstra's algorithm, stopping as soon as dst is reached.
+/// The weights of the edges must not be negative.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - src - The key of the first node of the path.
+/// - dst - The key of the last node of the path.
+/// # Return
+/// An Option containing the length of the path and its keys, None if dst is not reachable from src.
+pub fn dijkstra_to<K, N, C>(cluster: &C, src: &K, dst: &K) -> Option<(N::Weight, Path<K>)>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let (paths, found) = dijkstra_search(cluster, src, |key, distance| {
+        if key == dst {
+            Control::Break(distance)
+        } else {
+            Control::Continue
+        }
+    });
+    found.map(|distance| {
+        let path = reconstruct_path(&paths.predecessors, dst);
+        (distance, Path::new(path))
+    })
+}
+
+/// Dijkstra's algorithm calling a visitor each time the shortest distance to a node is settled.
+/// Returning Prune keeps the edges of the settled node from being relaxed.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - src - The key of the node the search starts from.
+/// - visitor - The callback receiving each settled node key and its distance, its Control drives the search.
+/// # Return
+/// An Option containing the value of the Break returned by the visitor, None if the search went to its end.
+pub fn dijkstra_visit<K, N, C, T, F>(cluster: &C, src: &K, visitor: F) -> Option<T>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+    F: FnMut(&K, N::Weight) -> Control<T>,
+{
+    dijkstra_search(cluster, src, visitor).1
+}
+
+/// Compute the shortest paths from src to every reachable node with Dijkstra's algorithm, recording them in the given maps.
+/// A DenseMap avoids hashing the keys, the maps should be empty when the search starts.
+/// The weights of the edges must not be negative.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - src - The key of the node the paths start from.
+/// - distances - The map receiving the distance from src to each reached node.
+/// - predecessors - The map receiving the previous node on a shortest path, for each reached node but src.
+pub fn dijkstra_with_maps<K, N, C, D, P>(
+    cluster: &C,
+    src: &K,
+    distances: &mut D,
+    predecessors: &mut P,
+) where
+    K: PartialEq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+    D: VisitMap<K, N::Weight>,
+    P: VisitMap<K, K>,
+{
+    if !cluster.contains_key(src) {
+        return;
+    }
+    distances.insert(src.clone(), N::Weight::zero());
+    let mut frontier = BinaryHeap::from([MinScored(N::Weight::zero(), src.clone())]);
+    while let Some(MinScored(distance, key)) = frontier.pop() {
+        if distances.get(&key).is_some_and(|best| *best < distance) {
+            continue;
+        }
+        let Some(node) = cluster.get(&key) else {
+            continue;
+        };
+        for next in node.adj() {
+            if !cluster.contains_key(next) {
+                continue;
+            }
+            let candidate = distance + node.weight(next);
+            if distances
+                .get(next)
+                .is_none_or(|current| candidate < *current)
+            {
+                distances.insert(next.clone(), candidate);
+                predecessors.insert(next.clone(), key.clone());
+                frontier.push(MinScored(candidate, next.clone()));
+            }
+        }
+    }
+}
+
+/// Compute a shortest path from src to dst with Dijkstra's algorithm, searched from both ends at the same time.
+/// The Cluster must be able to list the predecessors of its nodes, as an undirected Cluster does.
+/// The weights of the edges must not be negative.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - src - The key of the first node of the path.
+/// - dst - The key of the last node of the path.
+/// # Return
+/// An Option containing the length of the path and its keys, None if dst is not reachable from src.
+pub fn bidirectional_dijkstra<K, N, C>(
+    cluster: &C,
+    src: &K,
+    dst: &K,
+) -> Option<(N::Weight, Path<K>)>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N> + ReverseAdjacency<K>,
+{
+    bidirectional_dijkstra_with(cluster, cluster, src, dst)
+}
+
+/// Compute a shortest path from src to dst with Dijkstra's algorithm, searched from both ends at the same time.
+/// The searches stop once the sum of their frontier distances exceeds the best path met so far.
+/// The weights of the edges must not be negative.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - reverse - The reverse adjacency of the Cluster, a ReverseIndex for instance.
+/// - src - The key of the first node of the path.
+/// - dst - The key of the last node of the path.
+/// # Return
+/// An Option containing the length of the path and its keys, None if dst is not reachable from src.
+pub fn bidirectional_dijkstra_with<K, N, C, R>(
+    cluster: &C,
+    reverse: &R,
+    src: &K,
+    dst: &K,
+) -> Option<(N::Weight, Path<K>)>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+    R: ReverseAdjacency<K>,
+{
+    if !cluster.contains_key(src) || !cluster.contains_key(dst) {
+        return None;
+    }
+    let mut forward = DijkstraSide::new(src);
+    let mut backward = DijkstraSide::new(dst);
+    let mut best: Option<(N::Weight, K)> = (src == dst).then(|| (N::Weight::zero(), src.clone()));
+    while let (Some(top_forward), Some(top_backward)) = (forward.peek(), backward.peek()) {
+        if best
+            .as_ref()
+            .is_some_and(|(length, _)| top_forward + top_backward >= *length)
+        {
+            break;
+        }
+        if top_forward <= top_backward {
+            forward.step(&backward, &mut best, |key| {
+                cluster.weighted_adj(key).unwrap_or_default()
+            });
+        } else {
+            backward.step(&forward, &mut best, |key| {
+                reverse
+                    .reverse_adj(key)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|previous| Some((previous.clone(), cluster.weight(previous, key)?)))
+                    .collect()
+            });
+        }
+    }
+    let (length, meeting) = best?;
+    let mut path = reconstruct_path(&forward.predecessors, &meeting);
+    let mut rest = reconstruct_path(&backward.predecessors, &meeting);
+    rest.reverse();
+    path.extend(rest.into_iter().skip(1));
+    Some((length, Path::new(path)))
+}
+
+/// One side of a bidirectional Dijkstra search.
+struct DijkstraSide<K, W> {
+    distances: HashMap<K, W>,
+    predecessors: HashMap<K, K>,
+    settled: HashSet<K>,
+    frontier: BinaryHeap<MinScored<W, K>>,
+}
+
+impl<K: Hash + Eq + Clone, W: Weight> DijkstraSide<K, W> {
+    fn new(start: &K) -> DijkstraSide<K, W> {
+        DijkstraSide {
+            distances: HashMap::from([(start.clone(), W::zero())]),
+            predecessors: HashMap::new(),
+            settled: HashSet::new(),
+            frontier: BinaryHeap::from([MinScored(W::zero(), start.clone())]),
+        }
+    }
+
+    /// Get the distance of the next node to settle, dropping the stale entries of the frontier.
+    fn peek(&mut self) -> Option<W> {
+        while let Some(MinScored(distance, key)) = self.frontier.peek() {
+            if !self.settled.contains(key) {
+                return Some(*distance);
+            }
+            self.frontier.pop();
+        }
+        None
+    }
+
+    /// Settle the next node, updating the best path met by the other side.
+    fn step<F>(&mut self, other: &DijkstraSide<K, W>, best: &mut Option<(W, K)>, neighbours: F)
+    where
+        F: Fn(&K) -> Vec<(K, W)>,
+    {
+        let Some(MinScored(distance, key)) = self.frontier.pop() else {
+            return;
+        };
+        self.settled.insert(key.clone());
+        for (next, weight) in neighbours(&key) {
+            let candidate = distance + weight;
+            if self
+                .distances
+                .get(&next)
+                .is_none_or(|current| candidate < *current)
+            {
+                self.distances.insert(next.clone(), candidate);
+                self.predecessors.insert(next.clone(), key.clone());
+                self.frontier.push(MinScored(candidate, next.clone()));
+            }
+            if let Some(remaining) = other.distances.get(&next) {
+                let length = self.distances[&next] + *remaining;
+                if best.as_ref().is_none_or(|(current, _)| length < *current) {
+                    *best = Some((length, next));
+                }
+            }
+        }
+    }
+}
+
+/// Compute a shortest path from src to goal with the A* algorithm, guided by a heuristic.
+/// The heuristic must never overestimate the remaining distance to goal, for the path to be optimal.
+/// The costs of the edges must not be negative.
+/// # Parameters
+/// - cluster - The Cluster to search.
+/// - src - The key of the first node of the path.
+/// - goal - The key of the last node of the path.
+/// - heuristic - The callback estimating the distance from a node to goal.
+/// - cost - The callback giving the cost of the edge going from its first key to its second key.
+/// # Return
+/// An Option containing the cost of the path and its keys, None if goal is not reachable from src.
+pub fn astar<K, N, C, W, H, F>(
+    cluster: &C,
+    src: &K,
+    goal: &K,
+    mut heuristic: H,
+    mut cost: F,
+) -> Option<(W, Path<K>)>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    W: Weight,
+    H: FnMut(&K) -> W,
+    F: FnMut(&K, &K) -> W,
+{
+    if !cluster.contains_key(src) || !cluster.contains_key(goal) {
+        return None;
+    }
+    let mut distances = HashMap::from([(src.clone(), W::zero())]);
+    let mut predecessors = HashMap::new();
+    let mut frontier = BinaryHeap::from([MinScored(heuristic(src), (W::zero(), src.clone()))]);
+    while let Some(MinScored(_, (distance, key))) = frontier.pop() {
+        if key == *goal {
+            return Some((distance, Path::new(reconstruct_path(&predecessors, goal))));
+        }
+        if distances.get(&key).is_some_and(|best| *best < distance) {
+            continue;
+        }
+        for next in cluster.get_adj(&key).into_iter().flatten() {
+            if !cluster.contains_key(next) {
+                continue;
+            }
+            let candidate = distance + cost(&key, next);
+            if distances
+                .get(next)
+                .is_none_or(|current| candidate < *current)
+            {
+                distances.insert(next.clone(), candidate);
+                predecessors.insert(next.clone(), key.clone());
+                frontier.push(MinScored(
+                    candidate + heuristic(next),
+                    (candidate, next.clone()),
+                ));
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm with a binary heap frontier, driven by a visitor called on each settled node.
+fn dijkstra_search<K, N, C, T, F>(
+    cluster: &C,
+    src: &K,
+    mut visitor: F,
+) -> (ShortestPaths<K, N::Weight>, Option<T>)
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+    F: FnMut(&K, N::Weight) -> Control<T>,
+{
+    let mut paths = ShortestPaths {
+        source: src.clone(),
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+    };
+    if !cluster.contains_key(src) {
+        return (paths, None);
+    }
+    paths.distances.insert(src.clone(), N::Weight::zero());
+    let mut settled = KeyMap::for_cluster(cluster);
+    let mut frontier = BinaryHeap::from([MinScored(N::Weight::zero(), src.clone())]);
+    while let Some(MinScored(distance, key)) = frontier.pop() {
+        if settled.insert(key.clone(), ()).is_some() {
+            continue;
+        }
+        match visitor(&key, distance) {
+            Control::Break(value) => return (paths, Some(value)),
+            Control::Prune => continue,
+            Control::Continue => {}
+        }
+        let Some(node) = cluster.get(&key) else {
+            continue;
+        };
+        for next in node.adj() {
+            if settled.contains_key(next) || !cluster.contains_key(next) {
+                continue;
+            }
+            let candidate = distance + node.weight(next);
+            if paths
+                .distances
+                .get(next)
+                .is_none_or(|current| candidate < *current)
+            {
+                paths.distances.insert(next.clone(), candidate);
+                paths.predecessors.insert(next.clone(), key.clone());
+                frontier.push(MinScored(candidate, next.clone()));
+            }
+        }
+    }
+    (paths, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashCluster, ReverseIndex, WeightedBasicNode};
+
+    fn network() -> HashCluster<u32, WeightedBasicNode<u32, i64>> {
+        let mut cluster = HashCluster::new();
+        for key in 0..5 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)] {
+            cluster.add_weighted_edge(src, dst, weight).unwrap();
+        }
+        cluster
+    }
+
+    #[test]
+    fn lighter_detours_win_over_direct_edges() {
+        let cluster = network();
+        let paths = dijkstra(&cluster, &0);
+        assert_eq!(paths.distance(&1), Some(3));
+        assert_eq!(paths.distance(&3), Some(4));
+        assert_eq!(paths.distance(&4), None);
+        assert_eq!(paths.path_to(&3), Some(Path::new(vec![0, 2, 1, 3])));
+        assert!(dijkstra(&cluster, &9).distances.is_empty());
+    }
+
+    #[test]
+    fn every_point_to_point_search_agrees() {
+        let cluster = network();
+        let expected = Some((4, Path::new(vec![0, 2, 1, 3])));
+        assert_eq!(dijkstra_to(&cluster, &0, &3), expected);
+        let reverse = ReverseIndex::new(&cluster);
+        assert_eq!(
+            bidirectional_dijkstra_with(&cluster, &reverse, &0, &3),
+            expected
+        );
+        let cost = |src: &u32, dst: &u32| cluster.weight(src, dst).unwrap();
+        assert_eq!(astar(&cluster, &0, &3, |_| 0, cost), expected);
+        assert_eq!(dijkstra_to(&cluster, &3, &0), None);
+        assert_eq!(
+            bidirectional_dijkstra_with(&cluster, &reverse, &0, &4),
+            None
+        );
+    }
+
+    #[test]
+    fn the_visitor_stops_the_search() {
+        let cluster = network();
+        let first = dijkstra_visit(&cluster, &0, |key, distance| {
+            if distance >= 3 {
+                Control::Break((*key, distance))
+            } else {
+                Control::Continue
+            }
+        });
+        assert_eq!(first, Some((1, 3)));
+    }
+}
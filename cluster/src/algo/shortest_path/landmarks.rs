@@ -0,0 +1,134 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::{weighted_index, WeightedIndex};
+use crate::{Weight, WeightedCluster, WeightedNode};
+
+/// Distances from and to a few landmark nodes, giving lower bounds usable as an A* heuristic (ALT).
+/// By the triangle inequality, d(v, t) is at least d(l, t) - d(l, v) and d(v, l) - d(t, l) for every landmark l.
+/// The landmarks are chosen far from each other, the next one being the farthest node from those already chosen.
+/// The distances are not updated when the Cluster is modified afterward, the weights of the edges must not be negative.
+#[derive(Debug, Clone)]
+pub struct Landmarks<K, W> {
+    landmarks: Vec<K>,
+    index: HashMap<K, usize>,
+    from: Vec<Vec<Option<W>>>,
+    to: Vec<Vec<Option<W>>>,
+}
+
+impl<K: Hash + Eq + Clone, W: Weight> Landmarks<K, W> {
+    /// Select the landmarks of a Cluster and compute their distances.
+    /// # Parameters
+    /// - cluster - The weighted Cluster to preprocess.
+    /// - count - The number of landmarks to select, more landmarks give tighter bounds but cost more memory.
+    /// # Return
+    /// The newly computed Landmarks.
+    pub fn new<N, C>(cluster: &C, count: usize) -> Landmarks<K, W>
+    where
+        N: WeightedNode<K, Weight = W>,
+        C: WeightedCluster<K, N>,
+    {
+        let WeightedIndex { keys, index, adj } = weighted_index(cluster);
+        let mut reverse = vec![Vec::new(); keys.len()];
+        for (src, edges) in adj.iter().enumerate() {
+            for &(dst, weight) in edges {
+                reverse[dst].push((src, weight));
+            }
+        }
+        let mut chosen = Vec::new();
+        let mut from = Vec::new();
+        let mut to = Vec::new();
+        let mut nearest: Vec<Option<W>> = vec![None; keys.len()];
+        let mut next = 0;
+        while chosen.len() < count.min(keys.len()) {
+            chosen.push(next);
+            from.push(distances(&adj, next));
+            to.push(distances(&reverse, next));
+            for (v, distance) in from.last().unwrap().iter().enumerate() {
+                nearest[v] = match (nearest[v], distance) {
+                    (Some(a), Some(b)) if *b < a => Some(*b),
+                    (None, distance) => *distance,
+                    (current, _) => current,
+                };
+            }
+            for &landmark in &chosen {
+                nearest[landmark] = Some(W::zero());
+            }
+            let unreached = (0..keys.len()).find(|v| nearest[*v].is_none());
+            let farthest = (0..keys.len())
+                .filter(|v| !chosen.contains(v))
+                .reduce(|a, b| if nearest[b] > nearest[a] { b } else { a });
+            match unreached.or(farthest) {
+                Some(v) => next = v,
+                None => break,
+            }
+        }
+        Landmarks {
+            landmarks: chosen.into_iter().map(|v| keys[v].clone()).collect(),
+            index,
+            from,
+            to,
+        }
+    }
+
+    /// Get the selected landmarks.
+    /// # Return
+    /// The keys of the landmark nodes.
+    pub fn landmarks(&self) -> &[K] {
+        &self.landmarks
+    }
+
+    /// Get a lower bound of the distance from src to dst.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// The largest lower bound given by the landmarks, Weight::zero() if none of them is informative.
+    pub fn lower_bound(&self, src: &K, dst: &K) -> W {
+        let (Some(&src), Some(&dst)) = (self.index.get(src), self.index.get(dst)) else {
+            return W::zero();
+        };
+        let mut bound = W::zero();
+        for (from, to) in self.from.iter().zip(&self.to) {
+            for (far, near) in [(from[dst], from[src]), (to[src], to[dst])] {
+                if let (Some(far), Some(near)) = (far, near) {
+                    if far > near && far - near > bound {
+                        bound = far - near;
+                    }
+                }
+            }
+        }
+        bound
+    }
+
+    /// Get the heuristic guiding an A* search toward goal.
+    /// # Parameter
+    /// - goal - The key of the last node of the searched path.
+    /// # Return
+    /// An admissible heuristic, to be given to astar.
+    pub fn heuristic<'a>(&'a self, goal: &'a K) -> impl FnMut(&K) -> W + 'a {
+        move |key| self.lower_bound(key, goal)
+    }
+}
+
+/// Dijkstra's algorithm from src, giving the distance to every node.
+fn distances<W: Weight>(adj: &[Vec<(usize, W)>], src: usize) -> Vec<Option<W>> {
+    let mut distances = vec![None; adj.len()];
+    let mut settled = vec![false; adj.len()];
+    distances[src] = Some(W::zero());
+    let mut frontier = BinaryHeap::from([MinScored(W::zero(), src)]);
+    while let Some(MinScored(distance, v)) = frontier.pop() {
+        if std::mem::replace(&mut settled[v], true) {
+            continue;
+        }
+        for &(w, weight) in &adj[v] {
+            let candidate = distance + weight;
+            if !settled[w] && distances[w].is_none_or(|current| candidate < current) {
+                distances[w] = Some(candidate);
+                frontier.push(MinScored(candidate, w));
+            }
+        }
+    }
+    distances
+}
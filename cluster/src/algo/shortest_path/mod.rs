@@ -0,0 +1,113 @@
+//! Shortest paths between the nodes of a Cluster.
+
+mod all_pairs;
+mod bellman_ford;
+mod bfs;
+mod checkpoint;
+mod contraction;
+mod dijkstra;
+mod landmarks;
+mod query;
+mod routing;
+mod turns;
+mod yen;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Path, WeightedCluster, WeightedNode};
+
+pub use all_pairs::{floyd_warshall, DistanceMatrix};
+pub use bellman_ford::{bellman_ford, johnson, NegativeCycle};
+pub use bfs::{bidirectional_bfs, bidirectional_bfs_with};
+pub use checkpoint::{FloydWarshallRun, WeightCodec};
+pub use contraction::RoutingIndex;
+pub use dijkstra::{
+    astar, bidirectional_dijkstra, bidirectional_dijkstra_with, dijkstra, dijkstra_to,
+    dijkstra_visit, dijkstra_with_maps,
+};
+pub use landmarks::Landmarks;
+pub use query::{Query, Strategy};
+pub use routing::{routing_table, RoutingTable};
+pub use turns::{dijkstra_with_turns, TurnTable};
+pub use yen::k_shortest_paths;
+
+/// Distances and predecessors computed by a single source shortest path algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPaths<K: Hash + Eq, W> {
+    /// The key of the node the paths start from.
+    pub source: K,
+    /// Distance from the source to each reached node.
+    pub distances: HashMap<K, W>,
+    /// Previous node on a shortest path from the source, for each reached node but the source.
+    pub predecessors: HashMap<K, K>,
+}
+
+impl<K: Hash + Eq + Clone, W: Copy> ShortestPaths<K, W> {
+    /// Get the distance from the source to a node.
+    /// # Parameter
+    /// - dst - The key of the node.
+    /// # Return
+    /// An Option containing the distance, None if the node has not been reached.
+    pub fn distance(&self, dst: &K) -> Option<W> {
+        self.distances.get(dst).copied()
+    }
+
+    /// Get a shortest path from the source to a node.
+    /// # Parameter
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing the keys of the path, None if the node has not been reached.
+    pub fn path_to(&self, dst: &K) -> Option<Path<K>> {
+        self.distances
+            .contains_key(dst)
+            .then(|| Path::new(reconstruct_path(&self.predecessors, dst)))
+    }
+}
+
+/// Follow the predecessors from dst back to the node that has none, returning the keys in path order.
+pub(crate) fn reconstruct_path<K: Hash + Eq + Clone>(
+    predecessors: &HashMap<K, K>,
+    dst: &K,
+) -> Vec<K> {
+    let mut path = vec![dst.clone()];
+    while let Some(previous) = predecessors.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Snapshot of the weighted edges of a Cluster, each node being designed by its index.
+pub(crate) struct WeightedIndex<K, W> {
+    pub(crate) keys: Vec<K>,
+    pub(crate) index: HashMap<K, usize>,
+    pub(crate) adj: Vec<Vec<(usize, W)>>,
+}
+
+/// Index the weighted edges of a Cluster, edges toward keys not in the Cluster being ignored.
+pub(crate) fn weighted_index<K, N, C>(cluster: &C) -> WeightedIndex<K, N::Weight>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let keys: Vec<K> = cluster.keys().cloned().collect();
+    let index: HashMap<K, usize> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, k)| (k.clone(), i))
+        .collect();
+    let adj = keys
+        .iter()
+        .map(|key| {
+            cluster
+                .weighted_adj(key)
+                .into_iter()
+                .flatten()
+                .filter_map(|(dst, weight)| Some((*index.get(&dst)?, weight)))
+                .collect()
+        })
+        .collect();
+    WeightedIndex { keys, index, adj }
+}
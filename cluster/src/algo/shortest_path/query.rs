@@ -0,0 +1,145 @@
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::algo::shortest_path::{
+    bellman_ford, bidirectional_bfs_with, bidirectional_dijkstra_with, dijkstra_to, RoutingIndex,
+};
+use crate::{Path, ReverseIndex, Weight, WeightedCluster, WeightedNode};
+
+/// Number of nodes from which a Query searches from both ends.
+const BIDIRECTIONAL_THRESHOLD: usize = 1_000;
+/// Number of nodes from which a Query builds a contraction hierarchy.
+const HIERARCHY_THRESHOLD: usize = 20_000;
+
+/// Algorithm used by a Query to answer its shortest path requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Breadth-first search from both ends, for the Clusters whose edges all weigh the same.
+    BidirectionalBfs,
+    /// Dijkstra's algorithm from the source, for the small Clusters.
+    Dijkstra,
+    /// Dijkstra's algorithm from both ends, for the medium-sized Clusters.
+    BidirectionalDijkstra,
+    /// Contraction hierarchy built once, for the large Clusters queried many times.
+    ContractionHierarchy,
+    /// Bellman–Ford algorithm, for the Clusters having negative weights.
+    BellmanFord,
+}
+
+/// Shortest path facade choosing its algorithm from the characteristics of a Cluster.
+/// The Cluster is inspected once at creation, the indexes the chosen Strategy needs being built then,
+/// so a Query should be kept as long as the Cluster does not change.
+#[derive(Debug)]
+pub struct Query<'clu, K: Hash + Eq, N: WeightedNode<K>, C> {
+    cluster: &'clu C,
+    strategy: Strategy,
+    reverse: Option<ReverseIndex<K>>,
+    hierarchy: Option<RoutingIndex<K, N::Weight>>,
+    node: PhantomData<N>,
+}
+
+impl<'clu, K, N, C> Query<'clu, K, N, C>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    /// Inspect a Cluster and prepare the best suited Strategy.
+    /// Uniform weights lead to a breadth-first search, negative weights to Bellman–Ford,
+    /// the other Clusters going from Dijkstra to a contraction hierarchy as their size grows.
+    /// # Parameter
+    /// - cluster - The weighted Cluster to query.
+    /// # Return
+    /// The newly created Query.
+    pub fn new(cluster: &'clu C) -> Query<'clu, K, N, C> {
+        let mut weights = cluster
+            .keys()
+            .flat_map(|key| cluster.weighted_adj(key).unwrap_or_default())
+            .map(|(_, weight)| weight);
+        let first = weights.next();
+        let (mut negative, mut uniform) = (first.is_some_and(|w| w < N::Weight::zero()), true);
+        for weight in weights {
+            negative |= weight < N::Weight::zero();
+            uniform &= first == Some(weight);
+        }
+        let strategy = if negative {
+            Strategy::BellmanFord
+        } else if uniform {
+            Strategy::BidirectionalBfs
+        } else if cluster.len() >= HIERARCHY_THRESHOLD {
+            Strategy::ContractionHierarchy
+        } else if cluster.len() >= BIDIRECTIONAL_THRESHOLD {
+            Strategy::BidirectionalDijkstra
+        } else {
+            Strategy::Dijkstra
+        };
+        Query::with_strategy(cluster, strategy)
+    }
+
+    /// Prepare a given Strategy instead of letting the Query choose it.
+    /// # Parameters
+    /// - cluster - The weighted Cluster to query.
+    /// - strategy - The algorithm to use.
+    /// # Return
+    /// The newly created Query.
+    pub fn with_strategy(cluster: &'clu C, strategy: Strategy) -> Query<'clu, K, N, C> {
+        let bidirectional = matches!(
+            strategy,
+            Strategy::BidirectionalBfs | Strategy::BidirectionalDijkstra
+        );
+        Query {
+            cluster,
+            strategy,
+            reverse: bidirectional.then(|| ReverseIndex::new(cluster)),
+            hierarchy: (strategy == Strategy::ContractionHierarchy)
+                .then(|| RoutingIndex::new(cluster)),
+            node: PhantomData,
+        }
+    }
+
+    /// Get the algorithm used by the Query.
+    /// # Return
+    /// The Strategy of the Query.
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Compute a shortest path from src to dst with the Strategy of the Query.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing the length of the path and its keys, None if dst is not reachable from src or a negative cycle is reachable from src.
+    pub fn shortest_path(&self, src: &K, dst: &K) -> Option<(N::Weight, Path<K>)> {
+        match (self.strategy, &self.reverse, &self.hierarchy) {
+            (Strategy::BidirectionalBfs, Some(reverse), _) => {
+                let path = bidirectional_bfs_with(self.cluster, reverse, src, dst)?;
+                Some((path.cost(self.cluster)?, path))
+            }
+            (Strategy::BidirectionalDijkstra, Some(reverse), _) => {
+                bidirectional_dijkstra_with(self.cluster, reverse, src, dst)
+            }
+            (Strategy::ContractionHierarchy, _, Some(hierarchy)) => {
+                hierarchy.shortest_path(src, dst)
+            }
+            (Strategy::BellmanFord, _, _) => {
+                let paths = bellman_ford(self.cluster, src).ok()?;
+                Some((paths.distance(dst)?, paths.path_to(dst)?))
+            }
+            _ => dijkstra_to(self.cluster, src, dst),
+        }
+    }
+
+    /// Compute the length of a shortest path from src to dst with the Strategy of the Query.
+    /// # Parameters
+    /// - src - The key of the first node of the path.
+    /// - dst - The key of the last node of the path.
+    /// # Return
+    /// An Option containing the length of the path, None if dst is not reachable from src or a negative cycle is reachable from src.
+    pub fn distance(&self, src: &K, dst: &K) -> Option<N::Weight> {
+        match &self.hierarchy {
+            Some(hierarchy) => hierarchy.distance(src, dst),
+            None => self.shortest_path(src, dst).map(|(length, _)| length),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::{weighted_index, DistanceMatrix, WeightedIndex};
+use crate::{ClusterError, Path, Result, Weight, WeightedCluster, WeightedNode};
+
+/// Value of a cell of the table when the destination is not reachable.
+const NO_ROUTE: u32 = u32::MAX;
+
+/// Next hop of a shortest path between every pair of nodes, stored as a compact matrix of node indices.
+/// The table serializes to a flat byte buffer, the keys being serialized apart by the caller in the order of keys().
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutingTable<K: Hash + Eq> {
+    keys: Vec<K>,
+    index: HashMap<K, usize>,
+    hops: Vec<u32>,
+}
+
+impl<K: Hash + Eq + Clone> RoutingTable<K> {
+    /// Extract the routing table of a DistanceMatrix.
+    /// # Parameter
+    /// - matrix - The DistanceMatrix, computed with its next hops.
+    /// # Return
+    /// An Option containing the RoutingTable, None if the next hops of the matrix were not computed.
+    pub fn from_matrix<W>(matrix: &DistanceMatrix<K, W>) -> Option<RoutingTable<K>> {
+        let hops = matrix
+            .next_hops
+            .as_ref()?
+            .iter()
+            .map(|hop| hop.map_or(NO_ROUTE, to_index))
+            .collect();
+        Some(RoutingTable {
+            keys: matrix.keys.clone(),
+            index: matrix.index.clone(),
+            hops,
+        })
+    }
+
+    /// Rebuild a routing table from its serialized form.
+    /// # Parameters
+    /// - keys - The keys of the table, in the order given by keys() when it was serialized.
+    /// - bytes - The buffer written by to_bytes.
+    /// # Return
+    /// The RoutingTable, an error if the buffer does not match the keys.
+    pub fn from_bytes(keys: Vec<K>, bytes: &[u8]) -> Result<RoutingTable<K>> {
+        let n = keys.len();
+        if bytes.len() != 4 * n * n {
+            return Err(ClusterError::detailled(
+                "The buffer size does not match the number of keys.",
+            )
+            .into());
+        }
+        let hops: Vec<u32> = bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        if hops
+            .iter()
+            .any(|hop| *hop != NO_ROUTE && *hop as usize >= n)
+        {
+            return Err(ClusterError::detailled("The buffer refers to an unknown key.").into());
+        }
+        let index = keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.clone(), i))
+            .collect();
+        Ok(RoutingTable { keys, index, hops })
+    }
+
+    /// Serialize the next hops of the table as little endian 32 bits indices into keys().
+    /// # Return
+    /// The buffer holding the table, row by row.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.hops.iter().flat_map(|hop| hop.to_le_bytes()).collect()
+    }
+
+    /// Get the keys of the table, in the order of its rows.
+    /// # Return
+    /// The keys of the nodes of the table.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Get the node following src on a shortest path from src to dst.
+    /// # Parameters
+    /// - src - The key of the node looking up its route.
+    /// - dst - The key of the destination.
+    /// # Return
+    /// An Option containing the key of the next hop, src itself if src is dst, None if dst is not reachable.
+    pub fn next_hop(&self, src: &K, dst: &K) -> Option<&K> {
+        let (i, j) = (*self.index.get(src)?, *self.index.get(dst)?);
+        let hop = self.hops[i * self.keys.len() + j];
+        (hop != NO_ROUTE).then(|| &self.keys[hop as usize])
+    }
+
+    /// Follow the next hops from src to dst.
+    /// # Parameters
+    /// - src - The key of the first node of the route.
+    /// - dst - The key of the last node of the route.
+    /// # Return
+    /// An Option containing the path of the route, None if dst is not reachable.
+    pub fn route(&self, src: &K, dst: &K) -> Option<Path<K>> {
+        let mut path = vec![src.clone()];
+        while path.last() != Some(dst) {
+            if path.len() > self.keys.len() {
+                return None;
+            }
+            path.push(self.next_hop(path.last()?, dst)?.clone());
+        }
+        Some(Path::new(path))
+    }
+}
+
+/// Build the routing table of a Cluster by running Dijkstra's algorithm from every node.
+/// Faster than Floyd–Warshall on sparse Clusters, the weights of the edges must not be negative.
+/// # Parameter
+/// - cluster - The weighted Cluster to route.
+/// # Return
+/// The RoutingTable of the Cluster.
+pub fn routing_table<K, N, C>(cluster: &C) -> RoutingTable<K>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let WeightedIndex { keys, index, adj } = weighted_index(cluster);
+    let n = keys.len();
+    let mut hops = vec![NO_ROUTE; n * n];
+    for src in 0..n {
+        let row = &mut hops[src * n..(src + 1) * n];
+        let mut distances: Vec<Option<N::Weight>> = vec![None; n];
+        let mut settled = vec![false; n];
+        distances[src] = Some(N::Weight::zero());
+        row[src] = to_index(src);
+        let mut frontier = BinaryHeap::from([MinScored(N::Weight::zero(), src)]);
+        while let Some(MinScored(distance, v)) = frontier.pop() {
+            if std::mem::replace(&mut settled[v], true) {
+                continue;
+            }
+            for &(w, weight) in &adj[v] {
+                let candidate = distance + weight;
+                if !settled[w] && distances[w].is_none_or(|current| candidate < current) {
+                    distances[w] = Some(candidate);
+                    row[w] = if v == src { to_index(w) } else { row[v] };
+                    frontier.push(MinScored(candidate, w));
+                }
+            }
+        }
+    }
+    RoutingTable { keys, index, hops }
+}
+
+/// Convert a node index into a cell of the table.
+fn to_index(v: usize) -> u32 {
+    u32::try_from(v)
+        .ok()
+        .filter(|v| *v != NO_ROUTE)
+        .expect("The Cluster has too many nodes for a RoutingTable.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::shortest_path::{dijkstra_to, floyd_warshall};
+    use crate::{HashCluster, WeightedBasicNode};
+
+    fn network() -> HashCluster<u32, WeightedBasicNode<u32, i64>> {
+        let mut cluster = HashCluster::new();
+        for key in 0..5 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)] {
+            cluster.add_weighted_edge(src, dst, weight).unwrap();
+        }
+        cluster
+    }
+
+    #[test]
+    fn routes_follow_the_shortest_paths() {
+        let cluster = network();
+        let table = routing_table(&cluster);
+        assert_eq!(table.next_hop(&0, &3), Some(&2));
+        assert_eq!(table.next_hop(&1, &1), Some(&1));
+        assert_eq!(
+            table.route(&0, &3),
+            dijkstra_to(&cluster, &0, &3).map(|(_, path)| path)
+        );
+        assert_eq!(table.route(&3, &0), None);
+        let matrix = floyd_warshall(&cluster, true);
+        assert_eq!(RoutingTable::from_matrix(&matrix), Some(table));
+        assert_eq!(
+            RoutingTable::from_matrix(&floyd_warshall(&cluster, false)),
+            None
+        );
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let table = routing_table(&network());
+        let restored = RoutingTable::from_bytes(table.keys().to_vec(), &table.to_bytes()).unwrap();
+        assert_eq!(restored, table);
+        let bytes = table.to_bytes();
+        assert!(RoutingTable::from_bytes(table.keys()[1..].to_vec(), &bytes).is_err());
+        let mut corrupted = bytes;
+        corrupted[..4].copy_from_slice(&9u32.to_le_bytes());
+        assert!(RoutingTable::from_bytes(table.keys().to_vec(), &corrupted).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::algo::scored::MinScored;
+use crate::{Path, Weight, WeightedCluster, WeightedNode};
+
+/// Set of the forbidden turns of a Cluster : sequences of two edges that a path cannot take one after the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnTable<K: Hash + Eq> {
+    banned: HashSet<(K, K, K)>,
+}
+
+impl<K: Hash + Eq> TurnTable<K> {
+    /// Create a TurnTable allowing every turn.
+    /// # Return
+    /// The newly created TurnTable.
+    pub fn new() -> TurnTable<K> {
+        TurnTable {
+            banned: HashSet::new(),
+        }
+    }
+
+    /// Forbid to take the edge (via, to) right after the edge (from, via).
+    /// # Parameters
+    /// - from - The key of the node the turn comes from.
+    /// - via - The key of the node at which the turn is made.
+    /// - to - The key of the node the turn goes to.
+    pub fn ban(&mut self, from: K, via: K, to: K) {
+        self.banned.insert((from, via, to));
+    }
+
+    /// Allow again a turn previously forbidden.
+    /// # Parameters
+    /// - from - The key of the node the turn comes from.
+    /// - via - The key of the node at which the turn is made.
+    /// - to - The key of the node the turn goes to.
+    /// # Return
+    /// True if the turn was forbidden, false otherwise.
+    pub fn allow(&mut self, from: K, via: K, to: K) -> bool {
+        self.banned.remove(&(from, via, to))
+    }
+
+    /// Check if a turn is forbidden.
+    /// # Parameters
+    /// - from - The key of the node the turn comes from.
+    /// - via - The key of the node at which the turn is made.
+    /// - to - The key of the node the turn goes to.
+    /// # Return
+    /// True if the turn is forbidden, false otherwise.
+    pub fn is_banned(&self, from: &K, via: &K, to: &K) -> bool
+    where
+        K: Clone,
+    {
+        self.banned
+            .contains(&(from.clone(), via.clone(), to.clone()))
+    }
+}
+
+impl<K: Hash + Eq> Default for TurnTable<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compute a shortest path from src to dst with Dijkstra's algorithm, never taking a forbidden turn.
+/// The search runs over (node, previous node) states, so a node may be crossed several times when turns force detours.
+/// The weights of the edges must not be negative.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - src - The key of the first node of the path.
+/// - dst - The key of the last node of the path.
+/// - turns - The forbidden turns.
+/// # Return
+/// An Option containing the length of the path and its keys, None if dst is not reachable from src.
+pub fn dijkstra_with_turns<K, N, C>(
+    cluster: &C,
+    src: &K,
+    dst: &K,
+    turns: &TurnTable<K>,
+) -> Option<(N::Weight, Path<K>)>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    if !cluster.contains_key(src) || !cluster.contains_key(dst) {
+        return None;
+    }
+    type State<K> = (K, Option<K>);
+    let start: State<K> = (src.clone(), None);
+    let mut distances: HashMap<State<K>, N::Weight> =
+        HashMap::from([(start.clone(), N::Weight::zero())]);
+    let mut predecessors: HashMap<State<K>, State<K>> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut frontier = BinaryHeap::from([MinScored(N::Weight::zero(), start)]);
+    while let Some(MinScored(distance, state)) = frontier.pop() {
+        if !settled.insert(state.clone()) {
+            continue;
+        }
+        let (key, previous) = &state;
+        if key == dst {
+            let mut path = vec![key.clone()];
+            let mut current = &state;
+            while let Some(before) = predecessors.get(current) {
+                path.push(before.0.clone());
+                current = before;
+            }
+            path.reverse();
+            return Some((distance, Path::new(path)));
+        }
+        let Some(node) = cluster.get(key) else {
+            continue;
+        };
+        for next in node.adj() {
+            if !cluster.contains_key(next)
+                || previous
+                    .as_ref()
+                    .is_some_and(|from| turns.is_banned(from, key, next))
+            {
+                continue;
+            }
+            let next_state = (next.clone(), Some(key.clone()));
+            if settled.contains(&next_state) {
+                continue;
+            }
+            let candidate = distance + node.weight(next);
+            if distances
+                .get(&next_state)
+                .is_none_or(|current| candidate < *current)
+            {
+                distances.insert(next_state.clone(), candidate);
+                predecessors.insert(next_state.clone(), state.clone());
+                frontier.push(MinScored(candidate, next_state));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,136 @@
+use std::collections::{BinaryHeap, HashSet};
+use std::hash::Hash;
+
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::{weighted_index, WeightedIndex};
+use crate::{Path, Weight, WeightedCluster, WeightedNode};
+
+/// Compute up to k shortest simple paths from src to dst with Yen's algorithm.
+/// Each spur path is found by Dijkstra's algorithm, the weights of the edges must not be negative.
+/// # Parameters
+/// - cluster - The weighted Cluster to search.
+/// - src - The key of the first node of the paths.
+/// - dst - The key of the last node of the paths.
+/// - k - The maximum number of paths to compute.
+/// # Return
+/// The distinct paths along with their length, ordered by increasing length, empty if dst is not reachable from src.
+pub fn k_shortest_paths<K, N, C>(
+    cluster: &C,
+    src: &K,
+    dst: &K,
+    k: usize,
+) -> Vec<(N::Weight, Path<K>)>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let graph = weighted_index(cluster);
+    let (Some(&src), Some(&dst)) = (graph.index.get(src), graph.index.get(dst)) else {
+        return Vec::new();
+    };
+    let mut blocked_nodes = vec![false; graph.keys.len()];
+    let mut blocked_edges = HashSet::new();
+    let Some(first) = spur_path(&graph, src, dst, &blocked_nodes, &blocked_edges) else {
+        return Vec::new();
+    };
+    let mut found: Vec<(N::Weight, Vec<usize>)> = Vec::new();
+    let mut seen = HashSet::from([first.1.clone()]);
+    let mut candidates = BinaryHeap::from([MinScored(first.0, first.1)]);
+    while found.len() < k {
+        let Some(MinScored(length, path)) = candidates.pop() else {
+            break;
+        };
+        let mut root_length = N::Weight::zero();
+        for i in 0..path.len() - 1 {
+            let spur = path[i];
+            let root = &path[..=i];
+            for (_, other) in &found {
+                if other.len() > i + 1 && other[..=i] == *root {
+                    blocked_edges.insert((other[i], other[i + 1]));
+                }
+            }
+            if path.len() > i + 1 {
+                blocked_edges.insert((path[i], path[i + 1]));
+            }
+            for &node in &root[..i] {
+                blocked_nodes[node] = true;
+            }
+            if let Some((spur_length, spur_nodes)) =
+                spur_path(&graph, spur, dst, &blocked_nodes, &blocked_edges)
+            {
+                let mut candidate = root[..i].to_vec();
+                candidate.extend(spur_nodes);
+                if seen.insert(candidate.clone()) {
+                    candidates.push(MinScored(root_length + spur_length, candidate));
+                }
+            }
+            blocked_edges.clear();
+            blocked_nodes
+                .iter_mut()
+                .for_each(|blocked| *blocked = false);
+            root_length = root_length + edge_weight(&graph, path[i], path[i + 1]);
+        }
+        found.push((length, path));
+    }
+    found
+        .into_iter()
+        .map(|(length, path)| {
+            (
+                length,
+                path.into_iter().map(|i| graph.keys[i].clone()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Get the weight of the lightest edge going from src to dst.
+fn edge_weight<K, W: Weight>(graph: &WeightedIndex<K, W>, src: usize, dst: usize) -> W {
+    graph.adj[src]
+        .iter()
+        .filter(|(next, _)| *next == dst)
+        .map(|(_, weight)| *weight)
+        .reduce(|a, b| if b < a { b } else { a })
+        .unwrap_or(W::zero())
+}
+
+/// Dijkstra's algorithm from src to dst avoiding the blocked nodes and edges.
+fn spur_path<K, W: Weight>(
+    graph: &WeightedIndex<K, W>,
+    src: usize,
+    dst: usize,
+    blocked_nodes: &[bool],
+    blocked_edges: &HashSet<(usize, usize)>,
+) -> Option<(W, Vec<usize>)> {
+    let mut distances: Vec<Option<W>> = vec![None; graph.keys.len()];
+    let mut predecessors = vec![usize::MAX; graph.keys.len()];
+    let mut settled = vec![false; graph.keys.len()];
+    distances[src] = Some(W::zero());
+    let mut frontier = BinaryHeap::from([MinScored(W::zero(), src)]);
+    while let Some(MinScored(distance, node)) = frontier.pop() {
+        if settled[node] {
+            continue;
+        }
+        settled[node] = true;
+        if node == dst {
+            let mut path = vec![dst];
+            while *path.last().unwrap() != src {
+                path.push(predecessors[*path.last().unwrap()]);
+            }
+            path.reverse();
+            return Some((distance, path));
+        }
+        for &(next, weight) in &graph.adj[node] {
+            if settled[next] || blocked_nodes[next] || blocked_edges.contains(&(node, next)) {
+                continue;
+            }
+            let candidate = distance + weight;
+            if distances[next].is_none_or(|current| candidate < current) {
+                distances[next] = Some(candidate);
+                predecessors[next] = node;
+                frontier.push(MinScored(candidate, next));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,120 @@
+//! Simple paths between two nodes of a Cluster.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::{Cluster, Node, Path};
+
+/// Lazy enumeration of the simple paths going from a node to another, in depth first order.
+/// A simple path never goes twice through the same node.
+pub struct AllSimplePaths<'clu, K, N, C> {
+    cluster: &'clu C,
+    dst: K,
+    max_len: Option<usize>,
+    path: Vec<K>,
+    on_path: HashSet<K>,
+    next_edges: Vec<usize>,
+    node: PhantomData<N>,
+}
+
+impl<'clu, K, N, C> AllSimplePaths<'clu, K, N, C>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    /// Create the enumeration of the simple paths from src to dst.
+    /// # Parameters
+    /// - cluster - The Cluster to search.
+    /// - src - The key of the first node of the paths.
+    /// - dst - The key of the last node of the paths.
+    /// - max_len - The maximum number of edges of a path, None for no limit.
+    /// # Return
+    /// The newly created AllSimplePaths, yielding nothing if src or dst is not in the Cluster.
+    pub fn new(cluster: &'clu C, src: &K, dst: &K, max_len: Option<usize>) -> Self {
+        let mut paths = AllSimplePaths {
+            cluster,
+            dst: dst.clone(),
+            max_len,
+            path: Vec::new(),
+            on_path: HashSet::new(),
+            next_edges: Vec::new(),
+            node: PhantomData,
+        };
+        if cluster.contains_key(src) && cluster.contains_key(dst) {
+            paths.path.push(src.clone());
+            paths.on_path.insert(src.clone());
+            paths.next_edges.push(0);
+        }
+        paths
+    }
+
+    /// Remove the last node of the current path.
+    fn backtrack(&mut self) {
+        if let Some(key) = self.path.pop() {
+            self.on_path.remove(&key);
+        }
+        self.next_edges.pop();
+    }
+}
+
+impl<K, N, C> Iterator for AllSimplePaths<'_, K, N, C>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    type Item = Path<K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(key) = self.path.last() {
+            let edges = self.next_edges.len() - 1;
+            if self.next_edges[edges] == 0 && *key == self.dst {
+                self.next_edges[edges] = usize::MAX;
+                return Some(Path::new(self.path.clone()));
+            }
+            let adj = self.cluster.get_adj(key).map_or(&[][..], |adj| adj);
+            let too_long = self.max_len.is_some_and(|max| edges >= max);
+            let next = adj
+                .get(self.next_edges[edges]..)
+                .filter(|_| !too_long)
+                .into_iter()
+                .flatten()
+                .position(|next| !self.on_path.contains(next) && self.cluster.contains_key(next));
+            match next {
+                Some(offset) => {
+                    let next = adj[self.next_edges[edges] + offset].clone();
+                    self.next_edges[edges] += offset + 1;
+                    self.on_path.insert(next.clone());
+                    self.path.push(next);
+                    self.next_edges.push(0);
+                }
+                None => self.backtrack(),
+            }
+        }
+        None
+    }
+}
+
+/// Enumerate lazily the simple paths going from src to dst.
+/// # Parameters
+/// - cluster - The Cluster to search.
+/// - src - The key of the first node of the paths.
+/// - dst - The key of the last node of the paths.
+/// - max_len - The maximum number of edges of a path, None for no limit.
+/// # Return
+/// An iterator over the keys of each simple path, empty if src or dst is not in the Cluster.
+pub fn all_simple_paths<'clu, K, N, C>(
+    cluster: &'clu C,
+    src: &K,
+    dst: &K,
+    max_len: Option<usize>,
+) -> AllSimplePaths<'clu, K, N, C>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    AllSimplePaths::new(cluster, src, dst, max_len)
+}
@@ -0,0 +1,443 @@
+//! Spanning trees of a weighted Cluster.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+use crate::algo::connectivity::DisjointSet;
+#[cfg(feature = "rand")]
+use crate::algo::indexed::IndexedGraph;
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::weighted_index;
+#[cfg(feature = "rand")]
+use crate::{BasicNode, Cluster, RandomSource};
+use crate::{
+    Node, UndirectedHashCluster, Weight, WeightedBasicNode, WeightedCluster, WeightedNode,
+};
+
+/// Edges selected by a spanning tree algorithm, along with their total weight.
+/// On a disconnected Cluster, the edges form a spanning forest with a tree per connected component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningTree<K, W> {
+    /// The (source, destination, weight) triples of the selected edges, in the order they were selected.
+    pub edges: Vec<(K, K, W)>,
+    /// The sum of the weights of the selected edges.
+    pub total: W,
+}
+
+/// Compute a minimum spanning tree of a weighted Cluster with Kruskal's algorithm.
+/// The edges are sorted by weight then added when they join two trees, the trees being tracked in a DisjointSet.
+/// The direction of the edges is ignored, and edges toward keys not in the Cluster are skipped.
+/// # Parameters
+/// - cluster - The weighted Cluster to span.
+/// - maximum - True to compute a maximum spanning tree instead, the heaviest edges being tried first.
+/// # Return
+/// The SpanningTree of the Cluster, a spanning forest if it is not connected.
+pub fn mst_kruskal<K, N, C>(cluster: &C, maximum: bool) -> SpanningTree<K, N::Weight>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let mut edges: Vec<(K, K, N::Weight)> = cluster
+        .keys()
+        .flat_map(|src| {
+            cluster
+                .weighted_adj(src)
+                .into_iter()
+                .flatten()
+                .filter(|(dst, _)| cluster.contains_key(dst))
+                .map(move |(dst, weight)| (src.clone(), dst, weight))
+        })
+        .collect();
+    edges.sort_by(|a, b| {
+        let order = a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal);
+        if maximum {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+    let mut trees = DisjointSet::new();
+    let mut tree = SpanningTree {
+        edges: Vec::new(),
+        total: N::Weight::zero(),
+    };
+    for (src, dst, weight) in edges {
+        if trees.union(src.clone(), dst.clone()) {
+            tree.total = tree.total + weight;
+            tree.edges.push((src, dst, weight));
+        }
+    }
+    tree
+}
+
+/// Compute a minimum spanning tree of a weighted Cluster with Prim's algorithm, growing the tree from its first key.
+/// The edges leaving the tree wait in a lazy priority queue, those reaching a node already in the tree being skipped when popped.
+/// Its cost depends on the number of edges rather than on their sort, which suits dense Clusters better than mst_kruskal.
+/// The direction of the edges is ignored, and edges toward keys not in the Cluster are skipped.
+/// When the Cluster is not connected, a new tree is grown from each node not reached yet.
+/// # Parameter
+/// - cluster - The weighted Cluster to span.
+/// # Return
+/// A new Cluster holding all the keys of the Cluster and only the edges of the spanning forest, with their weights.
+pub fn mst_prim<K, N, C>(cluster: &C) -> UndirectedHashCluster<K, WeightedBasicNode<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let index = weighted_index(cluster);
+    let n = index.keys.len();
+    let mut neighbours: Vec<Vec<(usize, N::Weight)>> = vec![Vec::new(); n];
+    for (src, dsts) in index.adj.iter().enumerate() {
+        for (dst, weight) in dsts {
+            if *dst != src {
+                neighbours[src].push((*dst, *weight));
+                neighbours[*dst].push((src, *weight));
+            }
+        }
+    }
+    let mut nodes: Vec<WeightedBasicNode<K, N::Weight>> =
+        (0..n).map(|_| WeightedBasicNode::new(())).collect();
+    let mut in_tree = vec![false; n];
+    let mut heap = BinaryHeap::new();
+    for root in 0..n {
+        if in_tree[root] {
+            continue;
+        }
+        in_tree[root] = true;
+        heap.extend(
+            neighbours[root]
+                .iter()
+                .map(|(dst, weight)| MinScored(*weight, (root, *dst))),
+        );
+        while let Some(MinScored(weight, (src, dst))) = heap.pop() {
+            if in_tree[dst] {
+                continue;
+            }
+            in_tree[dst] = true;
+            nodes[src].adj_mut().push(index.keys[dst].clone());
+            nodes[src].set_weight(index.keys[dst].clone(), weight);
+            nodes[dst].adj_mut().push(index.keys[src].clone());
+            nodes[dst].set_weight(index.keys[src].clone(), weight);
+            heap.extend(
+                neighbours[dst]
+                    .iter()
+                    .filter(|(next, _)| !in_tree[*next])
+                    .map(|(next, weight)| MinScored(*weight, (dst, *next))),
+            );
+        }
+    }
+    index.keys.into_iter().zip(nodes).collect()
+}
+
+/// Draw a spanning tree of a Cluster uniformly at random among all its spanning trees, with Wilson's algorithm.
+/// Each node not in the tree yet starts a random walk that stops on the tree, the walk being added once its loops are erased.
+/// The Cluster is seen as a simple undirected graph, and edges toward keys not in the Cluster are skipped.
+/// When the Cluster is not connected, a tree is drawn independently for each connected component.
+/// # Parameters
+/// - cluster - The Cluster to span.
+/// - rng - The source of randomness.
+/// # Return
+/// A new Cluster holding all the keys of the Cluster and only the edges of the drawn spanning forest.
+#[cfg(feature = "rand")]
+pub fn random_spanning_tree<K, N, C, R>(
+    cluster: &C,
+    rng: &mut R,
+) -> UndirectedHashCluster<K, BasicNode<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: RandomSource + ?Sized,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let n = graph.keys.len();
+    let mut in_tree = vec![false; n];
+    let mut reached = vec![false; n];
+    for root in 0..n {
+        if reached[root] {
+            continue;
+        }
+        in_tree[root] = true;
+        reached[root] = true;
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            for w in &graph.adj[v] {
+                if !reached[*w] {
+                    reached[*w] = true;
+                    stack.push(*w);
+                }
+            }
+        }
+    }
+    let mut nodes: Vec<BasicNode<K>> = (0..n).map(|_| BasicNode::new(())).collect();
+    let mut next = vec![0; n];
+    for start in 0..n {
+        let mut v = start;
+        while !in_tree[v] {
+            let neighbours = &graph.adj[v];
+            next[v] = neighbours[rng.below(neighbours.len())];
+            v = next[v];
+        }
+        let mut v = start;
+        while !in_tree[v] {
+            in_tree[v] = true;
+            nodes[v].adj_mut().push(graph.keys[next[v]].clone());
+            nodes[next[v]].adj_mut().push(graph.keys[v].clone());
+            v = next[v];
+        }
+    }
+    graph.keys.into_iter().zip(nodes).collect()
+}
+
+/// Compute a minimum spanning arborescence of a weighted Cluster rooted at a node, with the Chu–Liu/Edmonds algorithm.
+/// Every node reachable from the root gets exactly one incoming edge, so that it is reached from the root by a single path
+/// and the total weight is minimal. The cheapest edge entering each node is chosen, then each cycle they form is contracted
+/// into a single node whose entering edges are discounted by the edge they would replace, until no cycle remains.
+/// Nodes not reachable from the root are left out, as well as self-loops and edges toward keys not in the Cluster.
+/// # Parameters
+/// - cluster - The weighted Cluster to span.
+/// - root - The key of the node every path starts from.
+/// # Return
+/// An Option containing the SpanningTree whose edges go from parent to child, None if the root is not in the Cluster.
+pub fn min_spanning_arborescence<K, N, C>(
+    cluster: &C,
+    root: &K,
+) -> Option<SpanningTree<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let index = weighted_index(cluster);
+    let root = *index.index.get(root)?;
+    let n = index.keys.len();
+    let mut reached = vec![false; n];
+    reached[root] = true;
+    let mut stack = vec![root];
+    while let Some(v) = stack.pop() {
+        for (w, _) in &index.adj[v] {
+            if !reached[*w] {
+                reached[*w] = true;
+                stack.push(*w);
+            }
+        }
+    }
+    let compact: Vec<usize> = reached
+        .iter()
+        .scan(0, |count, reached| {
+            *count += usize::from(*reached);
+            Some(*count - 1)
+        })
+        .collect();
+    let original: Vec<(usize, usize, N::Weight)> = index
+        .adj
+        .iter()
+        .enumerate()
+        .filter(|(src, _)| reached[*src])
+        .flat_map(|(src, dsts)| dsts.iter().map(move |(dst, weight)| (src, *dst, *weight)))
+        .filter(|(src, dst, _)| src != dst)
+        .collect();
+    let mut levels: Vec<Contraction<N::Weight>> = vec![Contraction {
+        edges: original
+            .iter()
+            .enumerate()
+            .map(|(i, (src, dst, weight))| (compact[*src], compact[*dst], *weight, i))
+            .collect(),
+        cheapest: Vec::new(),
+        in_cycle: Vec::new(),
+    }];
+    let mut nodes = reached.iter().filter(|reached| **reached).count();
+    let mut root = compact[root];
+    let mut selected = loop {
+        let level = levels.last_mut().unwrap();
+        let mut cheapest = vec![usize::MAX; nodes];
+        for (i, (_, dst, weight, _)) in level.edges.iter().enumerate() {
+            if *dst != root
+                && (cheapest[*dst] == usize::MAX || *weight < level.edges[cheapest[*dst]].2)
+            {
+                cheapest[*dst] = i;
+            }
+        }
+        let mut component = vec![usize::MAX; nodes];
+        let mut walk = vec![usize::MAX; nodes];
+        let mut in_cycle = vec![false; nodes];
+        let mut count = 0;
+        for start in 0..nodes {
+            let mut v = start;
+            while v != root && walk[v] == usize::MAX {
+                walk[v] = start;
+                v = level.edges[cheapest[v]].0;
+            }
+            if v != root && walk[v] == start {
+                while component[v] == usize::MAX {
+                    component[v] = count;
+                    in_cycle[v] = true;
+                    v = level.edges[cheapest[v]].0;
+                }
+                count += 1;
+            }
+        }
+        if count == 0 {
+            break (0..nodes)
+                .filter(|v| *v != root)
+                .map(|v| cheapest[v])
+                .collect::<Vec<usize>>();
+        }
+        for c in component.iter_mut().filter(|c| **c == usize::MAX) {
+            *c = count;
+            count += 1;
+        }
+        let edges = level
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, (src, dst, _, _))| component[*src] != component[*dst])
+            .map(|(i, (src, dst, weight, _))| {
+                let weight = if in_cycle[*dst] {
+                    *weight - level.edges[cheapest[*dst]].2
+                } else {
+                    *weight
+                };
+                (component[*src], component[*dst], weight, i)
+            })
+            .collect();
+        level.cheapest = cheapest;
+        level.in_cycle = in_cycle;
+        root = component[root];
+        nodes = count;
+        levels.push(Contraction {
+            edges,
+            cheapest: Vec::new(),
+            in_cycle: Vec::new(),
+        });
+    };
+    let top = levels.pop().unwrap();
+    let mut parents: Vec<usize> = selected.iter().map(|i| top.edges[*i].3).collect();
+    while let Some(level) = levels.pop() {
+        let mut entered = vec![false; level.in_cycle.len()];
+        for i in &parents {
+            entered[level.edges[*i].1] = true;
+        }
+        selected = parents;
+        selected.extend(
+            (0..level.in_cycle.len())
+                .filter(|v| level.in_cycle[*v] && !entered[*v])
+                .map(|v| level.cheapest[v]),
+        );
+        parents = selected.iter().map(|i| level.edges[*i].3).collect();
+    }
+    let mut tree = SpanningTree {
+        edges: Vec::new(),
+        total: N::Weight::zero(),
+    };
+    for i in parents {
+        let (src, dst, weight) = original[i];
+        tree.total = tree.total + weight;
+        tree.edges
+            .push((index.keys[src].clone(), index.keys[dst].clone(), weight));
+    }
+    Some(tree)
+}
+
+/// Graph of a step of the Chu–Liu/Edmonds algorithm, each (source, destination, weight, edge) quadruple
+/// referring to the edge of the previous step it stands for. The cheapest entering edges and the nodes on
+/// a cycle are filled once the step has been contracted.
+struct Contraction<W> {
+    edges: Vec<(usize, usize, W, usize)>,
+    cheapest: Vec<usize>,
+    in_cycle: Vec<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "rand")]
+    use crate::algo::components::connected_components;
+    use crate::HashCluster;
+    #[cfg(feature = "rand")]
+    use crate::SplitMix64;
+
+    fn undirected(
+        edges: &[(u32, u32, i64)],
+    ) -> UndirectedHashCluster<u32, WeightedBasicNode<u32, i64>> {
+        let mut cluster = UndirectedHashCluster::new();
+        for key in 0..7 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in edges {
+            cluster.add_weighted_edge(*src, *dst, *weight).unwrap();
+        }
+        cluster
+    }
+
+    const FOREST: [(u32, u32, i64); 6] = [
+        (0, 1, 1),
+        (1, 2, 2),
+        (0, 2, 3),
+        (2, 3, 1),
+        (1, 3, 4),
+        (5, 6, 2),
+    ];
+
+    #[test]
+    fn kruskal_spans_each_component() {
+        let cluster = undirected(&FOREST);
+        let minimum = mst_kruskal(&cluster, false);
+        assert_eq!((minimum.edges.len(), minimum.total), (4, 6));
+        let maximum = mst_kruskal(&cluster, true);
+        assert_eq!((maximum.edges.len(), maximum.total), (4, 11));
+    }
+
+    #[test]
+    fn prim_keeps_the_tree_edges_with_their_weights() {
+        let cluster = undirected(&FOREST);
+        let tree = mst_prim(&cluster);
+        let expected = undirected(&[(0, 1, 1), (1, 2, 2), (2, 3, 1), (5, 6, 2)]);
+        crate::assert_graph_eq!(tree, expected);
+        assert_eq!(tree.weight(&2, &1), Some(2));
+        let total: i64 = tree
+            .edges()
+            .map(|(src, dst)| tree.weight(src, dst).unwrap())
+            .sum();
+        assert_eq!(total, mst_kruskal(&cluster, false).total);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_spanning_trees_keep_the_components() {
+        let cluster = undirected(&FOREST);
+        let mut rng = SplitMix64::new(7);
+        for _ in 0..10 {
+            let tree = random_spanning_tree(&cluster, &mut rng);
+            assert_eq!(tree.len(), 7);
+            assert_eq!(tree.edges().count(), 4);
+            assert!(tree
+                .edges()
+                .all(|(src, dst)| cluster.weight(src, dst).is_some()));
+            assert_eq!(
+                connected_components(&tree).1,
+                connected_components(&cluster).1
+            );
+        }
+    }
+
+    #[test]
+    fn arborescences_contract_the_cycles() {
+        let mut cluster: HashCluster<u32, WeightedBasicNode<u32, i64>> = HashCluster::new();
+        for key in 0..4 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in [(0, 1, 10), (0, 2, 12), (1, 2, 1), (2, 1, 1), (3, 0, 1)] {
+            cluster.add_weighted_edge(src, dst, weight).unwrap();
+        }
+        let tree = min_spanning_arborescence(&cluster, &0).unwrap();
+        let mut edges = tree.edges.clone();
+        edges.sort();
+        assert_eq!(edges, vec![(0, 1, 10), (1, 2, 1)]);
+        assert_eq!(tree.total, 11);
+        assert!(min_spanning_arborescence(&cluster, &9).is_none());
+    }
+}
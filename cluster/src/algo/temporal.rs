@@ -0,0 +1,184 @@
+//! Journeys through Clusters whose edges can only be taken at scheduled times.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::algo::scored::MinScored;
+use crate::{Cluster, Node, ReverseAdjacency};
+
+/// Scheduled trip along an edge, such as a flight or a bus ride.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Connection<T> {
+    /// Time at which the trip leaves the source node.
+    pub departure: T,
+    /// Time at which the trip reaches the destination node.
+    pub arrival: T,
+}
+
+/// Edge taken by a journey, along with the times of the trip used.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Leg<K, T> {
+    /// The key of the node the leg leaves.
+    pub from: K,
+    /// The key of the node the leg reaches.
+    pub to: K,
+    /// Time at which the leg leaves its source node.
+    pub departure: T,
+    /// Time at which the leg reaches its destination node.
+    pub arrival: T,
+}
+
+/// Find the journey from src reaching dst as early as possible, waiting at the nodes being allowed.
+/// # Parameters
+/// - cluster - The Cluster to travel through.
+/// - src - The key of the node the journey starts from.
+/// - dst - The key of the node the journey goes to.
+/// - start - The time at which the traveller is at src.
+/// - schedule - The callback giving the connections of the edge going from its first key to its second key.
+/// # Return
+/// An Option containing the arrival time at dst and the legs of the journey, None if dst cannot be reached.
+pub fn earliest_arrival<K, N, C, T, F>(
+    cluster: &C,
+    src: &K,
+    dst: &K,
+    start: T,
+    mut schedule: F,
+) -> Option<(T, Vec<Leg<K, T>>)>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    T: Ord + Copy,
+    F: FnMut(&K, &K) -> Vec<Connection<T>>,
+{
+    if !cluster.contains_key(src) || !cluster.contains_key(dst) {
+        return None;
+    }
+    let mut arrivals = HashMap::from([(src.clone(), start)]);
+    let mut legs: HashMap<K, Leg<K, T>> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut frontier = BinaryHeap::from([MinScored(start, src.clone())]);
+    while let Some(MinScored(time, key)) = frontier.pop() {
+        if !settled.insert(key.clone()) {
+            continue;
+        }
+        if key == *dst {
+            let mut journey = collect_legs(&legs, dst, |leg| &leg.from);
+            journey.reverse();
+            return Some((time, journey));
+        }
+        for next in cluster.get_adj(&key).into_iter().flatten() {
+            if settled.contains(next) || !cluster.contains_key(next) {
+                continue;
+            }
+            let best = schedule(&key, next)
+                .into_iter()
+                .filter(|c| c.departure >= time)
+                .min_by_key(|c| c.arrival);
+            let Some(connection) = best else {
+                continue;
+            };
+            if arrivals
+                .get(next)
+                .is_none_or(|current| connection.arrival < *current)
+            {
+                arrivals.insert(next.clone(), connection.arrival);
+                legs.insert(next.clone(), leg(&key, next, connection));
+                frontier.push(MinScored(connection.arrival, next.clone()));
+            }
+        }
+    }
+    None
+}
+
+/// Find the journey leaving src as late as possible while still reaching dst before a deadline.
+/// # Parameters
+/// - cluster - The Cluster to travel through.
+/// - reverse - The reverse adjacency of the Cluster.
+/// - src - The key of the node the journey starts from.
+/// - dst - The key of the node the journey goes to.
+/// - deadline - The time before which the traveller must be at dst.
+/// - schedule - The callback giving the connections of the edge going from its first key to its second key.
+/// # Return
+/// An Option containing the departure time from src and the legs of the journey, None if dst cannot be reached in time.
+pub fn latest_departure<K, N, C, R, T, F>(
+    cluster: &C,
+    reverse: &R,
+    src: &K,
+    dst: &K,
+    deadline: T,
+    mut schedule: F,
+) -> Option<(T, Vec<Leg<K, T>>)>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: ReverseAdjacency<K>,
+    T: Ord + Copy,
+    F: FnMut(&K, &K) -> Vec<Connection<T>>,
+{
+    if !cluster.contains_key(src) || !cluster.contains_key(dst) {
+        return None;
+    }
+    let mut departures = HashMap::from([(dst.clone(), deadline)]);
+    let mut legs: HashMap<K, Leg<K, T>> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut frontier = BinaryHeap::from([MinScored(Reverse(deadline), dst.clone())]);
+    while let Some(MinScored(Reverse(time), key)) = frontier.pop() {
+        if !settled.insert(key.clone()) {
+            continue;
+        }
+        if key == *src {
+            let departure = legs.get(src).map_or(time, |leg| leg.departure);
+            return Some((departure, collect_legs(&legs, src, |leg| &leg.to)));
+        }
+        for previous in reverse.reverse_adj(&key).into_iter().flatten() {
+            if settled.contains(previous) || !cluster.contains_key(previous) {
+                continue;
+            }
+            let best = schedule(previous, &key)
+                .into_iter()
+                .filter(|c| c.arrival <= time)
+                .max_by_key(|c| c.departure);
+            let Some(connection) = best else {
+                continue;
+            };
+            if departures
+                .get(previous)
+                .is_none_or(|current| connection.departure > *current)
+            {
+                departures.insert(previous.clone(), connection.departure);
+                legs.insert(previous.clone(), leg(previous, &key, connection));
+                frontier.push(MinScored(Reverse(connection.departure), previous.clone()));
+            }
+        }
+    }
+    None
+}
+
+/// Build the leg taking the given connection from one node to another.
+fn leg<K: Clone, T>(from: &K, to: &K, connection: Connection<T>) -> Leg<K, T> {
+    Leg {
+        from: from.clone(),
+        to: to.clone(),
+        departure: connection.departure,
+        arrival: connection.arrival,
+    }
+}
+
+/// Chain the legs recorded per node, starting at the leg of end and moving to the node given by step.
+fn collect_legs<K, T, F>(legs: &HashMap<K, Leg<K, T>>, end: &K, step: F) -> Vec<Leg<K, T>>
+where
+    K: Hash + Eq + Clone,
+    T: Copy,
+    F: Fn(&Leg<K, T>) -> &K,
+{
+    let mut chain = Vec::new();
+    let mut key = end;
+    while let Some(leg) = legs.get(key) {
+        chain.push(leg.clone());
+        key = step(leg);
+    }
+    chain
+}
@@ -0,0 +1,516 @@
+//! Topological orderings of a directed Cluster.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::traversal::{DfsEvent, DfsEvents};
+use crate::{Cluster, ClusterError, Node};
+
+/// Error raised when a Cluster has no topological order because it contains a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<K> {
+    /// The key of a node on a cycle.
+    pub key: K,
+}
+
+impl<K: Debug> Display for CycleError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "The node {:?} is on a cycle.", self.key)
+    }
+}
+
+impl<K: Debug> Error for CycleError<K> {}
+
+/// Order the nodes of a Cluster so that every edge goes from a node to a later one, with Kahn's algorithm.
+/// The nodes without incoming edges are repeatedly taken out of the Cluster, edges toward keys not in the Cluster being ignored.
+/// # Parameter
+/// - cluster - The Cluster to order.
+/// # Return
+/// The keys in topological order, or a CycleError designing a node on a cycle.
+pub fn toposort<K, N, C>(cluster: &C) -> Result<Vec<K>, CycleError<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    let n = graph.keys.len();
+    let mut in_degrees = vec![0; n];
+    for dst in graph.adj.iter().flatten() {
+        in_degrees[*dst] += 1;
+    }
+    let mut ready: Vec<usize> = (0..n).filter(|v| in_degrees[*v] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(v) = ready.pop() {
+        order.push(v);
+        for w in &graph.adj[v] {
+            in_degrees[*w] -= 1;
+            if in_degrees[*w] == 0 {
+                ready.push(*w);
+            }
+        }
+    }
+    if order.len() < n {
+        return Err(CycleError {
+            key: graph.keys[node_on_cycle(&graph.adj, &in_degrees)].clone(),
+        });
+    }
+    Ok(order.into_iter().map(|v| graph.keys[v].clone()).collect())
+}
+
+/// Order the nodes of a Cluster so that every edge goes from a node to a later one, with the smallest keys first.
+/// Kahn's algorithm always takes out the smallest available key, giving the unique lexicographically smallest order.
+/// # Parameter
+/// - cluster - The Cluster to order.
+/// # Return
+/// The keys in topological order, or a CycleError designing a node on a cycle.
+pub fn toposort_lexicographic<K, N, C>(cluster: &C) -> Result<Vec<K>, CycleError<K>>
+where
+    K: Hash + Eq + Ord + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    let n = graph.keys.len();
+    let mut in_degrees = vec![0; n];
+    for dst in graph.adj.iter().flatten() {
+        in_degrees[*dst] += 1;
+    }
+    let mut ready: BinaryHeap<Reverse<(&K, usize)>> = (0..n)
+        .filter(|v| in_degrees[*v] == 0)
+        .map(|v| Reverse((&graph.keys[v], v)))
+        .collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse((key, v))) = ready.pop() {
+        order.push(key.clone());
+        for w in &graph.adj[v] {
+            in_degrees[*w] -= 1;
+            if in_degrees[*w] == 0 {
+                ready.push(Reverse((&graph.keys[*w], *w)));
+            }
+        }
+    }
+    if order.len() < n {
+        return Err(CycleError {
+            key: graph.keys[node_on_cycle(&graph.adj, &in_degrees)].clone(),
+        });
+    }
+    Ok(order)
+}
+
+/// Order the nodes of a Cluster so that every edge goes from a node to a later one, with a depth-first search.
+/// The nodes are listed by decreasing finishing time, a back edge revealing a cycle.
+/// # Parameter
+/// - cluster - The Cluster to order.
+/// # Return
+/// The keys in topological order, or a CycleError designing a node on a cycle.
+pub fn toposort_dfs<K, N, C>(cluster: &C) -> Result<Vec<K>, CycleError<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let mut keys = cluster.keys();
+    let Some(first) = keys.next() else {
+        return Ok(Vec::new());
+    };
+    let mut events = DfsEvents::new(cluster, first.clone());
+    let mut order = Vec::with_capacity(cluster.len());
+    loop {
+        for event in events.by_ref() {
+            match event {
+                DfsEvent::BackEdge(_, dst) => return Err(CycleError { key: dst }),
+                DfsEvent::Finish(key) => order.push(key),
+                _ => {}
+            }
+        }
+        let Some(next) = keys.find(|k| !events.visited().contains_key(*k)) else {
+            break;
+        };
+        events.move_to(next.clone());
+    }
+    order.reverse();
+    Ok(order)
+}
+
+/// Split the nodes of a Cluster into layers so that every edge goes from a layer to a later one, with the longest-path layering.
+/// The first layer holds the nodes without incoming edges, and each node lies one layer after the farthest of its predecessors,
+/// so that the nodes of a layer only depend on earlier layers and can be processed in parallel.
+/// Edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The Cluster to layer.
+/// # Return
+/// The keys of each layer in order, or a CycleError designing a node on a cycle.
+pub fn layers<K, N, C>(cluster: &C) -> Result<Vec<Vec<K>>, CycleError<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::directed(cluster);
+    let n = graph.keys.len();
+    let mut in_degrees = vec![0; n];
+    for dst in graph.adj.iter().flatten() {
+        in_degrees[*dst] += 1;
+    }
+    let mut layer: Vec<usize> = (0..n).filter(|v| in_degrees[*v] == 0).collect();
+    let mut layers = Vec::new();
+    let mut placed = 0;
+    while !layer.is_empty() {
+        let mut next = Vec::new();
+        for v in &layer {
+            for w in &graph.adj[*v] {
+                in_degrees[*w] -= 1;
+                if in_degrees[*w] == 0 {
+                    next.push(*w);
+                }
+            }
+        }
+        placed += layer.len();
+        layers.push(layer.into_iter().map(|v| graph.keys[v].clone()).collect());
+        layer = next;
+    }
+    if placed < n {
+        return Err(CycleError {
+            key: graph.keys[node_on_cycle(&graph.adj, &in_degrees)].clone(),
+        });
+    }
+    Ok(layers)
+}
+
+/// Directed acyclic Cluster keeping a topological order of its nodes up to date as edges are added, with the Pearce–Kelly algorithm.
+/// Adding an edge only reorders the nodes lying between its ends in the current order, an edge that would close a cycle being refused.
+#[derive(Debug, Clone)]
+pub struct IncrementalTopo<K: Hash + Eq, C> {
+    cluster: C,
+    positions: HashMap<K, usize>,
+    predecessors: HashMap<K, Vec<K>>,
+    next_position: usize,
+}
+
+impl<K: Hash + Eq + Clone, C> IncrementalTopo<K, C> {
+    /// Wrap an acyclic Cluster to maintain its topological order.
+    /// # Parameter
+    /// - cluster - The Cluster to wrap.
+    /// # Return
+    /// The newly created IncrementalTopo, or a CycleError designing a node on a cycle of the Cluster.
+    pub fn new<N: Node<K>>(cluster: C) -> Result<IncrementalTopo<K, C>, CycleError<K>>
+    where
+        C: Cluster<K, N>,
+    {
+        let order = toposort(&cluster)?;
+        let mut predecessors: HashMap<K, Vec<K>> =
+            order.iter().map(|k| (k.clone(), Vec::new())).collect();
+        for src in &order {
+            for dst in cluster.get_adj(src).into_iter().flatten() {
+                if let Some(preds) = predecessors.get_mut(dst) {
+                    preds.push(src.clone());
+                }
+            }
+        }
+        Ok(IncrementalTopo {
+            next_position: order.len(),
+            positions: order.into_iter().enumerate().map(|(i, k)| (k, i)).collect(),
+            predecessors,
+            cluster,
+        })
+    }
+
+    /// Get the wrapped Cluster.
+    /// # Return
+    /// A reference to the Cluster.
+    pub fn cluster(&self) -> &C {
+        &self.cluster
+    }
+
+    /// Unwrap the Cluster.
+    /// # Return
+    /// The wrapped Cluster.
+    pub fn into_inner(self) -> C {
+        self.cluster
+    }
+
+    /// Get the current topological order of the nodes.
+    /// # Return
+    /// The keys of the Cluster, every edge going from a node to a later one.
+    pub fn order(&self) -> Vec<K> {
+        let mut order: Vec<(&K, usize)> = self.positions.iter().map(|(k, p)| (k, *p)).collect();
+        order.sort_unstable_by_key(|(_, p)| *p);
+        order.into_iter().map(|(k, _)| k.clone()).collect()
+    }
+
+    /// Check if a node comes before another one in the current order.
+    /// # Parameters
+    /// - a - The key of the first node.
+    /// - b - The key of the second node.
+    /// # Return
+    /// An Option containing true if a is ordered before b, None if a key is not in the Cluster.
+    pub fn precedes(&self, a: &K, b: &K) -> Option<bool> {
+        Some(self.positions.get(a)? < self.positions.get(b)?)
+    }
+
+    /// Add a node to the Cluster, its edges being added one by one afterward.
+    /// An edge the Cluster refuses is left out.
+    /// # Parameter
+    /// - node - The node to add.
+    /// # Return
+    /// The key of the new node, or a CycleError if the node has an edge toward itself, which is then left out.
+    pub fn add<N: Node<K>>(&mut self, mut node: N) -> Result<K, CycleError<K>>
+    where
+        C: Cluster<K, N>,
+    {
+        let adj = std::mem::take(node.adj_mut());
+        let key = self.cluster.add(node);
+        self.positions.insert(key.clone(), self.next_position);
+        self.predecessors.insert(key.clone(), Vec::new());
+        self.next_position += 1;
+        let mut result = Ok(key.clone());
+        for dst in adj {
+            if dst == key {
+                result = Err(CycleError { key: key.clone() });
+            } else if self.positions.contains_key(&dst) {
+                let _ = self.link(key.clone(), dst);
+            } else {
+                let _ = self.cluster.add_edge(key.clone(), dst);
+            }
+        }
+        result
+    }
+
+    /// Remove a node and its edges from the Cluster, the order of the other nodes staying valid.
+    /// # Parameter
+    /// - key - The key of the node to remove.
+    /// # Return
+    /// An Option containing the removed node, None if there is no such node.
+    pub fn remove<N: Node<K>>(&mut self, key: &K) -> Option<N>
+    where
+        C: Cluster<K, N>,
+    {
+        let node = self.cluster.remove(key)?;
+        self.positions.remove(key);
+        for preds in self.predecessors.values_mut() {
+            preds.retain(|k| k != key);
+        }
+        self.predecessors.remove(key);
+        Some(node)
+    }
+
+    /// Add an edge between src and dst, reordering the nodes if dst currently comes before src.
+    /// # Parameters
+    /// - src - The key of the source node.
+    /// - dst - The key of the destination node.
+    /// # Return
+    /// Nothing if everithing gone well, a CycleError if the edge would close a cycle, which is then not added, another error otherwise.
+    pub fn add_edge<N: Node<K>>(&mut self, src: K, dst: K) -> crate::Result<()>
+    where
+        C: Cluster<K, N>,
+        K: Debug + 'static,
+    {
+        if !self.positions.contains_key(&src) || !self.positions.contains_key(&dst) {
+            return Err(ClusterError::detailled(
+                "To add edge, both node must exists in the Cluster.",
+            )
+            .into());
+        }
+        if !self.link(src.clone(), dst)? {
+            return Err(CycleError { key: src }.into());
+        }
+        Ok(())
+    }
+
+    /// Remove the edge between src and dst, the order staying valid.
+    /// # Parameters
+    /// - src - The key of the source node.
+    /// - dst - The key of the destination node.
+    /// # Return
+    /// Nothing if everithing gone well, an error otherwise.
+    pub fn remove_edge<N: Node<K>>(&mut self, src: &K, dst: &K) -> crate::Result<()>
+    where
+        C: Cluster<K, N>,
+    {
+        self.cluster.remove_edge(src, dst)?;
+        if let Some(preds) = self.predecessors.get_mut(dst) {
+            preds.retain(|k| k != src);
+        }
+        Ok(())
+    }
+
+    /// Add the edge between two nodes of the Cluster, returning false without adding it if it would close a cycle.
+    /// The positions may have been reordered when the Cluster refuses the edge, the order staying valid.
+    fn link<N: Node<K>>(&mut self, src: K, dst: K) -> crate::Result<bool>
+    where
+        C: Cluster<K, N>,
+    {
+        if src == dst {
+            return Ok(false);
+        }
+        if self
+            .cluster
+            .get_adj(&src)
+            .is_some_and(|adj| adj.contains(&dst))
+        {
+            return Ok(true);
+        }
+        let (upper, lower) = (self.positions[&src], self.positions[&dst]);
+        if lower <= upper {
+            let Some(forward) = self.forward(&dst, &src, upper) else {
+                return Ok(false);
+            };
+            let backward = self.backward(&src, lower);
+            self.reorder(backward, forward);
+        }
+        self.cluster.add_edge(src.clone(), dst.clone())?;
+        self.predecessors.entry(dst).or_default().push(src);
+        Ok(true)
+    }
+
+    /// Collect the nodes reachable from start without going past upper in the order, None if target is met.
+    fn forward<N: Node<K>>(&self, start: &K, target: &K, upper: usize) -> Option<Vec<K>>
+    where
+        C: Cluster<K, N>,
+    {
+        let mut seen = HashSet::from([start.clone()]);
+        let mut stack = vec![start.clone()];
+        while let Some(key) = stack.pop() {
+            for next in self.cluster.get_adj(&key).into_iter().flatten() {
+                if next == target {
+                    return None;
+                }
+                if self.positions.get(next).is_some_and(|p| *p < upper) && seen.insert(next.clone())
+                {
+                    stack.push(next.clone());
+                }
+            }
+        }
+        Some(seen.into_iter().collect())
+    }
+
+    /// Collect the nodes reaching start without going before lower in the order.
+    fn backward(&self, start: &K, lower: usize) -> Vec<K> {
+        let mut seen = HashSet::from([start.clone()]);
+        let mut stack = vec![start.clone()];
+        while let Some(key) = stack.pop() {
+            for previous in self.predecessors.get(&key).into_iter().flatten() {
+                if self.positions.get(previous).is_some_and(|p| *p > lower)
+                    && seen.insert(previous.clone())
+                {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        seen.into_iter().collect()
+    }
+
+    /// Give the positions held by both sets to the backward nodes first, then to the forward ones, each set keeping its relative order.
+    fn reorder(&mut self, backward: Vec<K>, forward: Vec<K>) {
+        let sorted = |mut keys: Vec<K>| {
+            keys.sort_unstable_by_key(|k| self.positions[k]);
+            keys
+        };
+        let moved: Vec<K> = sorted(backward)
+            .into_iter()
+            .chain(sorted(forward))
+            .collect();
+        let mut positions: Vec<usize> = moved.iter().map(|k| self.positions[k]).collect();
+        positions.sort_unstable();
+        for (key, position) in moved.into_iter().zip(positions) {
+            self.positions.insert(key, position);
+        }
+    }
+}
+
+/// Find a node on a cycle among the nodes Kahn's algorithm could not take out, by walking backward along their remaining edges.
+fn node_on_cycle(adj: &[Vec<usize>], in_degrees: &[usize]) -> usize {
+    let mut predecessor = vec![None; adj.len()];
+    for (v, dsts) in adj.iter().enumerate() {
+        if in_degrees[v] == 0 {
+            continue;
+        }
+        for w in dsts {
+            predecessor[*w] = Some(v);
+        }
+    }
+    let mut seen = vec![false; adj.len()];
+    let mut v = (0..adj.len()).find(|v| in_degrees[*v] > 0).unwrap();
+    while !seen[v] {
+        seen[v] = true;
+        v = predecessor[v].unwrap();
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster};
+
+    fn dag() -> HashCluster<u32, BasicNode<u32>> {
+        [(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]
+            .into_iter()
+            .collect()
+    }
+
+    fn respects_the_edges(cluster: &HashCluster<u32, BasicNode<u32>>, order: &[u32]) -> bool {
+        let position: HashMap<u32, usize> =
+            order.iter().enumerate().map(|(i, k)| (*k, i)).collect();
+        order.len() == cluster.len()
+            && cluster
+                .edges()
+                .all(|(src, dst)| position[src] < position[dst])
+    }
+
+    #[test]
+    fn every_order_respects_the_edges() {
+        let cluster = dag();
+        assert!(respects_the_edges(&cluster, &toposort(&cluster).unwrap()));
+        assert!(respects_the_edges(
+            &cluster,
+            &toposort_dfs(&cluster).unwrap()
+        ));
+        assert_eq!(
+            toposort_lexicographic(&cluster).unwrap(),
+            vec![4, 5, 0, 2, 3, 1]
+        );
+        assert_eq!(
+            layers(&cluster)
+                .unwrap()
+                .into_iter()
+                .map(|mut layer| {
+                    layer.sort();
+                    layer
+                })
+                .collect::<Vec<_>>(),
+            vec![vec![4, 5], vec![0, 2], vec![3], vec![1]]
+        );
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        let mut cluster = dag();
+        cluster.add_edge(1, 2).unwrap();
+        for result in [
+            toposort(&cluster),
+            toposort_dfs(&cluster),
+            toposort_lexicographic(&cluster),
+        ] {
+            assert!([1, 2, 3].contains(&result.unwrap_err().key));
+        }
+        assert!(layers(&cluster).is_err());
+    }
+
+    #[test]
+    fn the_incremental_order_refuses_cycles() {
+        let mut topo = IncrementalTopo::new(dag()).unwrap();
+        topo.add_edge::<BasicNode<u32>>(1, 0).unwrap();
+        assert_eq!(topo.precedes(&1, &0), Some(true));
+        assert!(respects_the_edges(topo.cluster(), &topo.order()));
+        assert!(topo.add_edge::<BasicNode<u32>>(0, 5).is_err());
+        let mut expected = dag();
+        expected.add_edge(1, 0).unwrap();
+        crate::assert_graph_eq!(*topo.cluster(), expected);
+    }
+}
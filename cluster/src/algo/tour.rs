@@ -0,0 +1,186 @@
+//! Tours visiting every node of a Cluster : travelling salesman approximation and Hamiltonian paths.
+
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::algo::shortest_path::weighted_index;
+use crate::{Cluster, ClusterError, Node, Result, Weight, WeightedCluster, WeightedNode};
+
+/// Closed tour through every node of a Cluster, returning to its first node after the last one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tour<K, W> {
+    /// Keys of the nodes in the order they are visited, each exactly once.
+    pub nodes: Vec<K>,
+    /// Total weight of the edges of the tour, including the one from the last node back to the first.
+    pub weight: W,
+}
+
+/// Approximate the shortest tour through every node of a complete weighted Cluster, the travelling salesman problem.
+/// A tour is first built by going to the nearest unvisited node, then improved by 2-opt moves, reversing a part of the tour
+/// as long as it makes the tour shorter. Directed Clusters may have different weights in each direction.
+/// Self-loops are ignored, and the lightest of parallel edges is used.
+/// # Parameter
+/// - cluster - The weighted Cluster to go through, with an edge from every node to every other node.
+/// # Return
+/// An Option containing a short Tour of the Cluster, None if it is empty or an edge between two nodes is missing.
+pub fn tsp_tour<K, N, C>(cluster: &C) -> Option<Tour<K, N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Into<f64>,
+    C: WeightedCluster<K, N>,
+{
+    let index = weighted_index(cluster);
+    let n = index.keys.len();
+    let mut weights: Vec<Vec<Option<N::Weight>>> = vec![vec![None; n]; n];
+    for (src, dsts) in index.adj.iter().enumerate() {
+        for (dst, weight) in dsts.iter().filter(|(dst, _)| *dst != src) {
+            let current = &mut weights[src][*dst];
+            if current.is_none_or(|current| *weight < current) {
+                *current = Some(*weight);
+            }
+        }
+    }
+    let weights: Vec<Vec<N::Weight>> = weights
+        .into_iter()
+        .enumerate()
+        .map(|(src, row)| {
+            row.into_iter()
+                .enumerate()
+                .map(|(dst, weight)| {
+                    if src == dst {
+                        Some(N::Weight::zero())
+                    } else {
+                        weight
+                    }
+                })
+                .collect::<Option<_>>()
+        })
+        .collect::<Option<_>>()?;
+    let costs: Vec<Vec<f64>> = weights
+        .iter()
+        .map(|row| row.iter().map(|w| (*w).into()).collect())
+        .collect();
+    let mut order = nearest_neighbour(&costs)?;
+    two_opt(&costs, &mut order);
+    let weight = (0..n).fold(N::Weight::zero(), |total, i| {
+        total + weights[order[i]][order[(i + 1) % n]]
+    });
+    Some(Tour {
+        nodes: order.into_iter().map(|v| index.keys[v].clone()).collect(),
+        weight,
+    })
+}
+
+/// Build a tour from the first node, going each time to the nearest node not visited yet.
+fn nearest_neighbour(costs: &[Vec<f64>]) -> Option<Vec<usize>> {
+    let n = costs.len();
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut current = (n > 0).then_some(0)?;
+    visited[current] = true;
+    order.push(current);
+    while order.len() < n {
+        current = (0..n)
+            .filter(|v| !visited[*v])
+            .min_by(|a, b| costs[current][*a].total_cmp(&costs[current][*b]))?;
+        visited[current] = true;
+        order.push(current);
+    }
+    Some(order)
+}
+
+/// Improve a tour by 2-opt moves until none of them shortens it, each move reversing the part of the tour between two edges.
+/// The cost of the reversed part is accounted for, so that asymmetric costs are supported.
+fn two_opt(costs: &[Vec<f64>], order: &mut [usize]) {
+    let n = order.len();
+    if n < 4 {
+        return;
+    }
+    let mut improved = true;
+    while improved {
+        improved = false;
+        let mut forward = vec![0.0; n];
+        let mut backward = vec![0.0; n];
+        for i in 1..n {
+            forward[i] = forward[i - 1] + costs[order[i - 1]][order[i]];
+            backward[i] = backward[i - 1] + costs[order[i]][order[i - 1]];
+        }
+        'search: for i in 0..n - 2 {
+            for j in i + 2..n {
+                let (a, b, c, d) = (order[i], order[i + 1], order[j], order[(j + 1) % n]);
+                if a == d {
+                    continue;
+                }
+                let before = costs[a][b] + costs[c][d] + forward[j] - forward[i + 1];
+                let after = costs[a][c] + costs[b][d] + backward[j] - backward[i + 1];
+                if after < before - 1e-9 * before.abs().max(1.0) {
+                    order[i + 1..=j].reverse();
+                    improved = true;
+                    break 'search;
+                }
+            }
+        }
+    }
+}
+
+/// Find a Hamiltonian path of a Cluster, visiting every node exactly once, by backtracking.
+/// The search tries the unvisited neighbours having the fewest unvisited neighbours first, but still takes exponential time at worst,
+/// so it is refused above a number of nodes. Edges toward keys not in the Cluster are ignored.
+/// # Parameters
+/// - cluster - The Cluster to go through.
+/// - max_nodes - The largest number of nodes the search is run on.
+/// # Return
+/// An Option containing the keys of the path in order, each having an edge toward the next one, None if there is no such path.
+/// An error if the Cluster has more than max_nodes nodes.
+pub fn hamiltonian_path<K, N, C>(cluster: &C, max_nodes: usize) -> Result<Option<Vec<K>>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let IndexedGraph { keys, adj } = IndexedGraph::directed(cluster);
+    let n = keys.len();
+    if n > max_nodes {
+        return Err(ClusterError::detailled(&format!(
+            "The Cluster has {n} nodes, more than the {max_nodes} a Hamiltonian path is searched on."
+        ))
+        .into());
+    }
+    if n == 0 {
+        return Ok(None);
+    }
+    let mut visited = vec![false; n];
+    for start in 0..n {
+        let mut path = vec![start];
+        visited[start] = true;
+        if extend(&adj, &mut visited, &mut path) {
+            return Ok(Some(path.into_iter().map(|v| keys[v].clone()).collect()));
+        }
+        visited[start] = false;
+    }
+    Ok(None)
+}
+
+/// Extend a path until it visits every node, backtracking on dead ends.
+fn extend(adj: &[Vec<usize>], visited: &mut [bool], path: &mut Vec<usize>) -> bool {
+    if path.len() == adj.len() {
+        return true;
+    }
+    let last = path[path.len() - 1];
+    let free = |v: usize, visited: &[bool]| adj[v].iter().filter(|w| !visited[**w]).count();
+    let mut candidates: Vec<usize> = adj[last].iter().copied().filter(|w| !visited[*w]).collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates.sort_by_key(|w| free(*w, visited));
+    for next in candidates {
+        visited[next] = true;
+        path.push(next);
+        if extend(adj, visited, path) {
+            return true;
+        }
+        path.pop();
+        visited[next] = false;
+    }
+    false
+}
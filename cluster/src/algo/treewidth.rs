@@ -0,0 +1,149 @@
+//! Tree decompositions of a Cluster, the direction of the edges being ignored.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Rule choosing the next node to eliminate when building a tree decomposition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EliminationHeuristic {
+    /// Eliminate the node with the fewest remaining neighbours.
+    MinDegree,
+    /// Eliminate the node whose elimination adds the fewest edges between its neighbours.
+    MinFill,
+}
+
+/// Tree decomposition of a Cluster.
+/// The bags are sorted so that each bag comes before its parent : walking them in order is a post-order
+/// of the tree, as dynamic programming over the decomposition needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeDecomposition<K> {
+    /// Keys of the nodes contained in each bag.
+    pub bags: Vec<Vec<K>>,
+    /// Index of the parent of each bag, None for the root.
+    pub parents: Vec<Option<usize>>,
+    /// Size of the largest bag minus one.
+    pub width: usize,
+}
+
+impl<K> TreeDecomposition<K> {
+    /// Get the index of the root bag.
+    /// # Return
+    /// An Option containing the index of the root, None if the decomposition has no bag.
+    pub fn root(&self) -> Option<usize> {
+        self.bags.len().checked_sub(1)
+    }
+
+    /// Get the bags whose parent is the given bag.
+    /// # Parameter
+    /// - bag - The index of the parent bag.
+    /// # Return
+    /// The indexes of the children of the bag.
+    pub fn children(&self, bag: usize) -> Vec<usize> {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(|(_, parent)| **parent == Some(bag))
+            .map(|(child, _)| child)
+            .collect()
+    }
+
+    /// Get the edges of the decomposition tree.
+    /// # Return
+    /// The (child, parent) pairs of bag indexes.
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter_map(|(child, parent)| parent.map(|parent| (child, parent)))
+            .collect()
+    }
+}
+
+/// Build a tree decomposition of a Cluster by eliminating its nodes one after another.
+/// Eliminating a node creates a bag made of it and its remaining neighbours, which are then linked together.
+/// The width is an upper bound of the treewidth of the Cluster.
+/// # Parameters
+/// - cluster - The Cluster to decompose.
+/// - heuristic - The rule choosing the next node to eliminate.
+/// # Return
+/// The TreeDecomposition of the Cluster.
+pub fn tree_decomposition<K, N, C>(
+    cluster: &C,
+    heuristic: EliminationHeuristic,
+) -> TreeDecomposition<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let n = graph.keys.len();
+    let mut adj: Vec<HashSet<usize>> = graph
+        .adj
+        .iter()
+        .map(|neighbours| neighbours.iter().copied().collect())
+        .collect();
+    let mut eliminated_at = vec![usize::MAX; n];
+    let mut order = Vec::with_capacity(n);
+    let mut bags = Vec::with_capacity(n);
+    for step in 0..n {
+        let v = (0..n)
+            .filter(|v| eliminated_at[*v] == usize::MAX)
+            .min_by_key(|v| match heuristic {
+                EliminationHeuristic::MinDegree => adj[*v].len(),
+                EliminationHeuristic::MinFill => fill_in(&adj, *v),
+            })
+            .unwrap();
+        eliminated_at[v] = step;
+        order.push(v);
+        let neighbours: Vec<usize> = adj[v].iter().copied().collect();
+        for (i, a) in neighbours.iter().enumerate() {
+            adj[*a].remove(&v);
+            for b in &neighbours[i + 1..] {
+                adj[*a].insert(*b);
+                adj[*b].insert(*a);
+            }
+        }
+        let mut bag = vec![v];
+        bag.extend(neighbours);
+        bags.push(bag);
+    }
+    let parents: Vec<Option<usize>> = bags
+        .iter()
+        .enumerate()
+        .map(|(step, bag)| {
+            bag[1..]
+                .iter()
+                .map(|u| eliminated_at[*u])
+                .min()
+                .or((step + 1 < n).then_some(step + 1))
+        })
+        .collect();
+    let width = bags.iter().map(|bag| bag.len()).max().unwrap_or(1) - 1;
+    TreeDecomposition {
+        bags: bags
+            .into_iter()
+            .map(|bag| bag.into_iter().map(|v| graph.keys[v].clone()).collect())
+            .collect(),
+        parents,
+        width,
+    }
+}
+
+/// Count the edges missing between the neighbours of v.
+fn fill_in(adj: &[HashSet<usize>], v: usize) -> usize {
+    let neighbours: Vec<usize> = adj[v].iter().copied().collect();
+    neighbours
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            neighbours[i + 1..]
+                .iter()
+                .filter(|b| !adj[*a].contains(b))
+                .count()
+        })
+        .sum()
+}
@@ -0,0 +1,190 @@
+//! 2-SAT solver working on the implication Cluster of the clauses.
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::algo::scc::tarjan;
+use crate::{BasicNode, Cluster, ClusterError, HashCluster};
+
+/// Variable of a 2-SAT problem, or its negation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Literal {
+    /// Index of the variable.
+    pub variable: usize,
+    /// False if the literal is the negation of the variable.
+    pub positive: bool,
+}
+
+impl Literal {
+    /// Create the literal true when the variable is true.
+    /// # Parameter
+    /// - variable - The index of the variable.
+    /// # Return
+    /// The newly created Literal.
+    pub fn pos(variable: usize) -> Literal {
+        Literal {
+            variable,
+            positive: true,
+        }
+    }
+
+    /// Create the literal true when the variable is false.
+    /// # Parameter
+    /// - variable - The index of the variable.
+    /// # Return
+    /// The newly created Literal.
+    pub fn neg(variable: usize) -> Literal {
+        Literal {
+            variable,
+            positive: false,
+        }
+    }
+
+    /// Get the negation of the literal.
+    /// # Return
+    /// The literal true when this one is false.
+    pub fn negate(self) -> Literal {
+        Literal {
+            variable: self.variable,
+            positive: !self.positive,
+        }
+    }
+
+    /// Get the key of the literal in the implication Cluster.
+    fn key(self) -> usize {
+        2 * self.variable + usize::from(!self.positive)
+    }
+
+    /// Get the literal designed by a key of the implication Cluster.
+    fn from_key(key: usize) -> Literal {
+        Literal {
+            variable: key / 2,
+            positive: key.is_multiple_of(2),
+        }
+    }
+}
+
+/// Proof that a 2-SAT problem has no solution : a variable implies its negation and the other way around.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsatisfiable {
+    /// Index of the variable that can be neither true nor false.
+    pub variable: usize,
+    /// Chain of implications going from the variable to its negation.
+    pub to_negation: Vec<Literal>,
+    /// Chain of implications going from the negation of the variable back to it.
+    pub from_negation: Vec<Literal>,
+}
+
+impl Display for Unsatisfiable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Unsatisfiable : variable {} implies its negation and is implied by it.",
+            self.variable
+        )
+    }
+}
+
+impl Error for Unsatisfiable {}
+
+/// 2-SAT problem : a conjunction of clauses made of two literals each.
+#[derive(Debug, Clone)]
+pub struct TwoSat {
+    variables: usize,
+    implications: HashCluster<usize, BasicNode<usize>>,
+}
+
+impl TwoSat {
+    /// Create a 2-SAT problem without any clause.
+    /// # Parameter
+    /// - variables - The number of variables of the problem.
+    /// # Return
+    /// The newly created TwoSat.
+    pub fn new(variables: usize) -> TwoSat {
+        let mut implications = HashCluster::new();
+        for key in 0..2 * variables {
+            implications.insert(key, BasicNode::default());
+        }
+        TwoSat {
+            variables,
+            implications,
+        }
+    }
+
+    /// Add the clause (a OR b) to the problem.
+    /// # Parameters
+    /// - a - The first literal of the clause.
+    /// - b - The second literal of the clause.
+    /// # Return
+    /// Nothing if everithing gone well, an error if a literal refers to an unknown variable.
+    pub fn add_clause(&mut self, a: Literal, b: Literal) -> crate::Result<()> {
+        if a.variable >= self.variables || b.variable >= self.variables {
+            return Err(
+                ClusterError::detailled("The clause refers to an unknown variable.").into(),
+            );
+        }
+        self.implications.add_edge(a.negate().key(), b.key())?;
+        self.implications.add_edge(b.negate().key(), a.key())
+    }
+
+    /// Get the implication Cluster of the problem.
+    /// The literal of variable i is stored at key 2i, its negation at key 2i + 1.
+    /// # Return
+    /// An immutable reference to the implication Cluster.
+    pub fn implication_cluster(&self) -> &HashCluster<usize, BasicNode<usize>> {
+        &self.implications
+    }
+
+    /// Solve the problem.
+    /// # Return
+    /// The value of each variable in a satisfying assignment, or the proof that there is none.
+    pub fn solve(&self) -> std::result::Result<Vec<bool>, Unsatisfiable> {
+        let graph = IndexedGraph::directed(&self.implications);
+        let mut component = vec![0; graph.keys.len()];
+        for (i, members) in tarjan(&graph.adj).iter().enumerate() {
+            for v in members {
+                component[graph.keys[*v]] = i;
+            }
+        }
+        let mut assignment = Vec::with_capacity(self.variables);
+        for variable in 0..self.variables {
+            let (pos, neg) = (Literal::pos(variable), Literal::neg(variable));
+            if component[pos.key()] == component[neg.key()] {
+                return Err(Unsatisfiable {
+                    variable,
+                    to_negation: self.implication_chain(pos, neg),
+                    from_negation: self.implication_chain(neg, pos),
+                });
+            }
+            assignment.push(component[pos.key()] < component[neg.key()]);
+        }
+        Ok(assignment)
+    }
+
+    /// Find a shortest chain of implications from src to dst.
+    fn implication_chain(&self, src: Literal, dst: Literal) -> Vec<Literal> {
+        let mut predecessors = HashMap::from([(src.key(), src.key())]);
+        let mut queue = VecDeque::from([src.key()]);
+        while let Some(key) = queue.pop_front() {
+            if key == dst.key() {
+                break;
+            }
+            for next in self.implications.get_adj(&key).into_iter().flatten() {
+                if !predecessors.contains_key(next) {
+                    predecessors.insert(*next, key);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        let mut chain = vec![dst];
+        let mut key = dst.key();
+        while key != src.key() {
+            key = predecessors[&key];
+            chain.push(Literal::from_key(key));
+        }
+        chain.reverse();
+        chain
+    }
+}
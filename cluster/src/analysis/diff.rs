@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+use crate::{Cluster, Node};
+
+/// Differences between two versions of a Cluster.
+/// In an undirected Cluster, each edge is reported once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphDiff<K> {
+    /// Nodes of the new Cluster missing from the old one.
+    pub added_nodes: Vec<K>,
+    /// Nodes of the old Cluster missing from the new one.
+    pub removed_nodes: Vec<K>,
+    /// Edges of the new Cluster missing from the old one.
+    pub added_edges: Vec<(K, K)>,
+    /// Edges of the old Cluster missing from the new one.
+    pub removed_edges: Vec<(K, K)>,
+}
+
+impl<K> GraphDiff<K> {
+    /// Check if both Clusters have the same nodes and edges.
+    /// # Return
+    /// True if nothing has been added nor removed, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+/// List the differences one per line, prefixed by - when only in the old Cluster and by + when only in the new one.
+impl<K: Debug> Display for GraphDiff<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} nodes and {} edges differ (- only in old, + only in new)",
+            self.added_nodes.len() + self.removed_nodes.len(),
+            self.added_edges.len() + self.removed_edges.len()
+        )?;
+        for key in &self.removed_nodes {
+            writeln!(f, "- node {:?}", key)?;
+        }
+        for key in &self.added_nodes {
+            writeln!(f, "+ node {:?}", key)?;
+        }
+        for (src, dst) in &self.removed_edges {
+            writeln!(f, "- edge {:?} -> {:?}", src, dst)?;
+        }
+        for (src, dst) in &self.added_edges {
+            writeln!(f, "+ edge {:?} -> {:?}", src, dst)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compute the nodes and edges added and removed between two versions of a Cluster.
+/// Only the keys and the edges are compared, not the values of the nodes.
+/// # Parameters
+/// - old - The previous version of the Cluster.
+/// - new - The current version of the Cluster.
+/// # Return
+/// The GraphDiff going from old to new.
+pub fn diff<K, N, C, M, D>(old: &C, new: &D) -> GraphDiff<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: Node<K>,
+    D: Cluster<K, M>,
+{
+    let directed = old.is_directed() || new.is_directed();
+    let old_edges = edge_set(old);
+    let new_edges = edge_set(new);
+    GraphDiff {
+        added_nodes: new
+            .keys()
+            .filter(|k| !old.contains_key(k))
+            .cloned()
+            .collect(),
+        removed_nodes: old
+            .keys()
+            .filter(|k| !new.contains_key(k))
+            .cloned()
+            .collect(),
+        added_edges: missing_edges(&new_edges, &old_edges, directed),
+        removed_edges: missing_edges(&old_edges, &new_edges, directed),
+    }
+}
+
+/// Collect the (source, destination) pairs of the edges of a Cluster.
+fn edge_set<K, N, C>(cluster: &C) -> HashSet<(K, K)>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    cluster
+        .keys()
+        .flat_map(|src| {
+            cluster
+                .get_adj(src)
+                .into_iter()
+                .flatten()
+                .map(move |dst| (src.clone(), dst.clone()))
+        })
+        .collect()
+}
+
+/// Get the edges of from that are not in to, an undirected edge being reported once.
+fn missing_edges<K: Hash + Eq + Clone>(
+    from: &HashSet<(K, K)>,
+    to: &HashSet<(K, K)>,
+    directed: bool,
+) -> Vec<(K, K)> {
+    let mut reported = HashSet::new();
+    let mut missing = Vec::new();
+    for (src, dst) in from {
+        if to.contains(&(src.clone(), dst.clone())) {
+            continue;
+        }
+        if !directed && reported.contains(&(dst, src)) {
+            continue;
+        }
+        reported.insert((src, dst));
+        missing.push((src.clone(), dst.clone()));
+    }
+    missing
+}
+
+/// Assert that two Clusters have the same keys and edges, the values of the nodes being ignored.
+/// On failure, the panic message lists the nodes and edges only in the left Cluster (-) and only in the right one (+),
+/// instead of the Debug representation of both Clusters.
+/// An optional message can be given after the Clusters, formatted as with assert_eq.
+#[macro_export]
+macro_rules! assert_graph_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_graph_eq!($left, $right, "assertion `left == right` failed: the Clusters differ")
+    };
+    ($left:expr, $right:expr, $($message:tt)+) => {{
+        let difference = $crate::analysis::diff(&$left, &$right);
+        if !difference.is_empty() {
+            panic!("{}\n{}", format_args!($($message)+), difference);
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster, UndirectedHashCluster};
+
+    fn directed(edges: &[(u32, u32)]) -> HashCluster<u32, BasicNode<u32>> {
+        edges.iter().copied().collect()
+    }
+
+    #[test]
+    fn diff_lists_what_changed() {
+        let old = directed(&[(0, 1), (1, 2)]);
+        let mut new = directed(&[(0, 1), (1, 3)]);
+        new.insert(4, BasicNode::new(()));
+        let mut changes = diff(&old, &new);
+        changes.added_nodes.sort();
+        assert_eq!(changes.added_nodes, vec![3, 4]);
+        assert_eq!(changes.removed_nodes, vec![2]);
+        assert_eq!(changes.added_edges, vec![(1, 3)]);
+        assert_eq!(changes.removed_edges, vec![(1, 2)]);
+        let text = changes.to_string();
+        assert!(text.starts_with("3 nodes and 2 edges differ"));
+        assert!(text.contains("- edge 1 -> 2\n") && text.contains("+ node 4\n"));
+    }
+
+    #[test]
+    fn undirected_edges_are_reported_once() {
+        let old: UndirectedHashCluster<u32, BasicNode<u32>> = UndirectedHashCluster::new();
+        let mut new: UndirectedHashCluster<u32, BasicNode<u32>> = UndirectedHashCluster::new();
+        new.insert(0, BasicNode::new(()));
+        new.insert(1, BasicNode::new(()));
+        new.add_edge(0, 1).unwrap();
+        assert_eq!(diff(&old, &new).added_edges.len(), 1);
+    }
+
+    #[test]
+    fn equal_clusters_pass_whatever_the_values() {
+        let left = directed(&[(0, 1), (1, 0)]);
+        let mut right: HashCluster<u32, BasicNode<u32, &str>> = HashCluster::new();
+        right.insert(0, BasicNode::new("zero"));
+        right.insert(1, BasicNode::new("one"));
+        right.add_edge(1, 0).unwrap();
+        right.add_edge(0, 1).unwrap();
+        crate::assert_graph_eq!(left, right);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "rewired\n1 nodes and 1 edges differ (- only in old, + only in new)\n+ node 2\n+ edge 1 -> 2\n"
+    )]
+    fn different_clusters_panic_with_the_diff() {
+        let left = directed(&[(0, 1)]);
+        let right = directed(&[(0, 1), (1, 2)]);
+        crate::assert_graph_eq!(left, right, "{}", "rewired");
+    }
+}
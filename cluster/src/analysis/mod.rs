@@ -0,0 +1,17 @@
+//! Summaries and metrics describing the structure of a Cluster.
+
+mod diff;
+mod motifs;
+mod report;
+mod stats;
+mod triangles;
+mod weighted;
+
+pub use diff::{diff, GraphDiff};
+pub use motifs::{motif_census, Motif, MotifSize};
+#[cfg(feature = "rand")]
+pub use motifs::{motif_significance, MotifSignificance};
+pub use report::{report, GraphReport};
+pub use stats::{stats, ClusterStats};
+pub use triangles::{global_clustering, local_clustering, triangle_count};
+pub use weighted::{strengths, weight_stats, weighted_clustering, Strength, WeightStats};
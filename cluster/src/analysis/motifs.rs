@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+#[cfg(feature = "rand")]
+use crate::{ClusterError, RandomSource, Result};
+
+/// Number of nodes of the motifs counted by a census.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MotifSize {
+    /// Motifs of 3 nodes : 13 classes in a directed Cluster, 2 in an undirected one.
+    Three,
+    /// Motifs of 4 nodes : 199 classes in a directed Cluster, 6 in an undirected one.
+    Four,
+}
+
+impl MotifSize {
+    fn nodes(self) -> usize {
+        match self {
+            MotifSize::Three => 3,
+            MotifSize::Four => 4,
+        }
+    }
+}
+
+/// Isomorphism class of a small connected subgraph, the edges between its nodes being taken as they are in the Cluster.
+/// Its nodes are numbered so that the bit set of its edges is the smallest among all numberings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Motif {
+    size: usize,
+    edges: u16,
+}
+
+impl Motif {
+    /// Get the number of nodes of the Motif.
+    /// # Return
+    /// The number of nodes, 3 or 4.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Check if the Motif has an edge between two of its nodes.
+    /// # Parameters
+    /// - src - The number of the source node, lower than the size.
+    /// - dst - The number of the destination node, lower than the size.
+    /// # Return
+    /// True if the edge src -> dst is in the Motif, false otherwise.
+    pub fn has_edge(&self, src: usize, dst: usize) -> bool {
+        src < self.size && dst < self.size && self.edges >> (src * self.size + dst) & 1 == 1
+    }
+
+    /// Get the number of edges of the Motif, an undirected edge counting twice.
+    /// # Return
+    /// The number of (src, dst) pairs linked in the Motif.
+    pub fn edge_count(&self) -> usize {
+        self.edges.count_ones() as usize
+    }
+}
+
+/// Count, for each motif, of the connected induced subgraphs of a Cluster it stands for.
+/// In a directed Cluster, a subgraph is connected when it is weakly connected.
+/// Self-loops are ignored, and edges toward keys not in the Cluster are skipped.
+/// # Parameters
+/// - cluster - The Cluster to explore.
+/// - size - The number of nodes of the subgraphs to count.
+/// # Return
+/// The number of occurrences of each motif found at least once.
+pub fn motif_census<K, N, C>(cluster: &C, size: MotifSize) -> HashMap<Motif, usize>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let directed = IndexedGraph::directed(cluster);
+    let mut adj = directed.adj;
+    for dsts in adj.iter_mut() {
+        dsts.sort_unstable();
+        dsts.dedup();
+    }
+    let mut neighbours = vec![Vec::new(); adj.len()];
+    for (src, dsts) in adj.iter().enumerate() {
+        for dst in dsts.iter().copied().filter(|dst| *dst != src) {
+            neighbours[src].push(dst);
+            neighbours[dst].push(src);
+        }
+    }
+    for list in neighbours.iter_mut() {
+        list.sort_unstable();
+        list.dedup();
+    }
+    let mut census = Census {
+        size: size.nodes(),
+        adj: &adj,
+        neighbours: &neighbours,
+        canonical: vec![None; 1 << (size.nodes() * size.nodes())],
+        permutations: permutations(size.nodes()),
+        counts: HashMap::new(),
+    };
+    for (root, list) in neighbours.iter().enumerate() {
+        let extension: Vec<usize> = list.iter().copied().filter(|v| *v > root).collect();
+        census.extend(&mut vec![root], extension, root);
+    }
+    census.counts
+}
+
+/// Comparison of the count of a motif with its counts in randomized Clusters.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotifSignificance {
+    /// Number of occurrences of the motif in the Cluster.
+    pub count: usize,
+    /// Mean number of occurrences in the randomized Clusters.
+    pub mean: f64,
+    /// Standard deviation of the number of occurrences in the randomized Clusters.
+    pub std_dev: f64,
+    /// Number of standard deviations between the count and the mean, NaN or infinite if the randomized counts never vary.
+    pub z_score: f64,
+}
+
+/// Compare the motif census of a Cluster with the censuses of randomized copies keeping the degree of every node.
+/// Each copy is rewired with 10 swap attempts per edge, see algo::rewire::rewire.
+/// # Parameters
+/// - cluster - The Cluster to explore.
+/// - size - The number of nodes of the subgraphs to count.
+/// - samples - The number of randomized copies to count the motifs of, at least 1.
+/// - rng - The source of randomness.
+/// # Return
+/// The significance of each motif found at least once in the Cluster or in a randomized copy, an error if samples is 0.
+#[cfg(feature = "rand")]
+pub fn motif_significance<K, N, C, R>(
+    cluster: &C,
+    size: MotifSize,
+    samples: usize,
+    rng: &mut R,
+) -> Result<HashMap<Motif, MotifSignificance>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N> + Clone,
+    R: RandomSource + ?Sized,
+{
+    if samples == 0 {
+        return Err(ClusterError::detailled(
+            "At least one randomized copy is needed to compare the motifs with.",
+        )
+        .into());
+    }
+    let observed = motif_census(cluster, size);
+    let edges: usize = cluster
+        .keys()
+        .map(|key| cluster.get_adj(key).map_or(0, |adj| adj.len()))
+        .sum();
+    let mut moments: HashMap<Motif, Moments> = observed
+        .keys()
+        .map(|motif| (*motif, Moments::default()))
+        .collect();
+    for sample in 0..samples {
+        let mut copy = cluster.clone();
+        crate::algo::rewire::rewire(&mut copy, 10 * edges, rng);
+        let random = motif_census(&copy, size);
+        for motif in random.keys() {
+            moments.entry(*motif).or_insert(Moments::zeros(sample));
+        }
+        for (motif, moments) in moments.iter_mut() {
+            moments.add(random.get(motif).copied().unwrap_or(0));
+        }
+    }
+    Ok(moments
+        .into_iter()
+        .map(|(motif, moments)| {
+            let count = observed.get(&motif).copied().unwrap_or(0);
+            (motif, moments.significance(count))
+        })
+        .collect())
+}
+
+/// Mean and sum of the squared deviations of the counts of a motif in the randomized Clusters, updated with Welford's method.
+/// The counts are converted to f64 before any product, so large counts can neither overflow nor cancel out the variance.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy, Default)]
+struct Moments {
+    samples: usize,
+    mean: f64,
+    deviations: f64,
+}
+
+#[cfg(feature = "rand")]
+impl Moments {
+    /// Moments of a motif absent from the first samples.
+    fn zeros(samples: usize) -> Moments {
+        Moments {
+            samples,
+            ..Moments::default()
+        }
+    }
+
+    fn add(&mut self, count: usize) {
+        let count = count as f64;
+        self.samples += 1;
+        let delta = count - self.mean;
+        self.mean += delta / self.samples as f64;
+        self.deviations += delta * (count - self.mean);
+    }
+
+    fn significance(&self, count: usize) -> MotifSignificance {
+        let std_dev = (self.deviations / self.samples as f64).max(0.0).sqrt();
+        MotifSignificance {
+            count,
+            mean: self.mean,
+            std_dev,
+            z_score: (count as f64 - self.mean) / std_dev,
+        }
+    }
+}
+
+/// Enumeration of the connected induced subgraphs of an indexed graph (ESU algorithm).
+struct Census<'a> {
+    size: usize,
+    adj: &'a [Vec<usize>],
+    neighbours: &'a [Vec<usize>],
+    canonical: Vec<Option<u16>>,
+    permutations: Vec<Vec<usize>>,
+    counts: HashMap<Motif, usize>,
+}
+
+impl Census<'_> {
+    /// Grow the subgraph with the nodes of the extension, only nodes greater than the root being added
+    /// and each new neighbour being added to the extension by the first node of the subgraph reaching it.
+    fn extend(&mut self, subgraph: &mut Vec<usize>, mut extension: Vec<usize>, root: usize) {
+        if subgraph.len() == self.size {
+            self.record(subgraph);
+            return;
+        }
+        while let Some(w) = extension.pop() {
+            let mut next = extension.clone();
+            for u in self.neighbours[w].iter().copied() {
+                if u > root
+                    && !subgraph.contains(&u)
+                    && !next.contains(&u)
+                    && !subgraph
+                        .iter()
+                        .any(|v| self.neighbours[*v].binary_search(&u).is_ok())
+                {
+                    next.push(u);
+                }
+            }
+            subgraph.push(w);
+            self.extend(subgraph, next, root);
+            subgraph.pop();
+        }
+    }
+
+    fn record(&mut self, subgraph: &[usize]) {
+        let mut edges = 0u16;
+        for (i, src) in subgraph.iter().enumerate() {
+            for (j, dst) in subgraph.iter().enumerate() {
+                if i != j && self.adj[*src].binary_search(dst).is_ok() {
+                    edges |= 1 << (i * self.size + j);
+                }
+            }
+        }
+        let canonical = match self.canonical[edges as usize] {
+            Some(canonical) => canonical,
+            None => {
+                let canonical = self
+                    .permutations
+                    .iter()
+                    .map(|p| permute(edges, p))
+                    .min()
+                    .unwrap();
+                self.canonical[edges as usize] = Some(canonical);
+                canonical
+            }
+        };
+        let motif = Motif {
+            size: self.size,
+            edges: canonical,
+        };
+        *self.counts.entry(motif).or_default() += 1;
+    }
+}
+
+/// Renumber the nodes of an edge bit set, node i becoming node p[i].
+fn permute(edges: u16, p: &[usize]) -> u16 {
+    let size = p.len();
+    let mut permuted = 0;
+    for i in 0..size {
+        for j in 0..size {
+            if edges >> (i * size + j) & 1 == 1 {
+                permuted |= 1 << (p[i] * size + p[j]);
+            }
+        }
+    }
+    permuted
+}
+
+fn permutations(size: usize) -> Vec<Vec<usize>> {
+    let mut all = vec![Vec::new()];
+    for n in 0..size {
+        all = all
+            .into_iter()
+            .flat_map(|p: Vec<usize>| {
+                (0..=n).map(move |i| {
+                    let mut q = p.clone();
+                    q.insert(i, n);
+                    q
+                })
+            })
+            .collect();
+    }
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster};
+
+    #[cfg(feature = "rand")]
+    fn circulant() -> crate::UndirectedHashCluster<usize, BasicNode<usize>> {
+        let mut cluster = crate::UndirectedHashCluster::new();
+        for key in 0..8 {
+            cluster.insert(key, BasicNode::new(()));
+        }
+        for key in 0..8 {
+            cluster.add_edge(key, (key + 1) % 8).unwrap();
+            cluster.add_edge(key, (key + 3) % 8).unwrap();
+        }
+        cluster
+    }
+
+    #[test]
+    fn census_counts_every_connected_triple() {
+        let mut triangle: HashCluster<u32, BasicNode<u32>> = HashCluster::new();
+        for key in 0..4 {
+            triangle.insert(key, BasicNode::new(()));
+        }
+        triangle.add_edge(0, 1).unwrap();
+        triangle.add_edge(1, 2).unwrap();
+        triangle.add_edge(2, 0).unwrap();
+        triangle.add_edge(2, 3).unwrap();
+        let census = motif_census(&triangle, MotifSize::Three);
+        assert_eq!(census.values().sum::<usize>(), 3);
+        assert!(census.keys().all(|motif| motif.size() == 3));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn significance_needs_samples() {
+        let mut rng = crate::SplitMix64::new(7);
+        assert!(motif_significance(&circulant(), MotifSize::Three, 0, &mut rng).is_err());
+        let significance =
+            motif_significance(&circulant(), MotifSize::Three, 20, &mut rng).unwrap();
+        assert!(!significance.is_empty());
+        assert!(significance
+            .values()
+            .all(|s| s.mean.is_finite() && s.std_dev.is_finite()));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn moments_of_large_counts_do_not_overflow() {
+        // Squaring such a count overflows a usize, and summing the squares in f64 cancels out the variance.
+        let count = 1 << 40;
+        let mut moments = Moments::default();
+        moments.add(count);
+        moments.add(count + 2);
+        let significance = moments.significance(count + 3);
+        assert_eq!(significance.mean, (count + 1) as f64);
+        assert_eq!(significance.std_dev, 1.0);
+        assert_eq!(significance.z_score, 2.0);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn moments_count_the_samples_a_motif_is_missing_from() {
+        let mut moments = Moments::zeros(2);
+        moments.add(4);
+        moments.add(4);
+        let significance = moments.significance(2);
+        assert_eq!(significance.mean, 2.0);
+        assert_eq!(significance.std_dev, 2.0);
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Write};
+use std::hash::Hash;
+
+use crate::{Cluster, Node};
+
+/// Number of hubs listed in a GraphReport.
+const TOP_HUBS: usize = 5;
+
+/// Human-readable summary of the structure of a Cluster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphReport<K> {
+    /// Number of nodes in the Cluster.
+    pub node_count: usize,
+    /// Number of edges between nodes of the Cluster, an undirected edge being counted once.
+    pub edge_count: usize,
+    /// True if the edges of the Cluster are directed.
+    pub directed: bool,
+    /// Nodes with the highest degree, along with their degree, highest first.
+    pub top_hubs: Vec<(K, usize)>,
+    /// Number of connected components, the direction of the edges being ignored.
+    pub component_count: usize,
+    /// Number of nodes in the largest connected component.
+    pub largest_component: usize,
+    /// Nodes without any edge.
+    pub isolated_nodes: Vec<K>,
+    /// Nodes having an edge to themselves.
+    pub self_loops: Vec<K>,
+    /// Edges whose destination is not in the Cluster.
+    pub dangling_edges: Vec<(K, K)>,
+    /// Edges appearing more than once in the adjacency list of their source.
+    pub duplicate_edges: Vec<(K, K)>,
+}
+
+/// Build the GraphReport of a Cluster.
+/// # Parameter
+/// - cluster - The Cluster to summarize.
+/// # Return
+/// The GraphReport of the Cluster.
+pub fn report<K, N, C>(cluster: &C) -> GraphReport<K>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let mut degrees: HashMap<&K, usize> = cluster.keys().map(|k| (k, 0)).collect();
+    let mut neighbours: HashMap<&K, Vec<&K>> = HashMap::new();
+    let mut self_loops = Vec::new();
+    let mut dangling_edges = Vec::new();
+    let mut duplicate_edges = Vec::new();
+    let mut entries = 0;
+    for src in cluster.keys() {
+        let adj = cluster.get_adj(src).unwrap_or(&[]);
+        let mut seen = HashSet::new();
+        for dst in adj {
+            if !seen.insert(dst) {
+                duplicate_edges.push((src.clone(), dst.clone()));
+            }
+            if !cluster.contains_key(dst) {
+                dangling_edges.push((src.clone(), dst.clone()));
+                continue;
+            }
+            entries += 1;
+            if src == dst {
+                self_loops.push(src.clone());
+            }
+            *degrees.entry(src).or_default() += 1;
+            if cluster.is_directed() {
+                *degrees.entry(dst).or_default() += 1;
+            }
+            neighbours.entry(src).or_default().push(dst);
+            neighbours.entry(dst).or_default().push(src);
+        }
+    }
+    let edge_count = if cluster.is_directed() {
+        entries
+    } else {
+        (entries + self_loops.len()) / 2
+    };
+
+    let mut isolated_nodes = Vec::new();
+    let mut component_count = 0;
+    let mut largest_component = 0;
+    let mut visited = HashSet::new();
+    for key in cluster.keys() {
+        if !neighbours.contains_key(key) {
+            isolated_nodes.push(key.clone());
+        }
+        if !visited.insert(key) {
+            continue;
+        }
+        component_count += 1;
+        let mut size = 0;
+        let mut stack = vec![key];
+        while let Some(current) = stack.pop() {
+            size += 1;
+            for next in neighbours.get(current).into_iter().flatten() {
+                if visited.insert(*next) {
+                    stack.push(*next);
+                }
+            }
+        }
+        largest_component = largest_component.max(size);
+    }
+
+    let mut top_hubs: Vec<(K, usize)> = degrees
+        .into_iter()
+        .filter(|(_, degree)| *degree > 0)
+        .map(|(k, degree)| (k.clone(), degree))
+        .collect();
+    top_hubs.sort_by_key(|(_, degree)| std::cmp::Reverse(*degree));
+    top_hubs.truncate(TOP_HUBS);
+
+    GraphReport {
+        node_count: cluster.len(),
+        edge_count,
+        directed: cluster.is_directed(),
+        top_hubs,
+        component_count,
+        largest_component,
+        isolated_nodes,
+        self_loops,
+        dangling_edges,
+        duplicate_edges,
+    }
+}
+
+impl<K: Debug> GraphReport<K> {
+    /// Render the report as plain text.
+    /// # Return
+    /// The text rendering of the report.
+    pub fn to_text(&self) -> String {
+        let mut out = String::from("Cluster report\n");
+        for (name, value) in self.lines() {
+            let _ = writeln!(out, "{} : {}", name, value);
+        }
+        out
+    }
+
+    /// Render the report as a Markdown table.
+    /// # Return
+    /// The Markdown rendering of the report.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("## Cluster report\n\n| Metric | Value |\n| --- | --- |\n");
+        for (name, value) in self.lines() {
+            let _ = writeln!(out, "| {} | {} |", name, value.replace('|', "\\|"));
+        }
+        out
+    }
+
+    /// Render the report as a JSON object, keys being rendered as strings.
+    /// # Return
+    /// The JSON rendering of the report.
+    pub fn to_json(&self) -> String {
+        let key = |k: &K| json_string(&format!("{:?}", k));
+        let keys = |ks: &[K]| ks.iter().map(key).collect::<Vec<_>>().join(",");
+        let edges = |es: &[(K, K)]| {
+            es.iter()
+                .map(|(src, dst)| format!("[{},{}]", key(src), key(dst)))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let hubs = self
+            .top_hubs
+            .iter()
+            .map(|(k, degree)| format!("{{\"key\":{},\"degree\":{}}}", key(k), degree))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{{\"node_count\":{},\"edge_count\":{},\"directed\":{},\"top_hubs\":[{}],\
+             \"component_count\":{},\"largest_component\":{},\"isolated_nodes\":[{}],\
+             \"self_loops\":[{}],\"dangling_edges\":[{}],\"duplicate_edges\":[{}]}}",
+            self.node_count,
+            self.edge_count,
+            self.directed,
+            hubs,
+            self.component_count,
+            self.largest_component,
+            keys(&self.isolated_nodes),
+            keys(&self.self_loops),
+            edges(&self.dangling_edges),
+            edges(&self.duplicate_edges),
+        )
+    }
+
+    /// Get the (metric, value) lines shared by the text and Markdown renderings.
+    fn lines(&self) -> Vec<(&'static str, String)> {
+        let list = |items: Vec<String>| {
+            if items.is_empty() {
+                String::from("none")
+            } else {
+                items.join(", ")
+            }
+        };
+        let edges = |es: &[(K, K)]| {
+            list(
+                es.iter()
+                    .map(|(s, d)| format!("{:?} -> {:?}", s, d))
+                    .collect(),
+            )
+        };
+        vec![
+            ("Nodes", self.node_count.to_string()),
+            (
+                "Edges",
+                format!(
+                    "{} ({})",
+                    self.edge_count,
+                    if self.directed {
+                        "directed"
+                    } else {
+                        "undirected"
+                    }
+                ),
+            ),
+            (
+                "Components",
+                format!(
+                    "{} (largest has {} nodes)",
+                    self.component_count, self.largest_component
+                ),
+            ),
+            (
+                "Top hubs",
+                list(
+                    self.top_hubs
+                        .iter()
+                        .map(|(k, degree)| format!("{:?} (degree {})", k, degree))
+                        .collect(),
+                ),
+            ),
+            (
+                "Isolated nodes",
+                list(
+                    self.isolated_nodes
+                        .iter()
+                        .map(|k| format!("{:?}", k))
+                        .collect(),
+                ),
+            ),
+            (
+                "Self-loops",
+                list(self.self_loops.iter().map(|k| format!("{:?}", k)).collect()),
+            ),
+            ("Dangling edges", edges(&self.dangling_edges)),
+            ("Duplicate edges", edges(&self.duplicate_edges)),
+        ]
+    }
+}
+
+impl<K: Debug> Display for GraphReport<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+/// Quote and escape a string so it can be embedded in JSON.
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
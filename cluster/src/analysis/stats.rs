@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::algo::connectivity::DisjointSet;
+use crate::{Cluster, Node};
+
+/// Numerical summary of the size and degrees of a Cluster, cheap to compute and to log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterStats {
+    /// Number of nodes in the Cluster.
+    pub node_count: usize,
+    /// Number of edges between nodes of the Cluster, an undirected edge being counted once.
+    pub edge_count: usize,
+    /// True if the edges of the Cluster are directed.
+    pub directed: bool,
+    /// Share of the possible edges between distinct nodes present in the Cluster, 0 with less than two nodes.
+    pub density: f64,
+    /// Smallest degree of a node, 0 for an empty Cluster.
+    pub min_degree: usize,
+    /// Largest degree of a node, 0 for an empty Cluster.
+    pub max_degree: usize,
+    /// Mean degree of the nodes, 0 for an empty Cluster.
+    pub mean_degree: f64,
+    /// Number of nodes of each degree, indexed by the degree.
+    pub degree_histogram: Vec<usize>,
+    /// Number of connected components, the direction of the edges being ignored.
+    pub component_count: usize,
+}
+
+/// Compute the ClusterStats of a Cluster.
+/// The degree of a node counts its edges in both directions in a directed Cluster, edges toward keys not in the Cluster being ignored.
+/// # Parameter
+/// - cluster - The Cluster to summarize.
+/// # Return
+/// The ClusterStats of the Cluster.
+pub fn stats<K, N, C>(cluster: &C) -> ClusterStats
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let directed = cluster.is_directed();
+    let mut degrees: HashMap<&K, usize> = cluster.keys().map(|k| (k, 0)).collect();
+    let mut entries = 0;
+    let mut loops = 0;
+    for src in cluster.keys() {
+        for dst in cluster.get_adj(src).into_iter().flatten() {
+            if !cluster.contains_key(dst) {
+                continue;
+            }
+            entries += 1;
+            if src == dst {
+                loops += 1;
+            }
+            *degrees.entry(src).or_default() += 1;
+            if directed {
+                *degrees.entry(dst).or_default() += 1;
+            }
+        }
+    }
+    let node_count = degrees.len();
+    let edge_count = if directed {
+        entries
+    } else {
+        (entries + loops) / 2
+    };
+    let pairs = node_count * node_count.saturating_sub(1);
+    let density = match (pairs, directed) {
+        (0, _) => 0.0,
+        (pairs, true) => (edge_count - loops) as f64 / pairs as f64,
+        (pairs, false) => 2.0 * (edge_count - loops) as f64 / pairs as f64,
+    };
+    let max_degree = degrees.values().copied().max().unwrap_or(0);
+    let mut degree_histogram = vec![0; max_degree + 1];
+    for degree in degrees.values() {
+        degree_histogram[*degree] += 1;
+    }
+    ClusterStats {
+        node_count,
+        edge_count,
+        directed,
+        density,
+        min_degree: degrees.values().copied().min().unwrap_or(0),
+        max_degree,
+        mean_degree: degrees.values().sum::<usize>() as f64 / node_count.max(1) as f64,
+        degree_histogram: if node_count == 0 {
+            Vec::new()
+        } else {
+            degree_histogram
+        },
+        component_count: DisjointSet::from_cluster(cluster).set_count(),
+    }
+}
+
+impl Display for ClusterStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} nodes, {} {} edges, density {:.4}, degree {}..{} (mean {:.2}), {} components",
+            self.node_count,
+            self.edge_count,
+            if self.directed {
+                "directed"
+            } else {
+                "undirected"
+            },
+            self.density,
+            self.min_degree,
+            self.max_degree,
+            self.mean_degree,
+            self.component_count
+        )
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::algo::indexed::IndexedGraph;
+use crate::{Cluster, Node};
+
+/// Count the triangles of a Cluster seen as a simple undirected graph.
+/// Each edge is oriented from its end of lower degree toward the other, so each triangle is found once
+/// by intersecting short adjacency lists, in O(m √m) time.
+/// # Parameter
+/// - cluster - The Cluster to measure.
+/// # Return
+/// The number of triangles of the Cluster.
+pub fn triangle_count<K, N, C>(cluster: &C) -> usize
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    triangles(&graph.adj).iter().sum::<usize>() / 3
+}
+
+/// Compute the local clustering coefficient of each node of a Cluster seen as a simple undirected graph :
+/// the share of the pairs of its neighbours that are linked to each other.
+/// The nodes having less than two neighbours get a coefficient of 0.
+/// # Parameter
+/// - cluster - The Cluster to measure.
+/// # Return
+/// The coefficient of each key of the Cluster, between 0 and 1.
+pub fn local_clustering<K, N, C>(cluster: &C) -> HashMap<K, f64>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let counts = triangles(&graph.adj);
+    graph
+        .keys
+        .into_iter()
+        .enumerate()
+        .map(|(v, key)| {
+            let pairs = pairs(graph.adj[v].len());
+            let coefficient = if pairs == 0 {
+                0.0
+            } else {
+                counts[v] as f64 / pairs as f64
+            };
+            (key, coefficient)
+        })
+        .collect()
+}
+
+/// Compute the global clustering coefficient of a Cluster seen as a simple undirected graph, also known as its transitivity :
+/// three times the number of triangles over the number of paths of length two.
+/// # Parameter
+/// - cluster - The Cluster to measure.
+/// # Return
+/// The coefficient of the Cluster, between 0 and 1, 0 if it has no path of length two.
+pub fn global_clustering<K, N, C>(cluster: &C) -> f64
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let graph = IndexedGraph::undirected(cluster);
+    let closed: usize = triangles(&graph.adj).iter().sum();
+    let paths: usize = graph.adj.iter().map(|n| pairs(n.len())).sum();
+    if paths == 0 {
+        return 0.0;
+    }
+    closed as f64 / paths as f64
+}
+
+/// Count the triangles each node of a simple undirected graph belongs to.
+fn triangles(adj: &[Vec<usize>]) -> Vec<usize> {
+    let rank = |v: usize| (adj[v].len(), v);
+    let forward: Vec<Vec<usize>> = adj
+        .iter()
+        .enumerate()
+        .map(|(v, n)| n.iter().copied().filter(|w| rank(*w) > rank(v)).collect())
+        .collect();
+    let mut counts = vec![0; adj.len()];
+    let mut marked = vec![false; adj.len()];
+    for (v, higher) in forward.iter().enumerate() {
+        for w in higher {
+            marked[*w] = true;
+        }
+        for u in higher {
+            for w in &forward[*u] {
+                if marked[*w] {
+                    counts[v] += 1;
+                    counts[*u] += 1;
+                    counts[*w] += 1;
+                }
+            }
+        }
+        for w in higher {
+            marked[*w] = false;
+        }
+    }
+    counts
+}
+
+/// Get the number of pairs among a number of neighbours.
+fn pairs(degree: usize) -> usize {
+    degree * degree.saturating_sub(1) / 2
+}
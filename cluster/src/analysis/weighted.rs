@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Weight, WeightedCluster, WeightedNode};
+
+/// Total weight of the edges leaving and entering a node.
+/// In an undirected Cluster, both are the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Strength<W> {
+    /// Sum of the weights of the edges leaving the node.
+    pub outgoing: W,
+    /// Sum of the weights of the edges entering the node.
+    pub incoming: W,
+}
+
+/// Distribution of the weights of the edges of a Cluster.
+/// In an undirected Cluster, each edge is counted once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightStats<W> {
+    /// Number of edges.
+    pub count: usize,
+    /// Sum of the weights.
+    pub total: W,
+    /// Smallest weight.
+    pub min: W,
+    /// Largest weight.
+    pub max: W,
+    /// Mean weight.
+    pub mean: f64,
+    /// Population standard deviation of the weights.
+    pub std_dev: f64,
+}
+
+/// Compute the strength of each node, the weighted counterpart of its degree.
+/// Edges toward keys not in the Cluster are ignored.
+/// # Parameter
+/// - cluster - The weighted Cluster to measure.
+/// # Return
+/// The Strength of each key of the Cluster.
+pub fn strengths<K, N, C>(cluster: &C) -> HashMap<K, Strength<N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let zero = Strength {
+        outgoing: N::Weight::zero(),
+        incoming: N::Weight::zero(),
+    };
+    let mut strengths: HashMap<K, Strength<N::Weight>> =
+        cluster.keys().map(|k| (k.clone(), zero)).collect();
+    for (src, dst, weight) in weighted_edges(cluster, true) {
+        if let Some(strength) = strengths.get_mut(&src) {
+            strength.outgoing = strength.outgoing + weight;
+        }
+        if let Some(strength) = strengths.get_mut(&dst) {
+            strength.incoming = strength.incoming + weight;
+        }
+    }
+    strengths
+}
+
+/// Compute the weighted clustering coefficient of each node, as defined by Barrat et al.
+/// The Cluster is seen as undirected : in a directed Cluster, the weight of a link is the sum of the weights of both of its directions.
+/// Self-loops are ignored, the nodes having less than two neighbours get a coefficient of 0.
+/// # Parameter
+/// - cluster - The weighted Cluster to measure.
+/// # Return
+/// The coefficient of each key of the Cluster, between 0 and 1.
+pub fn weighted_clustering<K, N, C>(cluster: &C) -> HashMap<K, f64>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Into<f64>,
+    C: WeightedCluster<K, N>,
+{
+    let mut links: HashMap<K, HashMap<K, f64>> = cluster
+        .keys()
+        .map(|k| (k.clone(), HashMap::new()))
+        .collect();
+    for (src, dst, weight) in weighted_edges(cluster, false) {
+        if src == dst {
+            continue;
+        }
+        let weight: f64 = weight.into();
+        *links.get_mut(&src).unwrap().entry(dst.clone()).or_default() += weight;
+        *links.get_mut(&dst).unwrap().entry(src).or_default() += weight;
+    }
+    links
+        .iter()
+        .map(|(key, neighbours)| {
+            let degree = neighbours.len();
+            if degree < 2 {
+                return (key.clone(), 0.0);
+            }
+            let strength: f64 = neighbours.values().sum();
+            let mut closed = 0.0;
+            for (j, w_ij) in neighbours {
+                for (h, w_ih) in neighbours {
+                    if j != h && links[j].contains_key(h) {
+                        closed += (w_ij + w_ih) / 2.0;
+                    }
+                }
+            }
+            (key.clone(), closed / (strength * (degree - 1) as f64))
+        })
+        .collect()
+}
+
+/// Compute the distribution of the weights of the edges of a Cluster.
+/// # Parameter
+/// - cluster - The weighted Cluster to measure.
+/// # Return
+/// An Option containing the WeightStats of the Cluster, None if it has no edge.
+pub fn weight_stats<K, N, C>(cluster: &C) -> Option<WeightStats<N::Weight>>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    N::Weight: Into<f64>,
+    C: WeightedCluster<K, N>,
+{
+    let weights: Vec<N::Weight> = weighted_edges(cluster, false)
+        .into_iter()
+        .map(|(_, _, weight)| weight)
+        .collect();
+    let first = *weights.first()?;
+    let mut stats = WeightStats {
+        count: weights.len(),
+        total: N::Weight::zero(),
+        min: first,
+        max: first,
+        mean: 0.0,
+        std_dev: 0.0,
+    };
+    for weight in &weights {
+        stats.total = stats.total + *weight;
+        if *weight < stats.min {
+            stats.min = *weight;
+        }
+        if *weight > stats.max {
+            stats.max = *weight;
+        }
+    }
+    let count = weights.len() as f64;
+    stats.mean = weights.iter().map(|w| (*w).into()).sum::<f64>() / count;
+    let variance = weights
+        .iter()
+        .map(|w| ((*w).into() - stats.mean).powi(2))
+        .sum::<f64>()
+        / count;
+    stats.std_dev = variance.sqrt();
+    Some(stats)
+}
+
+/// Get the weighted edges of a Cluster, edges toward keys not in the Cluster being ignored.
+/// Unless both_directions is set, each edge of an undirected Cluster is reported once.
+fn weighted_edges<K, N, C>(cluster: &C, both_directions: bool) -> Vec<(K, K, N::Weight)>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let once = !both_directions && !cluster.is_directed();
+    let mut order: HashMap<&K, usize> = HashMap::new();
+    for (i, key) in cluster.keys().enumerate() {
+        order.insert(key, i);
+    }
+    let mut edges = Vec::new();
+    for src in cluster.keys() {
+        for (dst, weight) in cluster.weighted_adj(src).into_iter().flatten() {
+            let Some(position) = order.get(&dst) else {
+                continue;
+            };
+            if once && *position < order[src] {
+                continue;
+            }
+            edges.push((src.clone(), dst, weight));
+        }
+    }
+    edges
+}
@@ -0,0 +1,396 @@
+//! Compatibility layer for the Clusters written against the first version of the traits (feature `compat`).
+//!
+//! The first Cluster trait had no keys() method and its Node handed out its adjacency as a `&Vec<K>`.
+//! Code implementing them keeps compiling by importing the traits of this module instead of the ones of the crate root.
+//! Wrapping a legacy Cluster in a Tracked gives a Cluster of the crate root, able to run every algorithm,
+//! its nodes being seen through the Legacy wrapper.
+
+use crate::{ClusterError, Result};
+
+/// Node trait of the first version of the crate.
+pub trait Node<K> {
+    /// Get the adjacency of the current Node.
+    /// # Return
+    /// A immutable reference to the adjacency list of the current Node.
+    fn adj(&self) -> &Vec<K>;
+
+    /// Get the adjacency of the current Node.
+    /// # Return
+    /// A mutable reference to the adjacency list of the current Node.
+    fn adj_mut(&mut self) -> &mut Vec<K>;
+}
+
+/// Legacy Node seen as a crate::Node, the nodes of a Tracked Cluster being handed out through it.
+#[repr(transparent)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Legacy<N>(pub N);
+
+impl<N> Legacy<N> {
+    fn from_ref(node: &N) -> &Legacy<N> {
+        // SAFETY: Legacy is a transparent wrapper, it has the layout of N.
+        unsafe { &*(node as *const N as *const Legacy<N>) }
+    }
+
+    fn from_mut(node: &mut N) -> &mut Legacy<N> {
+        // SAFETY: Legacy is a transparent wrapper, it has the layout of N.
+        unsafe { &mut *(node as *mut N as *mut Legacy<N>) }
+    }
+}
+
+impl<K, N: Node<K>> crate::Node<K> for Legacy<N> {
+    fn adj(&self) -> &[K] {
+        self.0.adj()
+    }
+
+    fn adj_mut(&mut self) -> &mut Vec<K> {
+        self.0.adj_mut()
+    }
+}
+
+/// Cluster trait of the first version of the crate, without the keys() method required since.
+/// Wrap a Cluster implementing it in a Tracked to get a crate::Cluster.
+pub trait Cluster<K, N: Node<K>>
+where
+    K: PartialEq,
+    K: Clone,
+{
+    /// Removes the designated value from the Cluster
+    /// # Parameter
+    /// - key - The key of the value to remove.
+    /// # Return
+    /// An Option containing the value if it exists, None otherwise
+    fn remove(&mut self, key: &K) -> Option<N>;
+
+    /// Check if the Cluster contains a value at a given key.
+    /// # Parameter
+    /// - key - The key on we want to check the Cluster contains it or no.
+    ///
+    /// # Return
+    /// True if the key is in the Cluster, false otherwise.
+    ///
+    fn contains_key(&self, key: &K) -> bool;
+
+    /// Get a value from the Cluster.
+    /// # Parameter
+    /// - key - the key of the node in the Cluster.
+    ///
+    /// # Return
+    /// An option containing an immutable reference to the Value if present in the Cluster, returns None otherwise.
+    fn get(&self, key: &K) -> Option<&N>;
+
+    /// Get a value from the Cluster.
+    /// # Parameter
+    /// - key - the key of the value in the Cluster.
+    ///
+    /// # Return
+    /// An option containing an mutable reference to the value if present in the Cluster, returns None otherwise.
+    fn get_mut(&mut self, key: &K) -> Option<&mut N>;
+
+    /// Get the adjancy list of the node designed by it key given in parameter.
+    /// # Parameter
+    /// - key - the index of the node we want to get the adjacency list.
+    /// # Return
+    /// An immutable reference to the adjacency list of the desgnated node of the Cluster or None if there is no such Node.
+    fn get_adj<'clu, 'res>(&'clu self, key: &K) -> Option<&'res Vec<K>>
+    where
+        'clu: 'res,
+        N: 'res,
+    {
+        self.get(key).map(|n| n.adj())
+    }
+
+    /// Generate a key that is not already used in the graph and returns it.
+    /// # Return
+    /// The key newly generated.
+    fn new_key(&self) -> K;
+
+    /// Add a node in the Cluster.
+    /// # Return
+    /// The index at which the node has been stored in the graph.
+    fn add(&mut self, node: N) -> K;
+
+    /// Get the adjancy list of the node designed by it key given in parameter.
+    /// # Parameter
+    /// - key - the index of the node we want to get the adjacency list.
+    /// # Return
+    /// A mutable reference to the adjacency list of the desgnated node of the Cluster or None if there is no such Node.
+    fn get_adj_mut<'clu, 'res>(&'clu mut self, key: &K) -> Option<&'res mut Vec<K>>
+    where
+        'clu: 'res,
+        N: 'res,
+    {
+        self.get_mut(key).map(|n| n.adj_mut())
+    }
+
+    /// Add an edge between src and dst in the Cluster.
+    /// # Parameters
+    /// - src - The key of the source node
+    /// - dst - The key of the destination node.
+    ///
+    /// # Return
+    /// Nothing if everithing gone well, an error otherwise.
+    ///
+    fn add_edge(&mut self, src: K, dst: K) -> Result<()> {
+        let adj = self.get_adj_mut(&src).ok_or(ClusterError::detailled(
+            "To add edge, both node must exists in the Cluster.",
+        ))?;
+        if !adj.contains(&dst) {
+            adj.push(dst);
+        }
+        Ok(())
+    }
+
+    /// Remove the edge between src and dst in the Cluster.
+    /// # Parameters
+    /// - src - The key of the source node
+    /// - dst - The key of the destination node.
+    ///
+    /// # Return
+    /// Nothing if everithing gone well, an error otherwise.
+    ///
+    fn remove_edge(&mut self, src: &K, dst: &K) -> Result<()> {
+        let adj = self
+            .get_adj_mut(src)
+            .ok_or(ClusterError::detailled("<src> node does not exists."))?;
+        if let Some(index) = adj.iter().position(|i| *i == *dst) {
+            adj.remove(index);
+        }
+        Ok(())
+    }
+
+    /// Add an edge between src and dst in the Cluster in both directions.
+    /// # Parameters
+    /// - src - The key of the source node
+    /// - dst - The key of the destination node.
+    ///
+    /// # Return
+    /// Nothing if everithing gone well, an error otherwise.
+    ///
+    fn add_doubly_edge(&mut self, src: K, dst: K) -> Result<()> {
+        self.add_edge(src.clone(), dst.clone())?;
+        self.add_edge(dst, src)?;
+        Ok(())
+    }
+
+    /// Remove the edges between src and dst in the Cluster in both directions.
+    /// # Parameters
+    /// - src - The key of the source node
+    /// - dst - The key of the destination node.
+    ///
+    /// # Return
+    /// Nothing if everithing gone well, an error otherwise.
+    ///
+    fn remove_doubly_edge(&mut self, src: &K, dst: &K) -> Result<()> {
+        self.remove_edge(src, dst)?;
+        self.remove_edge(dst, src)?;
+        Ok(())
+    }
+}
+
+/// Cluster wrapping a legacy Cluster and recording the keys of its nodes, so it implements crate::Cluster over Legacy nodes.
+/// The keys are those given at creation plus those of the nodes added through the Tracked Cluster,
+/// the nodes added or removed directly on the wrapped Cluster are not seen.
+/// Removing a node costs a linear search among the keys.
+#[derive(Debug, Clone, Default)]
+pub struct Tracked<K, C> {
+    inner: C,
+    keys: Vec<K>,
+}
+
+impl<K: PartialEq + Clone, C> Tracked<K, C> {
+    /// Wrap an empty legacy Cluster.
+    /// # Parameter
+    /// - cluster - The Cluster to wrap, the nodes it already holds being ignored.
+    /// # Return
+    /// The newly created Tracked Cluster.
+    pub fn new(cluster: C) -> Tracked<K, C> {
+        Tracked {
+            inner: cluster,
+            keys: Vec::new(),
+        }
+    }
+
+    /// Wrap a legacy Cluster already holding nodes.
+    /// # Parameters
+    /// - cluster - The Cluster to wrap.
+    /// - keys - The keys of the nodes of the Cluster, those not in it or repeated being ignored.
+    /// # Return
+    /// The newly created Tracked Cluster.
+    pub fn with_keys<N: Node<K>>(cluster: C, keys: impl IntoIterator<Item = K>) -> Tracked<K, C>
+    where
+        C: Cluster<K, N>,
+    {
+        let mut tracked = Tracked::new(cluster);
+        for key in keys {
+            if tracked.inner.contains_key(&key) && !tracked.keys.contains(&key) {
+                tracked.keys.push(key);
+            }
+        }
+        tracked
+    }
+
+    /// Get the wrapped Cluster.
+    /// # Return
+    /// A reference to the wrapped Cluster.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the Cluster.
+    /// # Return
+    /// The wrapped Cluster.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<K, N, C> crate::Cluster<K, Legacy<N>> for Tracked<K, C>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    fn remove(&mut self, key: &K) -> Option<Legacy<N>> {
+        let node = self.inner.remove(key)?;
+        if let Some(index) = self.keys.iter().position(|k| k == key) {
+            self.keys.swap_remove(index);
+        }
+        Some(Legacy(node))
+    }
+
+    fn keys<'clu>(&'clu self) -> impl Iterator<Item = &'clu K>
+    where
+        K: 'clu,
+    {
+        self.keys.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    fn get(&self, key: &K) -> Option<&Legacy<N>> {
+        self.inner.get(key).map(Legacy::from_ref)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut Legacy<N>> {
+        self.inner.get_mut(key).map(Legacy::from_mut)
+    }
+
+    fn new_key(&self) -> K {
+        self.inner.new_key()
+    }
+
+    fn add(&mut self, node: Legacy<N>) -> K {
+        let key = self.inner.add(node.0);
+        if !self.keys.contains(&key) {
+            self.keys.push(key.clone());
+        }
+        key
+    }
+
+    fn add_edge(&mut self, src: K, dst: K) -> Result<()> {
+        self.inner.add_edge(src, dst)
+    }
+
+    fn remove_edge(&mut self, src: &K, dst: &K) -> Result<()> {
+        self.inner.remove_edge(src, dst)
+    }
+
+    fn add_doubly_edge(&mut self, src: K, dst: K) -> Result<()> {
+        self.inner.add_doubly_edge(src, dst)
+    }
+
+    fn remove_doubly_edge(&mut self, src: &K, dst: &K) -> Result<()> {
+        self.inner.remove_doubly_edge(src, dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::traversal::Bfs;
+
+    struct OldNode {
+        adj: Vec<u32>,
+    }
+
+    impl Node<u32> for OldNode {
+        fn adj(&self) -> &Vec<u32> {
+            &self.adj
+        }
+
+        fn adj_mut(&mut self) -> &mut Vec<u32> {
+            &mut self.adj
+        }
+    }
+
+    #[derive(Default)]
+    struct OldCluster {
+        nodes: HashMap<u32, OldNode>,
+    }
+
+    impl Cluster<u32, OldNode> for OldCluster {
+        fn remove(&mut self, key: &u32) -> Option<OldNode> {
+            self.nodes.remove(key)
+        }
+
+        fn contains_key(&self, key: &u32) -> bool {
+            self.nodes.contains_key(key)
+        }
+
+        fn get(&self, key: &u32) -> Option<&OldNode> {
+            self.nodes.get(key)
+        }
+
+        fn get_mut(&mut self, key: &u32) -> Option<&mut OldNode> {
+            self.nodes.get_mut(key)
+        }
+
+        fn new_key(&self) -> u32 {
+            self.nodes.len() as u32
+        }
+
+        fn add(&mut self, node: OldNode) -> u32 {
+            let key = self.new_key();
+            self.nodes.insert(key, node);
+            key
+        }
+    }
+
+    fn chain() -> OldCluster {
+        let mut cluster = OldCluster::default();
+        for _ in 0..3 {
+            cluster.add(OldNode { adj: Vec::new() });
+        }
+        cluster.add_edge(0, 1).unwrap();
+        cluster.add_doubly_edge(1, 2).unwrap();
+        cluster
+    }
+
+    #[test]
+    fn legacy_cluster_runs_the_new_algorithms_once_tracked() {
+        let tracked = Tracked::with_keys(chain(), [0, 1, 2, 2, 7]);
+        assert_eq!(crate::Cluster::len(&tracked), 3);
+        assert_eq!(Bfs::new(&tracked, 0).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(crate::Cluster::get_adj(&tracked, &1), Some(&[2][..]));
+    }
+
+    #[test]
+    fn tracked_cluster_records_the_added_and_removed_keys() {
+        let mut tracked = Tracked::new(OldCluster::default());
+        let key = crate::Cluster::add(&mut tracked, Legacy(OldNode { adj: Vec::new() }));
+        let other = crate::Cluster::add(&mut tracked, Legacy(OldNode { adj: Vec::new() }));
+        crate::Cluster::add_edge(&mut tracked, key, other).unwrap();
+        assert!(crate::Cluster::remove(&mut tracked, &key).is_some());
+        let keys: Vec<&u32> = crate::Cluster::keys(&tracked).collect();
+        assert_eq!(keys, vec![&other]);
+        assert!(tracked.inner().get_adj(&other).is_some_and(Vec::is_empty));
+    }
+}
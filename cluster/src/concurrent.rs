@@ -0,0 +1,651 @@
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::algo::flow::MaxFlow;
+use crate::algo::scored::MinScored;
+use crate::algo::shortest_path::ShortestPaths;
+use crate::algo::topo::CycleError;
+use crate::traversal::{KeyMap, VisitMap};
+use crate::{Cluster, Node, Weight, WeightedCluster, WeightedNode};
+
+/// Cluster shared between threads behind a RwLock.
+/// Readers can take a snapshot of the keys or edges and iterate over it while writers keep modifying the Cluster.
+/// A lock poisoned by a panicking thread is recovered, the Cluster being left as the panicking thread modified it.
+/// Each write lock taken starts a new generation, so the checked algorithms can release the read lock between their steps
+/// and still detect that the Cluster was modified under them.
+#[derive(Debug, Default)]
+pub struct ConcurrentCluster<C> {
+    inner: RwLock<C>,
+    generation: AtomicU64,
+}
+
+/// Error returned by a checked algorithm when the Cluster was locked for writing during its run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrentModification {
+    /// The generation of the Cluster when the run started.
+    pub expected: u64,
+    /// The generation of the Cluster when the modification was detected.
+    pub found: u64,
+}
+
+impl Display for ConcurrentModification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Cluster Error : The Cluster was modified during the run (generation {} expected, {} found).",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for ConcurrentModification {}
+
+impl<C> ConcurrentCluster<C> {
+    /// Wrap a Cluster to share it between threads.
+    /// # Parameter
+    /// - cluster - The Cluster to share.
+    /// # Return
+    /// The newly created ConcurrentCluster.
+    pub fn new(cluster: C) -> ConcurrentCluster<C> {
+        ConcurrentCluster {
+            inner: RwLock::new(cluster),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Lock the Cluster for reading, blocking the writers until the guard is dropped.
+    /// # Return
+    /// The guard giving access to the Cluster.
+    pub fn read(&self) -> RwLockReadGuard<'_, C> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lock the Cluster for writing, blocking the readers and the other writers until the guard is dropped.
+    /// The generation is incremented, whether the Cluster is actually modified through the guard or not.
+    /// # Return
+    /// The guard giving access to the Cluster.
+    pub fn write(&self) -> RwLockWriteGuard<'_, C> {
+        let guard = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        guard
+    }
+
+    /// Get the generation of the Cluster, the number of write locks taken on it so far.
+    /// # Return
+    /// The current generation.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Run an algorithm step by step, the read lock being taken for each step and released between them so writers are not starved.
+    /// The run is aborted as soon as a step sees a generation different from the one of the first step.
+    /// # Parameters
+    /// - state - The state of the algorithm, carried from one step to the next.
+    /// - step - The function making a step, returning Some result once the algorithm is done.
+    /// # Return
+    /// The result of the algorithm, a ConcurrentModification error if the Cluster was locked for writing during the run.
+    pub fn run_checked<S, R, F>(
+        &self,
+        mut state: S,
+        mut step: F,
+    ) -> Result<R, ConcurrentModification>
+    where
+        F: FnMut(&C, &mut S) -> Option<R>,
+    {
+        let mut expected = None;
+        loop {
+            let cluster = self.read();
+            let found = self.generation();
+            match expected {
+                None => expected = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(ConcurrentModification { expected, found });
+                }
+                Some(_) => {}
+            }
+            if let Some(result) = step(&cluster, &mut state) {
+                return Ok(result);
+            }
+        }
+    }
+
+    /// Traverse the Cluster breadth-first from a node, one node being expanded per read lock.
+    /// # Parameter
+    /// - start - The key of the node to start from.
+    /// # Return
+    /// The keys of the nodes reached, in the order of their discovery, empty if start is not in the Cluster,
+    /// a ConcurrentModification error if the Cluster was locked for writing during the traversal.
+    pub fn bfs_checked<K, N>(&self, start: K) -> Result<Vec<K>, ConcurrentModification>
+    where
+        K: Hash + Eq + Clone,
+        N: Node<K>,
+        C: Cluster<K, N>,
+    {
+        let visited = KeyMap::for_cluster(&*self.read());
+        self.bfs_checked_with(start, visited)
+    }
+
+    /// Traverse the Cluster breadth-first from a node, one node being expanded per read lock,
+    /// the depth of the visited keys being recorded in the given map as traversal::Bfs does.
+    /// # Parameters
+    /// - start - The key of the node to start from.
+    /// - visited - The map in which the depth of the visited keys is recorded, the keys already in it being skipped.
+    /// # Return
+    /// The keys of the nodes reached, in the order of their discovery, empty if start is not in the Cluster or already visited,
+    /// a ConcurrentModification error if the Cluster was locked for writing during the traversal.
+    pub fn bfs_checked_with<K, N, M>(
+        &self,
+        start: K,
+        visited: M,
+    ) -> Result<Vec<K>, ConcurrentModification>
+    where
+        K: PartialEq + Clone,
+        N: Node<K>,
+        C: Cluster<K, N>,
+        M: VisitMap<K, usize>,
+    {
+        let state = (VecDeque::from([(start, 0)]), visited, Vec::new());
+        self.run_checked(state, |cluster, (queue, visited, order)| {
+            let Some((key, depth)) = queue.pop_front() else {
+                return Some(std::mem::take(order));
+            };
+            if order.is_empty() {
+                if !cluster.contains_key(&key) || visited.contains_key(&key) {
+                    return Some(Vec::new());
+                }
+                visited.insert(key.clone(), 0);
+            }
+            for next in cluster.get_adj(&key).into_iter().flatten() {
+                if cluster.contains_key(next) && !visited.contains_key(next) {
+                    visited.insert(next.clone(), depth + 1);
+                    queue.push_back((next.clone(), depth + 1));
+                }
+            }
+            order.push(key);
+            None
+        })
+    }
+
+    /// Traverse the Cluster depth-first from a node, in the discovery order of traversal::Dfs, one edge being followed per read lock.
+    /// # Parameter
+    /// - start - The key of the node to start from.
+    /// # Return
+    /// The keys of the nodes reached, in the order of their discovery, empty if start is not in the Cluster,
+    /// a ConcurrentModification error if the Cluster was locked for writing during the traversal.
+    pub fn dfs_checked<K, N>(&self, start: K) -> Result<Vec<K>, ConcurrentModification>
+    where
+        K: Hash + Eq + Clone,
+        N: Node<K>,
+        C: Cluster<K, N>,
+    {
+        let visited = KeyMap::for_cluster(&*self.read());
+        self.dfs_checked_with(start, visited)
+    }
+
+    /// Traverse the Cluster depth-first from a node, one edge being followed per read lock,
+    /// the progress being recorded in the given map as traversal::Dfs does.
+    /// # Parameters
+    /// - start - The key of the node to start from.
+    /// - visited - The map in which the discovered keys are recorded along with true once their exploration is over, the keys already in it being skipped.
+    /// # Return
+    /// The keys of the nodes reached, in the order of their discovery, empty if start is not in the Cluster or already visited,
+    /// a ConcurrentModification error if the Cluster was locked for writing during the traversal.
+    pub fn dfs_checked_with<K, N, M>(
+        &self,
+        start: K,
+        visited: M,
+    ) -> Result<Vec<K>, ConcurrentModification>
+    where
+        K: PartialEq + Clone,
+        N: Node<K>,
+        C: Cluster<K, N>,
+        M: VisitMap<K, bool>,
+    {
+        let state: (Vec<(K, usize)>, M, Vec<K>) = (Vec::new(), visited, Vec::new());
+        self.run_checked(state, |cluster, (stack, visited, order)| {
+            if order.is_empty() {
+                if !cluster.contains_key(&start) || visited.contains_key(&start) {
+                    return Some(Vec::new());
+                }
+                visited.insert(start.clone(), false);
+                stack.push((start.clone(), 0));
+                order.push(start.clone());
+                return None;
+            }
+            let Some((key, index)) = stack.last_mut() else {
+                return Some(std::mem::take(order));
+            };
+            let Some(next) = cluster.get_adj(key).and_then(|adj| adj.get(*index)) else {
+                if let Some((key, _)) = stack.pop() {
+                    visited.insert(key, true);
+                }
+                return None;
+            };
+            *index += 1;
+            if cluster.contains_key(next) && !visited.contains_key(next) {
+                visited.insert(next.clone(), false);
+                stack.push((next.clone(), 0));
+                order.push(next.clone());
+            }
+            None
+        })
+    }
+
+    /// Compute the shortest paths from src to every reachable node with Dijkstra's algorithm, as shortest_path::dijkstra does,
+    /// one node being settled per read lock.
+    /// The weights of the edges must not be negative.
+    /// # Parameter
+    /// - src - The key of the node the paths start from.
+    /// # Return
+    /// The ShortestPaths from src, empty if src is not in the Cluster,
+    /// a ConcurrentModification error if the Cluster was locked for writing during the search.
+    pub fn dijkstra_checked<K, N>(
+        &self,
+        src: K,
+    ) -> Result<ShortestPaths<K, N::Weight>, ConcurrentModification>
+    where
+        K: Hash + Eq + Clone,
+        N: WeightedNode<K>,
+        C: WeightedCluster<K, N>,
+    {
+        let frontier = BinaryHeap::from([MinScored(N::Weight::zero(), src.clone())]);
+        let settled = KeyMap::for_cluster(&*self.read());
+        let state = (frontier, settled, HashMap::new(), HashMap::new());
+        self.run_checked(
+            state,
+            |cluster, (frontier, settled, distances, predecessors)| {
+                let done = |distances: &mut HashMap<K, N::Weight>,
+                            predecessors: &mut HashMap<K, K>| {
+                    ShortestPaths {
+                        source: src.clone(),
+                        distances: std::mem::take(distances),
+                        predecessors: std::mem::take(predecessors),
+                    }
+                };
+                if distances.is_empty() {
+                    if !cluster.contains_key(&src) {
+                        return Some(done(distances, predecessors));
+                    }
+                    distances.insert(src.clone(), N::Weight::zero());
+                }
+                let (distance, key) = loop {
+                    let Some(MinScored(distance, key)) = frontier.pop() else {
+                        return Some(done(distances, predecessors));
+                    };
+                    if settled.insert(key.clone(), ()).is_none() {
+                        break (distance, key);
+                    }
+                };
+                let node = cluster.get(&key)?;
+                for next in node.adj() {
+                    if settled.contains_key(next) || !cluster.contains_key(next) {
+                        continue;
+                    }
+                    let candidate = distance + node.weight(next);
+                    if distances
+                        .get(next)
+                        .is_none_or(|current| candidate < *current)
+                    {
+                        distances.insert(next.clone(), candidate);
+                        predecessors.insert(next.clone(), key.clone());
+                        frontier.push(MinScored(candidate, next.clone()));
+                    }
+                }
+                None
+            },
+        )
+    }
+
+    /// Order the nodes of the Cluster so that every edge goes from a node to a later one with Kahn's algorithm, as topo::toposort does,
+    /// the in-degrees being counted under a first read lock then one node being taken out per read lock.
+    /// # Return
+    /// The keys in topological order or a CycleError designing a node on a cycle,
+    /// a ConcurrentModification error if the Cluster was locked for writing during the run.
+    pub fn toposort_checked<K, N>(
+        &self,
+    ) -> Result<Result<Vec<K>, CycleError<K>>, ConcurrentModification>
+    where
+        K: Hash + Eq + Clone,
+        N: Node<K>,
+        C: Cluster<K, N>,
+    {
+        let state: (HashMap<K, usize>, Vec<K>, Vec<K>) = (HashMap::new(), Vec::new(), Vec::new());
+        self.run_checked(state, |cluster, (in_degrees, ready, order)| {
+            if in_degrees.is_empty() {
+                if cluster.is_empty() {
+                    return Some(Ok(Vec::new()));
+                }
+                for key in cluster.keys() {
+                    in_degrees.entry(key.clone()).or_insert(0);
+                    for dst in cluster.get_adj(key).into_iter().flatten() {
+                        if cluster.contains_key(dst) {
+                            *in_degrees.entry(dst.clone()).or_insert(0) += 1;
+                        }
+                    }
+                }
+                ready.extend(cluster.keys().filter(|key| in_degrees[*key] == 0).cloned());
+                return None;
+            }
+            let Some(key) = ready.pop() else {
+                if order.len() < in_degrees.len() {
+                    return Some(Err(CycleError {
+                        key: node_on_cycle(cluster, in_degrees),
+                    }));
+                }
+                return Some(Ok(std::mem::take(order)));
+            };
+            for dst in cluster.get_adj(&key).into_iter().flatten() {
+                if let Some(degree) = in_degrees.get_mut(dst) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        ready.push(dst.clone());
+                    }
+                }
+            }
+            order.push(key);
+            None
+        })
+    }
+
+    /// Compute a maximum flow from a source to a sink with the Edmonds–Karp algorithm, the weight of each edge being its capacity,
+    /// one augmenting path being searched per read lock.
+    /// In an undirected Cluster, each edge can carry its capacity in both directions, only the net flow being reported.
+    /// Edges with a negative capacity, self-loops and edges toward keys not in the Cluster are ignored.
+    /// # Parameters
+    /// - source - The key of the node the flow leaves.
+    /// - sink - The key of the node the flow reaches.
+    /// # Return
+    /// An Option containing the MaxFlow, None if the source or the sink is not in the Cluster,
+    /// a ConcurrentModification error if the Cluster was locked for writing during the run.
+    pub fn max_flow_checked<K, N>(
+        &self,
+        source: K,
+        sink: K,
+    ) -> Result<Option<MaxFlow<K, N::Weight>>, ConcurrentModification>
+    where
+        K: Hash + Eq + Clone,
+        N: WeightedNode<K>,
+        C: WeightedCluster<K, N>,
+    {
+        let state = (N::Weight::zero(), HashMap::new(), HashMap::new());
+        self.run_checked(state, |cluster, (value, flows, backward)| {
+            if !cluster.contains_key(&source) || !cluster.contains_key(&sink) {
+                return Some(None);
+            }
+            let path = (source != sink)
+                .then(|| augmenting_path(cluster, flows, backward, &source, &sink))
+                .flatten();
+            let Some((path, bottleneck)) = path else {
+                let mut flows = std::mem::take(flows);
+                flows.retain(|_, carried| *carried > N::Weight::zero());
+                return Some(Some(MaxFlow {
+                    value: *value,
+                    flows,
+                }));
+            };
+            for pair in path.windows(2) {
+                let (src, dst) = (&pair[0], &pair[1]);
+                let forward = flows
+                    .entry((src.clone(), dst.clone()))
+                    .or_insert(N::Weight::zero());
+                *forward = *forward + bottleneck;
+                let reverse = flows
+                    .entry((dst.clone(), src.clone()))
+                    .or_insert(N::Weight::zero());
+                *reverse = *reverse - bottleneck;
+                let predecessors: &mut Vec<K> = backward.entry(dst.clone()).or_default();
+                if !predecessors.contains(src) {
+                    predecessors.push(src.clone());
+                }
+            }
+            *value = *value + bottleneck;
+            None
+        })
+    }
+
+    /// Unwrap the shared Cluster.
+    /// # Return
+    /// The Cluster that was shared.
+    pub fn into_inner(self) -> C {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Capture the keys of the Cluster, the read lock being held only while they are copied.
+    /// # Return
+    /// The keys of the nodes of the Cluster at the time of the call.
+    pub fn snapshot_keys<K, N>(&self) -> Vec<K>
+    where
+        K: PartialEq + Clone,
+        N: Node<K>,
+        C: Cluster<K, N>,
+    {
+        self.read().keys().cloned().collect()
+    }
+
+    /// Capture the edges of the Cluster, the read lock being held only while they are copied.
+    /// The keys and the edges are read under the same lock, so no edge of the snapshot is torn by a concurrent write.
+    /// In an undirected Cluster, each edge is reported once.
+    /// # Return
+    /// The (source, destination) pairs of the edges of the Cluster at the time of the call.
+    pub fn snapshot_edges<K, N>(&self) -> Vec<(K, K)>
+    where
+        K: Hash + Eq + Clone,
+        N: Node<K>,
+        C: Cluster<K, N>,
+    {
+        let cluster = self.read();
+        let directed = cluster.is_directed();
+        let mut reported = HashSet::new();
+        let mut edges = Vec::new();
+        for src in cluster.keys() {
+            for dst in cluster.get_adj(src).into_iter().flatten() {
+                if !directed && (reported.contains(&(dst, src)) || !reported.insert((src, dst))) {
+                    continue;
+                }
+                edges.push((src.clone(), dst.clone()));
+            }
+        }
+        edges
+    }
+}
+
+/// Find a key on a cycle among the nodes Kahn's algorithm could not take out, each of them having a predecessor among them.
+fn node_on_cycle<K, N, C>(cluster: &C, in_degrees: &HashMap<K, usize>) -> K
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let mut predecessor = HashMap::new();
+    for (key, degree) in in_degrees {
+        if *degree == 0 {
+            continue;
+        }
+        for dst in cluster.get_adj(key).into_iter().flatten() {
+            predecessor.insert(dst, key);
+        }
+    }
+    let mut seen = HashSet::new();
+    let mut key = in_degrees
+        .iter()
+        .find(|(_, degree)| **degree > 0)
+        .map(|(key, _)| key)
+        .unwrap();
+    while seen.insert(key) {
+        key = predecessor[key];
+    }
+    key.clone()
+}
+
+/// Search a shortest path from source to sink in the residual network of the flow, with a breadth-first search.
+/// The residual capacity of an edge is its capacity minus the net flow going through it,
+/// the edges carrying flow backward being listed in backward so they can be cancelled.
+fn augmenting_path<K, N, C>(
+    cluster: &C,
+    flows: &HashMap<(K, K), N::Weight>,
+    backward: &HashMap<K, Vec<K>>,
+    source: &K,
+    sink: &K,
+) -> Option<(Vec<K>, N::Weight)>
+where
+    K: Hash + Eq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    let residual = |src: &K, dst: &K| {
+        let capacity = cluster
+            .get(src)
+            .filter(|node| src != dst && node.adj().contains(dst))
+            .map(|node| node.weight(dst))
+            .filter(|capacity| *capacity > N::Weight::zero())
+            .unwrap_or(N::Weight::zero());
+        let carried = flows
+            .get(&(src.clone(), dst.clone()))
+            .copied()
+            .unwrap_or(N::Weight::zero());
+        capacity - carried
+    };
+    let mut parents = HashMap::from([(source.clone(), None)]);
+    let mut queue = VecDeque::from([source.clone()]);
+    while let Some(key) = queue.pop_front() {
+        let forward = cluster.get_adj(&key).into_iter().flatten();
+        let cancelled = backward.get(&key).into_iter().flatten();
+        for next in forward.chain(cancelled) {
+            if parents.contains_key(next)
+                || !cluster.contains_key(next)
+                || residual(&key, next) <= N::Weight::zero()
+            {
+                continue;
+            }
+            parents.insert(next.clone(), Some(key.clone()));
+            if next == sink {
+                let mut path = vec![sink.clone()];
+                while let Some(Some(parent)) = parents.get(path.last()?) {
+                    path.push(parent.clone());
+                }
+                path.reverse();
+                let bottleneck = path
+                    .windows(2)
+                    .map(|pair| residual(&pair[0], &pair[1]))
+                    .reduce(|min, capacity| if capacity < min { capacity } else { min })?;
+                return Some((path, bottleneck));
+            }
+            queue.push_back(next.clone());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::flow::max_flow;
+    use crate::algo::shortest_path::dijkstra;
+    use crate::hash_cluster::HashCluster;
+    use crate::{BasicNode, UndirectedHashCluster, WeightedBasicNode};
+
+    fn network(edges: &[(u32, u32, i64)]) -> HashCluster<u32, WeightedBasicNode<u32, i64>> {
+        let mut cluster = HashCluster::new();
+        for (src, dst, _) in edges {
+            cluster.insert(*src, WeightedBasicNode::new(()));
+            cluster.insert(*dst, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in edges {
+            cluster.add_weighted_edge(*src, *dst, *weight).unwrap();
+        }
+        cluster
+    }
+
+    #[test]
+    fn checked_traversals_follow_the_unchecked_ones() {
+        let cluster: HashCluster<u32, BasicNode<u32>> = [(0, 1), (0, 2), (1, 3), (2, 3), (3, 0)]
+            .into_iter()
+            .collect();
+        let shared = ConcurrentCluster::new(cluster);
+        assert_eq!(shared.bfs_checked(0), Ok(vec![0, 1, 2, 3]));
+        assert_eq!(shared.dfs_checked(0), Ok(vec![0, 1, 3, 2]));
+        assert_eq!(shared.bfs_checked(7), Ok(Vec::new()));
+        let mut visited = HashMap::from([(3, true)]);
+        assert_eq!(shared.dfs_checked_with(0, &mut visited), Ok(vec![0, 1, 2]));
+        assert_eq!(visited.len(), 4);
+    }
+
+    #[test]
+    fn checked_dijkstra_finds_the_same_distances() {
+        let cluster = network(&[(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 7)]);
+        let expected = dijkstra(&cluster, &0);
+        let shared = ConcurrentCluster::new(cluster);
+        let paths = shared.dijkstra_checked(0).unwrap();
+        assert_eq!(paths.distances, expected.distances);
+        assert_eq!(paths.distance(&3), Some(4));
+        assert!(shared.dijkstra_checked(9).unwrap().distances.is_empty());
+    }
+
+    #[test]
+    fn checked_toposort_orders_the_edges_or_finds_a_cycle() {
+        let cluster: HashCluster<u32, BasicNode<u32>> = [(0, 1), (0, 2), (1, 3), (2, 3), (4, 0)]
+            .into_iter()
+            .collect();
+        let shared = ConcurrentCluster::new(cluster);
+        let order = shared.toposort_checked().unwrap().unwrap();
+        let position = |key: u32| order.iter().position(|k| *k == key).unwrap();
+        assert_eq!(order.len(), 5);
+        for (src, dst) in shared.snapshot_edges() {
+            assert!(position(src) < position(dst));
+        }
+        shared.write().add_edge(3, 4).unwrap();
+        let cycle = shared.toposort_checked().unwrap().unwrap_err();
+        assert!([0, 1, 2, 3, 4].contains(&cycle.key));
+        shared.write().remove_edge(&3, &4).unwrap();
+        shared.write().add_edge(3, 1).unwrap();
+        let cycle = shared.toposort_checked().unwrap().unwrap_err();
+        assert!([1, 3].contains(&cycle.key));
+    }
+
+    #[test]
+    fn checked_max_flow_reaches_the_same_value() {
+        let edges = [
+            (0, 1, 3),
+            (0, 2, 2),
+            (1, 2, 1),
+            (1, 3, 2),
+            (2, 3, 3),
+            (3, 1, 1),
+        ];
+        let cluster = network(&edges);
+        let expected = max_flow(&cluster, &0, &3).unwrap();
+        let shared = ConcurrentCluster::new(cluster);
+        let flow = shared.max_flow_checked(0, 3).unwrap().unwrap();
+        assert_eq!(flow.value, expected.value);
+        assert_eq!(flow.value, 5);
+        for ((src, dst), carried) in &flow.flows {
+            let capacity = edges
+                .iter()
+                .find(|(s, d, _)| s == src && d == dst)
+                .map(|(_, _, capacity)| *capacity);
+            assert!(capacity.is_some_and(|capacity| *carried <= capacity));
+        }
+        assert_eq!(shared.max_flow_checked(0, 9).unwrap(), None);
+    }
+
+    #[test]
+    fn checked_max_flow_uses_undirected_edges_both_ways() {
+        let mut cluster: UndirectedHashCluster<u32, WeightedBasicNode<u32, i64>> =
+            UndirectedHashCluster::new();
+        for key in 0..3 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        cluster.add_weighted_edge(1, 0, 5).unwrap();
+        cluster.add_weighted_edge(2, 1, 2).unwrap();
+        let shared = ConcurrentCluster::new(cluster);
+        let flow = shared.max_flow_checked(0, 2).unwrap().unwrap();
+        assert_eq!(flow.value, 2);
+        assert_eq!(flow.flows.get(&(0, 1)), Some(&2));
+    }
+}
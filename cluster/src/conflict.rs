@@ -0,0 +1,116 @@
+use crate::{ClusterError, Result};
+
+/// Policy applied when a node is inserted at a key already used in the Cluster.
+#[derive(Default)]
+pub enum KeyConflict<N> {
+    /// Refuse the insertion with an error, keeping the stored node.
+    Error,
+    /// Replace the stored node by the inserted one.
+    #[default]
+    Overwrite,
+    /// Ignore the inserted node, keeping the stored one.
+    Skip,
+    /// Merge the inserted node into the stored one with the given function.
+    Merge(fn(&mut N, N)),
+}
+
+impl<N> KeyConflict<N> {
+    /// Resolve the conflict between the stored node and the inserted one.
+    /// # Parameters
+    /// - stored - The node already stored at the key.
+    /// - inserted - The node being inserted at the key.
+    /// # Return
+    /// An Option containing the node no longer stored after the resolution, an error if the policy refuses the insertion.
+    pub(crate) fn resolve(&self, stored: &mut N, inserted: N) -> Result<Option<N>> {
+        match self {
+            KeyConflict::Error => {
+                Err(ClusterError::detailled("A node is already stored at the inserted key.").into())
+            }
+            KeyConflict::Overwrite => Ok(Some(std::mem::replace(stored, inserted))),
+            KeyConflict::Skip => Ok(Some(inserted)),
+            KeyConflict::Merge(merge) => {
+                merge(stored, inserted);
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<N> Clone for KeyConflict<N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N> Copy for KeyConflict<N> {}
+
+impl<N> std::fmt::Debug for KeyConflict<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyConflict::Error => write!(f, "Error"),
+            KeyConflict::Overwrite => write!(f, "Overwrite"),
+            KeyConflict::Skip => write!(f, "Skip"),
+            KeyConflict::Merge(_) => write!(f, "Merge"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, Cluster, HashCluster};
+
+    fn stored() -> HashCluster<u32, BasicNode<u32, u32>> {
+        let mut cluster = HashCluster::new();
+        cluster.insert(0, BasicNode::new(1));
+        cluster
+    }
+
+    fn merge(stored: &mut BasicNode<u32, u32>, inserted: BasicNode<u32, u32>) {
+        *stored.value_mut() += *inserted.value();
+    }
+
+    #[test]
+    fn each_policy_resolves_a_used_key() {
+        let mut cluster = stored();
+        assert!(cluster
+            .insert_with(0, BasicNode::new(2), KeyConflict::Error)
+            .is_err());
+        assert_eq!(cluster.get(&0).map(|node| *node.value()), Some(1));
+        let skipped = cluster
+            .insert_with(0, BasicNode::new(3), KeyConflict::Skip)
+            .unwrap();
+        assert_eq!(skipped.map(|node| *node.value()), Some(3));
+        assert_eq!(cluster.get(&0).map(|node| *node.value()), Some(1));
+        let replaced = cluster
+            .insert_with(0, BasicNode::new(4), KeyConflict::Overwrite)
+            .unwrap();
+        assert_eq!(replaced.map(|node| *node.value()), Some(1));
+        assert!(cluster
+            .insert_with(0, BasicNode::new(5), KeyConflict::Merge(merge))
+            .unwrap()
+            .is_none());
+        assert_eq!(cluster.get(&0).map(|node| *node.value()), Some(9));
+    }
+
+    #[test]
+    fn a_free_key_is_inserted_whatever_the_policy() {
+        let mut cluster = stored();
+        assert!(cluster
+            .insert_with(1, BasicNode::new(2), KeyConflict::Error)
+            .unwrap()
+            .is_none());
+        assert_eq!(cluster.len(), 2);
+    }
+
+    #[test]
+    fn try_insert_follows_the_policy_of_the_cluster() {
+        let mut cluster = stored();
+        assert!(matches!(cluster.conflict_policy(), KeyConflict::Overwrite));
+        cluster.set_conflict_policy(KeyConflict::Merge(merge));
+        cluster.try_insert(0, BasicNode::new(2)).unwrap();
+        assert_eq!(cluster.get(&0).map(|node| *node.value()), Some(3));
+        cluster.set_conflict_policy(KeyConflict::Error);
+        assert!(cluster.try_insert(0, BasicNode::new(2)).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+/// Trait implemented by the markers describing the direction of the edges of a Cluster.
+pub trait Direction {
+    /// True if the edges of the Cluster are directed, false otherwise.
+    const DIRECTED: bool;
+}
+
+/// Marker for Clusters whose edges go from a source node to a destination node only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Directed;
+
+/// Marker for Clusters whose edges link both of their nodes to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Undirected;
+
+impl Direction for Directed {
+    const DIRECTED: bool = true;
+}
+
+impl Direction for Undirected {
+    const DIRECTED: bool = false;
+}
@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+use crate::analysis::diff;
+use crate::export::Grouping;
+use crate::{Cluster, Node};
+
+/// Color of the nodes and edges added between two versions of a Cluster.
+const ADDED_COLOR: &str = "green";
+/// Color of the nodes and edges removed between two versions of a Cluster.
+const REMOVED_COLOR: &str = "red";
+
+/// Render a Cluster in the DOT language of Graphviz, each node being named after the Debug output of its key.
+/// # Parameter
+/// - cluster - The Cluster to render.
+/// # Return
+/// The DOT description of the Cluster.
+pub fn to_dot<K, N, C>(cluster: &C) -> String
+where
+    K: Hash + Eq + Clone + Debug,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let mut dot = DotWriter::new(cluster.is_directed());
+    for key in cluster.keys() {
+        dot.node(key, None);
+    }
+    for (src, dst) in undirected_once(edges(cluster), cluster.is_directed()) {
+        dot.edge(&src, &dst, None);
+    }
+    dot.finish()
+}
+
+/// Render a Cluster in the DOT language of Graphviz, the nodes of each group being boxed in a labelled cluster subgraph.
+/// # Parameters
+/// - cluster - The Cluster to render.
+/// - grouping - The groups of the nodes, keys not in the Cluster being ignored.
+/// # Return
+/// The DOT description of the Cluster.
+pub fn to_dot_grouped<K, N, C>(cluster: &C, grouping: &Grouping<K>) -> String
+where
+    K: Hash + Eq + Clone + Debug,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let mut dot = DotWriter::new(cluster.is_directed());
+    let (members, loose) = grouping.split(cluster.keys());
+    for key in loose {
+        dot.node(key, None);
+    }
+    for (index, (name, keys)) in grouping.groups().zip(members).enumerate() {
+        if !keys.is_empty() {
+            dot.group(index, name, &keys);
+        }
+    }
+    for (src, dst) in undirected_once(edges(cluster), cluster.is_directed()) {
+        dot.edge(&src, &dst, None);
+    }
+    dot.finish()
+}
+
+/// Render two versions of a Cluster as a single DOT graph highlighting their differences.
+/// Added nodes and edges are drawn in green, removed ones in dashed red, the others being left as is.
+/// Graphviz turns the result into an SVG with `dot -Tsvg`.
+/// # Parameters
+/// - old - The previous version of the Cluster.
+/// - new - The current version of the Cluster.
+/// # Return
+/// The DOT description of the union of both Clusters.
+pub fn diff_to_dot<K, N, C, M, D>(old: &C, new: &D) -> String
+where
+    K: Hash + Eq + Clone + Debug,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: Node<K>,
+    D: Cluster<K, M>,
+{
+    let directed = old.is_directed() || new.is_directed();
+    let changes = diff(old, new);
+    let added_nodes: HashSet<&K> = changes.added_nodes.iter().collect();
+    let added_edges: HashSet<&(K, K)> = changes.added_edges.iter().collect();
+    let mut dot = DotWriter::new(directed);
+    for key in new.keys() {
+        dot.node(key, added_nodes.contains(key).then_some(ADDED_COLOR));
+    }
+    for key in &changes.removed_nodes {
+        dot.node(key, Some(REMOVED_COLOR));
+    }
+    for edge in undirected_once(edges(new), directed) {
+        let added = added_edges.contains(&edge)
+            || (!directed && added_edges.contains(&(edge.1.clone(), edge.0.clone())));
+        let color = added.then_some(ADDED_COLOR);
+        dot.edge(&edge.0, &edge.1, color);
+    }
+    for (src, dst) in &changes.removed_edges {
+        dot.edge(src, dst, Some(REMOVED_COLOR));
+    }
+    dot.finish()
+}
+
+/// Get the (source, destination) pairs of the edges of a Cluster, edges toward keys not in the Cluster being ignored.
+pub(super) fn edges<K, N, C>(cluster: &C) -> Vec<(K, K)>
+where
+    K: Clone + PartialEq,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    cluster
+        .keys()
+        .flat_map(|src| {
+            cluster
+                .get_adj(src)
+                .into_iter()
+                .flatten()
+                .filter(|dst| cluster.contains_key(dst))
+                .map(move |dst| (src.clone(), dst.clone()))
+        })
+        .collect()
+}
+
+/// Keep a single direction of each edge of an undirected Cluster.
+pub(super) fn undirected_once<K: Hash + Eq + Clone>(
+    edges: Vec<(K, K)>,
+    directed: bool,
+) -> Vec<(K, K)> {
+    if directed {
+        return edges;
+    }
+    let mut reported = HashSet::new();
+    edges
+        .into_iter()
+        .filter(|(src, dst)| {
+            let once = !reported.contains(&(dst.clone(), src.clone()));
+            reported.insert((src.clone(), dst.clone()));
+            once
+        })
+        .collect()
+}
+
+/// Builder of the text of a DOT graph.
+struct DotWriter {
+    out: String,
+    arrow: &'static str,
+}
+
+impl DotWriter {
+    fn new(directed: bool) -> DotWriter {
+        DotWriter {
+            out: String::from(if directed { "digraph {\n" } else { "graph {\n" }),
+            arrow: if directed { "->" } else { "--" },
+        }
+    }
+
+    /// Write a node statement, dashed if drawn in the removed color.
+    fn node<K: Debug>(&mut self, key: &K, color: Option<&str>) {
+        let _ = write!(self.out, "    {}", dot_id(key));
+        self.attributes(color);
+    }
+
+    /// Write a cluster subgraph drawing a labelled box around the given nodes.
+    fn group<K: Debug>(&mut self, index: usize, name: &str, keys: &[&K]) {
+        let _ = writeln!(self.out, "    subgraph \"cluster_{index}\" {{");
+        let _ = writeln!(self.out, "        label={};", quote(name));
+        for key in keys {
+            let _ = writeln!(self.out, "        {};", dot_id(key));
+        }
+        self.out.push_str("    }\n");
+    }
+
+    /// Write an edge statement, dashed if drawn in the removed color.
+    fn edge<K: Debug>(&mut self, src: &K, dst: &K, color: Option<&str>) {
+        let _ = write!(
+            self.out,
+            "    {} {} {}",
+            dot_id(src),
+            self.arrow,
+            dot_id(dst)
+        );
+        self.attributes(color);
+    }
+
+    fn attributes(&mut self, color: Option<&str>) {
+        match color {
+            Some(REMOVED_COLOR) => {
+                let _ = write!(self.out, " [color={REMOVED_COLOR}, style=dashed]");
+            }
+            Some(color) => {
+                let _ = write!(self.out, " [color={color}]");
+            }
+            None => {}
+        }
+        self.out.push_str(";\n");
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push_str("}\n");
+        self.out
+    }
+}
+
+/// Quote the Debug output of a key as a DOT identifier.
+fn dot_id<K: Debug>(key: &K) -> String {
+    quote(&format!("{:?}", key))
+}
+
+/// Quote a text as a DOT string.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, Directed, Direction, HashCluster, Undirected};
+
+    /// Read back the node and edge statements of a DOT graph written for u32 keys, skipping those drawn as removed.
+    fn parse<D: Direction>(dot: &str) -> HashCluster<u32, BasicNode<u32>, D> {
+        let mut cluster = HashCluster::new();
+        let mut edges = Vec::new();
+        for line in dot.lines().map(str::trim) {
+            if !line.starts_with('"') || line.contains(REMOVED_COLOR) {
+                continue;
+            }
+            let statement = line.trim_end_matches(';').split(" [").next().unwrap();
+            let ids: Vec<u32> = statement
+                .split([' ', '-', '>'])
+                .filter(|id| !id.is_empty())
+                .map(|id| id.trim_matches('"').parse().unwrap())
+                .collect();
+            match ids[..] {
+                [key] => {
+                    cluster.insert(key, BasicNode::new(()));
+                }
+                [src, dst] => edges.push((src, dst)),
+                _ => panic!("unexpected statement {line}"),
+            }
+        }
+        for (src, dst) in edges {
+            cluster.add_edge(src, dst).unwrap();
+        }
+        cluster
+    }
+
+    fn directed() -> HashCluster<u32, BasicNode<u32>> {
+        let mut cluster: HashCluster<u32, BasicNode<u32>> =
+            [(0, 1), (1, 2), (2, 0), (2, 2)].into_iter().collect();
+        cluster.insert(3, BasicNode::new(()));
+        cluster
+    }
+
+    #[test]
+    fn directed_clusters_round_trip() {
+        let cluster = directed();
+        let dot = to_dot(&cluster);
+        assert!(dot.starts_with("digraph {\n") && dot.contains("    \"0\" -> \"1\";\n"));
+        crate::assert_graph_eq!(parse::<Directed>(&dot), cluster);
+    }
+
+    #[test]
+    fn undirected_clusters_round_trip() {
+        let mut cluster: HashCluster<u32, BasicNode<u32>, Undirected> = HashCluster::new();
+        for key in 0..4 {
+            cluster.insert(key, BasicNode::new(()));
+        }
+        for (src, dst) in [(0, 1), (1, 2), (3, 1)] {
+            cluster.add_edge(src, dst).unwrap();
+        }
+        let dot = to_dot(&cluster);
+        assert!(dot.starts_with("graph {\n"));
+        assert_eq!(dot.matches(" -- ").count(), 3);
+        crate::assert_graph_eq!(parse::<Undirected>(&dot), cluster);
+    }
+
+    #[test]
+    fn grouped_clusters_round_trip() {
+        let cluster = directed();
+        let mut grouping = Grouping::new();
+        grouping.assign(0, "cycle");
+        grouping.assign(1, "cycle");
+        let dot = to_dot_grouped(&cluster, &grouping);
+        assert!(dot.contains("    subgraph \"cluster_0\" {\n        label=\"cycle\";\n"));
+        crate::assert_graph_eq!(parse::<Directed>(&dot), cluster);
+    }
+
+    #[test]
+    fn diffs_keep_the_new_version_apart_from_the_removed_parts() {
+        let old = directed();
+        let mut new: HashCluster<u32, BasicNode<u32>> =
+            [(0, 1), (1, 2), (1, 4)].into_iter().collect();
+        new.insert(3, BasicNode::new(()));
+        let dot = diff_to_dot(&old, &new);
+        assert!(dot.contains("    \"2\" -> \"0\" [color=red, style=dashed];\n"));
+        assert!(dot.contains("    \"4\" [color=green];\n"));
+        crate::assert_graph_eq!(parse::<Directed>(&dot), new);
+    }
+}
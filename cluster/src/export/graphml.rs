@@ -0,0 +1,124 @@
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+use crate::export::dot::{edges, undirected_once};
+use crate::export::Grouping;
+use crate::{Cluster, Node};
+
+/// Render a Cluster in GraphML, each node being identified by the Debug output of its key.
+/// # Parameter
+/// - cluster - The Cluster to render.
+/// # Return
+/// The GraphML document describing the Cluster.
+pub fn to_graphml<K, N, C>(cluster: &C) -> String
+where
+    K: Hash + Eq + Clone + Debug,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    to_graphml_grouped(cluster, &Grouping::new())
+}
+
+/// Render a Cluster in GraphML, the nodes of each group being nested in the graph of a labelled group node,
+/// which yEd and Gephi draw as a box around them.
+/// The group nodes are identified by `group<index>`, the index following the order of Grouping::groups.
+/// # Parameters
+/// - cluster - The Cluster to render.
+/// - grouping - The groups of the nodes, keys not in the Cluster being ignored.
+/// # Return
+/// The GraphML document describing the Cluster.
+pub fn to_graphml_grouped<K, N, C>(cluster: &C, grouping: &Grouping<K>) -> String
+where
+    K: Hash + Eq + Clone + Debug,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let directed = cluster.is_directed();
+    let edge_default = if directed { "directed" } else { "undirected" };
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+    let _ = writeln!(out, "  <graph id=\"G\" edgedefault=\"{edge_default}\">");
+    let (members, loose) = grouping.split(cluster.keys());
+    for key in loose {
+        let _ = writeln!(out, "    <node id={}/>", xml_id(key));
+    }
+    for (index, (name, keys)) in grouping.groups().zip(members).enumerate() {
+        if keys.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "    <node id=\"group{index}\">");
+        let _ = writeln!(out, "      <data key=\"label\">{}</data>", escape_xml(name));
+        let _ = writeln!(
+            out,
+            "      <graph id=\"group{index}:\" edgedefault=\"{edge_default}\">"
+        );
+        for key in keys {
+            let _ = writeln!(out, "        <node id={}/>", xml_id(key));
+        }
+        out.push_str("      </graph>\n    </node>\n");
+    }
+    for (src, dst) in undirected_once(edges(cluster), directed) {
+        let _ = writeln!(
+            out,
+            "    <edge source={} target={}/>",
+            xml_id(&src),
+            xml_id(&dst)
+        );
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Quote the Debug output of a key as an XML attribute value.
+fn xml_id<K: Debug>(key: &K) -> String {
+    format!("\"{}\"", escape_xml(&format!("{:?}", key)))
+}
+
+/// Escape the characters of a text that are special in XML.
+pub(super) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster, UndirectedHashCluster};
+
+    #[test]
+    fn groups_are_nested_graphs() {
+        let cluster: HashCluster<u32, BasicNode<u32>> = [(0, 1), (1, 2)].into_iter().collect();
+        let mut grouping = Grouping::new();
+        grouping.assign(0, "core & io");
+        grouping.assign(1, "core & io");
+        grouping.assign(9, "empty");
+        let graphml = to_graphml_grouped(&cluster, &grouping);
+        assert!(graphml.contains("<graph id=\"G\" edgedefault=\"directed\">"));
+        assert!(graphml.contains("<data key=\"label\">core &amp; io</data>"));
+        let nested = graphml.split("<graph id=\"group0:\"").nth(1).unwrap();
+        let nested = nested.split("</graph>").next().unwrap();
+        assert!(nested.contains("<node id=\"0\"/>") && nested.contains("<node id=\"1\"/>"));
+        assert!(!nested.contains("<node id=\"2\"/>"));
+        assert!(!graphml.contains("group1"));
+        assert!(graphml.contains("<edge source=\"1\" target=\"2\"/>"));
+    }
+
+    #[test]
+    fn undirected_edges_are_written_once() {
+        let mut cluster: UndirectedHashCluster<String, BasicNode<String>> =
+            UndirectedHashCluster::new();
+        cluster.insert(String::from("a"), BasicNode::new(()));
+        cluster.insert(String::from("<b>"), BasicNode::new(()));
+        cluster
+            .add_edge(String::from("a"), String::from("<b>"))
+            .unwrap();
+        let graphml = to_graphml(&cluster);
+        assert!(graphml.contains("edgedefault=\"undirected\""));
+        assert!(graphml.contains("<node id=\"&quot;&lt;b&gt;&quot;\"/>"));
+        assert_eq!(graphml.matches("<edge ").count(), 1);
+    }
+}
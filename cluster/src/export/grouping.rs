@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Assignment of the nodes of a Cluster to named groups, rendered as boxes around their members by the exporters.
+/// A node belongs to at most one group, the nodes without a group being drawn outside of every box.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grouping<K: Hash + Eq> {
+    names: Vec<String>,
+    groups: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq> Grouping<K> {
+    /// Create a grouping without any group.
+    /// # Return
+    /// The newly created Grouping.
+    pub fn new() -> Grouping<K> {
+        Grouping {
+            names: Vec::new(),
+            groups: HashMap::new(),
+        }
+    }
+
+    /// Put a node in a group, creating the group if needed and taking the node out of its previous group.
+    /// # Parameters
+    /// - key - The key of the node.
+    /// - group - The name of the group.
+    pub fn assign(&mut self, key: K, group: &str) {
+        let index = match self.names.iter().position(|name| name == group) {
+            Some(index) => index,
+            None => {
+                self.names.push(group.to_string());
+                self.names.len() - 1
+            }
+        };
+        self.groups.insert(key, index);
+    }
+
+    /// Take a node out of its group.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the name of the group the node belonged to, None if it had no group.
+    pub fn unassign(&mut self, key: &K) -> Option<&str> {
+        let index = self.groups.remove(key)?;
+        Some(&self.names[index])
+    }
+
+    /// Get the group of a node.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the name of the group, None if the node has no group.
+    pub fn group_of(&self, key: &K) -> Option<&str> {
+        self.groups
+            .get(key)
+            .map(|index| self.names[*index].as_str())
+    }
+
+    /// Get the names of the groups, in the order they were created.
+    /// # Return
+    /// An iterator over the names of the groups.
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Get the index of the group of a node, in the order given by groups().
+    pub(super) fn group_index(&self, key: &K) -> Option<usize> {
+        self.groups.get(key).copied()
+    }
+
+    /// Sort keys by group, the members of each group in the order given by groups() and the keys without a group apart.
+    pub(super) fn split<'k>(
+        &self,
+        keys: impl Iterator<Item = &'k K>,
+    ) -> (Vec<Vec<&'k K>>, Vec<&'k K>)
+    where
+        K: 'k,
+    {
+        let mut members: Vec<Vec<&K>> = self.names.iter().map(|_| Vec::new()).collect();
+        let mut loose = Vec::new();
+        for key in keys {
+            match self.group_index(key) {
+                Some(index) => members[index].push(key),
+                None => loose.push(key),
+            }
+        }
+        (members, loose)
+    }
+}
+
+impl<K: Hash + Eq> Default for Grouping<K> {
+    fn default() -> Grouping<K> {
+        Grouping::new()
+    }
+}
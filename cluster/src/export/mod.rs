@@ -0,0 +1,13 @@
+//! Renderings of Clusters for external tools.
+
+mod dot;
+mod graphml;
+mod grouping;
+mod partition;
+mod svg;
+
+pub use dot::{diff_to_dot, to_dot, to_dot_grouped};
+pub use graphml::{to_graphml, to_graphml_grouped};
+pub use grouping::Grouping;
+pub use partition::{hash_partition, write_partitions, PartitionFile, PartitionSummary};
+pub use svg::{to_svg, to_svg_grouped};
@@ -0,0 +1,296 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use crate::export::dot::{edges, undirected_once};
+use crate::{Cluster, ClusterError, Node, Result};
+
+/// Kind of the files written for each partition by write_partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionFile {
+    /// The nodes of the partition and the edges between them.
+    Graph,
+    /// The cut edges having an end in the partition.
+    Manifest,
+}
+
+/// Counts of what has been written for a partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PartitionSummary {
+    /// Number of nodes of the partition.
+    pub nodes: usize,
+    /// Number of edges between two nodes of the partition.
+    pub edges: usize,
+    /// Number of edges between a node of the partition and a node of another one.
+    pub cut_edges: usize,
+}
+
+/// Get the partition of a key by hashing it, the same key always going to the same partition.
+/// The hash does not depend on the process, but may change with the version of Rust.
+/// # Parameters
+/// - key - The key of the node.
+/// - parts - The number of partitions, greater than 0.
+/// # Return
+/// The index of the partition of the key, lower than parts.
+pub fn hash_partition<K: Hash>(key: &K, parts: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    (hasher.finish() % parts as u64) as usize
+}
+
+/// What is written for a partition, gathered before the partitions are written in parallel.
+struct Bucket<'clu, K> {
+    nodes: Vec<&'clu K>,
+    edges: Vec<(K, K)>,
+    cuts: Vec<usize>,
+}
+
+/// Split a Cluster into partitions written separately, along with manifests of the edges cut between them.
+/// Each file is made of tab-separated lines, the keys being written with their Display output :
+/// - the Graph file of a partition holds `node <key>` lines then `edge <src> <dst>` lines for the edges inside the partition ;
+/// - the Manifest file of a partition holds `cut <src> <dst> <partition of src> <partition of dst>` lines for the edges leaving or entering it,
+///   each cut edge being listed in the manifests of both its partitions.
+///
+/// The writers are opened and the keys and edges sorted into their partitions on the calling thread,
+/// then each partition is formatted and written on its own scoped thread.
+/// In an undirected Cluster, each edge is written once. Edges toward keys not in the Cluster are skipped.
+/// # Parameters
+/// - cluster - The Cluster to split.
+/// - parts - The number of partitions.
+/// - partition_of - The function giving the partition of a key, such as hash_partition or the output of a partitioner.
+/// - open - The function creating the writer of a file of a partition, typically by creating a file named after them.
+/// # Return
+/// The PartitionSummary of each partition, an error if a partition index is not lower than parts or a write failed.
+pub fn write_partitions<K, N, C, P, F, W>(
+    cluster: &C,
+    parts: usize,
+    partition_of: P,
+    mut open: F,
+) -> Result<Vec<PartitionSummary>>
+where
+    K: Hash + Eq + Clone + Display + Sync,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    P: Fn(&K) -> usize,
+    F: FnMut(usize, PartitionFile) -> std::io::Result<W>,
+    W: Write + Send,
+{
+    let part = |key: &K| -> Result<usize> {
+        let index = partition_of(key);
+        if index >= parts {
+            return Err(
+                ClusterError::detailled("A key has been given an unknown partition.").into(),
+            );
+        }
+        Ok(index)
+    };
+    let mut buckets: Vec<Bucket<K>> = (0..parts)
+        .map(|_| Bucket {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            cuts: Vec::new(),
+        })
+        .collect();
+    for key in cluster.keys() {
+        buckets[part(key)?].nodes.push(key);
+    }
+    let mut cuts = Vec::new();
+    for (src, dst) in undirected_once(edges(cluster), cluster.is_directed()) {
+        let (from, to) = (part(&src)?, part(&dst)?);
+        if from == to {
+            buckets[from].edges.push((src, dst));
+            continue;
+        }
+        buckets[from].cuts.push(cuts.len());
+        buckets[to].cuts.push(cuts.len());
+        cuts.push((src, dst, from, to));
+    }
+    let mut writers = Vec::with_capacity(parts);
+    for index in 0..parts {
+        writers.push((
+            open(index, PartitionFile::Graph)?,
+            open(index, PartitionFile::Manifest)?,
+        ));
+    }
+    let cuts = &cuts;
+    let summaries = std::thread::scope(|scope| {
+        let handles: Vec<_> = buckets
+            .iter()
+            .zip(writers)
+            .enumerate()
+            .map(|(index, (bucket, (graph, manifest)))| {
+                scope.spawn(move || write_partition(index, parts, bucket, cuts, graph, manifest))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect::<std::io::Result<Vec<PartitionSummary>>>()
+    })?;
+    Ok(summaries)
+}
+
+/// Write the files of a single partition.
+fn write_partition<K: Display, W: Write>(
+    index: usize,
+    parts: usize,
+    bucket: &Bucket<K>,
+    cuts: &[(K, K, usize, usize)],
+    mut graph: W,
+    mut manifest: W,
+) -> std::io::Result<PartitionSummary> {
+    writeln!(graph, "# partition {} of {}", index, parts)?;
+    for key in &bucket.nodes {
+        writeln!(graph, "node\t{}", key)?;
+    }
+    for (src, dst) in &bucket.edges {
+        writeln!(graph, "edge\t{}\t{}", src, dst)?;
+    }
+    graph.flush()?;
+    writeln!(manifest, "# cut edges of partition {} of {}", index, parts)?;
+    for cut in &bucket.cuts {
+        let (src, dst, from, to) = &cuts[*cut];
+        writeln!(manifest, "cut\t{}\t{}\t{}\t{}", src, dst, from, to)?;
+    }
+    manifest.flush()?;
+    Ok(PartitionSummary {
+        nodes: bucket.nodes.len(),
+        edges: bucket.edges.len(),
+        cut_edges: bucket.cuts.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{BasicNode, HashCluster};
+
+    type Files = Arc<Mutex<HashMap<(usize, PartitionFile), String>>>;
+
+    struct Sink {
+        files: Files,
+        file: (usize, PartitionFile),
+    }
+
+    impl Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let mut files = self.files.lock().unwrap();
+            let text = files.entry(self.file).or_default();
+            text.push_str(std::str::from_utf8(buf).unwrap());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn lines(files: &Files, file: (usize, PartitionFile)) -> Vec<String> {
+        let files = files.lock().unwrap();
+        let mut lines: Vec<String> = files[&file].lines().skip(1).map(String::from).collect();
+        lines.sort();
+        lines
+    }
+
+    #[test]
+    fn partitions_are_written_with_their_cut_edges() {
+        let cluster: HashCluster<u32, BasicNode<u32>> =
+            [(0, 2), (2, 4), (1, 3), (3, 0)].into_iter().collect();
+        let files = Files::default();
+        let summaries = write_partitions(
+            &cluster,
+            2,
+            |key| (*key % 2) as usize,
+            |index, kind| {
+                Ok(Sink {
+                    files: Arc::clone(&files),
+                    file: (index, kind),
+                })
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            summaries[0],
+            PartitionSummary {
+                nodes: 3,
+                edges: 2,
+                cut_edges: 1
+            }
+        );
+        assert_eq!(summaries[1].edges, 1);
+        assert_eq!(
+            lines(&files, (0, PartitionFile::Graph)),
+            ["edge\t0\t2", "edge\t2\t4", "node\t0", "node\t2", "node\t4"]
+        );
+        assert_eq!(
+            lines(&files, (1, PartitionFile::Manifest)),
+            ["cut\t3\t0\t1\t0"]
+        );
+        assert_eq!(
+            lines(&files, (0, PartitionFile::Manifest)),
+            ["cut\t3\t0\t1\t0"]
+        );
+    }
+
+    #[test]
+    fn the_partitions_rebuild_the_cluster() {
+        let mut cluster: HashCluster<u32, BasicNode<u32>> =
+            (0..40).map(|key| (key, (key * 7 + 3) % 40)).collect();
+        cluster.insert(40, BasicNode::new(()));
+        let files = Files::default();
+        write_partitions(
+            &cluster,
+            3,
+            |key| hash_partition(key, 3),
+            |index, kind| {
+                Ok(Sink {
+                    files: Arc::clone(&files),
+                    file: (index, kind),
+                })
+            },
+        )
+        .unwrap();
+        let mut rebuilt: HashCluster<u32, BasicNode<u32>> = HashCluster::new();
+        let mut edges = HashSet::new();
+        for index in 0..3 {
+            for kind in [PartitionFile::Graph, PartitionFile::Manifest] {
+                for line in lines(&files, (index, kind)) {
+                    let fields: Vec<u32> = line
+                        .split('\t')
+                        .skip(1)
+                        .map(|f| f.parse().unwrap())
+                        .collect();
+                    match fields[..] {
+                        [key] => {
+                            rebuilt.insert(key, BasicNode::new(()));
+                        }
+                        [src, dst, ..] => {
+                            edges.insert((src, dst));
+                        }
+                        _ => panic!("unexpected line {line}"),
+                    }
+                }
+            }
+        }
+        for (src, dst) in edges {
+            rebuilt.add_edge(src, dst).unwrap();
+        }
+        crate::assert_graph_eq!(rebuilt, cluster);
+    }
+
+    #[test]
+    fn unknown_partitions_are_refused() {
+        let cluster: HashCluster<u32, BasicNode<u32>> = [(0, 1)].into_iter().collect();
+        let written = write_partitions(&cluster, 1, |key| *key as usize, |_, _| Ok(Vec::new()));
+        assert!(written.is_err());
+        assert!(hash_partition(&"key", 3) < 3);
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+use crate::export::dot::{edges, undirected_once};
+use crate::export::graphml::escape_xml;
+use crate::export::Grouping;
+use crate::{Cluster, Node};
+
+/// Radius of the circle drawn for a node.
+const NODE_RADIUS: f64 = 18.0;
+/// Horizontal space given to a column of nodes.
+const COLUMN_WIDTH: f64 = 160.0;
+/// Vertical space given to a node of a column.
+const ROW_HEIGHT: f64 = 60.0;
+/// Space kept above the nodes of a column for the label of its group.
+const LABEL_HEIGHT: f64 = 24.0;
+/// Space around the drawing and between the boxes of the groups.
+const MARGIN: f64 = 20.0;
+
+/// Render a Cluster as an SVG picture, each node being drawn as a circle labelled with the Debug output of its key.
+/// # Parameter
+/// - cluster - The Cluster to render.
+/// # Return
+/// The SVG document picturing the Cluster.
+pub fn to_svg<K, N, C>(cluster: &C) -> String
+where
+    K: Hash + Eq + Clone + Debug,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    to_svg_grouped(cluster, &Grouping::new())
+}
+
+/// Render a Cluster as an SVG picture, the nodes of each group being stacked in a column boxed with the name of the group.
+/// The nodes without a group are stacked in a last column without a box, the edges being drawn as straight lines between the circles,
+/// ending with an arrow in a directed Cluster.
+/// # Parameters
+/// - cluster - The Cluster to render.
+/// - grouping - The groups of the nodes, keys not in the Cluster being ignored.
+/// # Return
+/// The SVG document picturing the Cluster.
+pub fn to_svg_grouped<K, N, C>(cluster: &C, grouping: &Grouping<K>) -> String
+where
+    K: Hash + Eq + Clone + Debug,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    let (members, loose) = grouping.split(cluster.keys());
+    let mut columns: Vec<(Option<&str>, Vec<&K>)> = grouping
+        .groups()
+        .zip(members)
+        .filter(|(_, keys)| !keys.is_empty())
+        .map(|(name, keys)| (Some(name), keys))
+        .collect();
+    if !loose.is_empty() || columns.is_empty() {
+        columns.push((None, loose));
+    }
+    let rows = columns
+        .iter()
+        .map(|(_, keys)| keys.len())
+        .max()
+        .unwrap_or(0);
+    let width = 2.0 * MARGIN + columns.len() as f64 * COLUMN_WIDTH;
+    let height = 2.0 * MARGIN + LABEL_HEIGHT + rows as f64 * ROW_HEIGHT;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+    );
+    let directed = cluster.is_directed();
+    if directed {
+        out.push_str("  <defs>\n    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">\n");
+        out.push_str("      <path d=\"M 0 0 L 10 5 L 0 10 z\"/>\n    </marker>\n  </defs>\n");
+    }
+    let mut positions = HashMap::new();
+    for (column, (name, keys)) in columns.iter().enumerate() {
+        let left = MARGIN + column as f64 * COLUMN_WIDTH;
+        if let Some(name) = name {
+            let _ = writeln!(
+                out,
+                "  <g class=\"group\"><rect x=\"{}\" y=\"{MARGIN}\" width=\"{}\" height=\"{}\" rx=\"8\" fill=\"#f4f4f4\" stroke=\"#888\"/><text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text></g>",
+                left + MARGIN / 2.0,
+                COLUMN_WIDTH - MARGIN,
+                LABEL_HEIGHT + keys.len() as f64 * ROW_HEIGHT,
+                left + COLUMN_WIDTH / 2.0,
+                MARGIN + LABEL_HEIGHT * 0.75,
+                escape_xml(name)
+            );
+        }
+        for (row, key) in keys.iter().enumerate() {
+            let center = (
+                left + COLUMN_WIDTH / 2.0,
+                MARGIN + LABEL_HEIGHT + (row as f64 + 0.5) * ROW_HEIGHT,
+            );
+            positions.insert(*key, center);
+        }
+    }
+    let marker = if directed {
+        " marker-end=\"url(#arrow)\""
+    } else {
+        ""
+    };
+    for (src, dst) in undirected_once(edges(cluster), directed) {
+        let ((x1, y1), (x2, y2)) = (positions[&src], positions[&dst]);
+        if src == dst {
+            let _ = writeln!(
+                out,
+                "  <path d=\"M {} {} A 12 12 0 1 1 {} {}\" fill=\"none\" stroke=\"#333\"{marker}/>",
+                x1 - NODE_RADIUS / 2.0,
+                y1 - NODE_RADIUS,
+                x1 + NODE_RADIUS / 2.0,
+                y1 - NODE_RADIUS
+            );
+            continue;
+        }
+        let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+        let (dx, dy) = (
+            (x2 - x1) / length * NODE_RADIUS,
+            (y2 - y1) / length * NODE_RADIUS,
+        );
+        let _ = writeln!(
+            out,
+            "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#333\"{marker}/>",
+            x1 + dx,
+            y1 + dy,
+            x2 - dx,
+            y2 - dy
+        );
+    }
+    for (key, (x, y)) in &positions {
+        let _ = writeln!(
+            out,
+            "  <g class=\"node\"><circle cx=\"{x}\" cy=\"{y}\" r=\"{NODE_RADIUS}\" fill=\"white\" stroke=\"#333\"/><text x=\"{x}\" y=\"{}\" text-anchor=\"middle\">{}</text></g>",
+            y + 5.0,
+            escape_xml(&format!("{:?}", key))
+        );
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster};
+
+    #[test]
+    fn groups_are_drawn_as_labelled_boxes() {
+        let cluster: HashCluster<u32, BasicNode<u32>> =
+            [(0, 1), (1, 2), (2, 2)].into_iter().collect();
+        let mut grouping = Grouping::new();
+        grouping.assign(0, "<core>");
+        grouping.assign(1, "<core>");
+        let svg = to_svg_grouped(&cluster, &grouping);
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("class=\"group\"").count(), 1);
+        assert!(svg.contains(">&lt;core&gt;</text>"));
+        assert_eq!(svg.matches("class=\"node\"").count(), 3);
+        assert_eq!(svg.matches("<line ").count(), 2);
+        assert_eq!(svg.matches(" A 12 12 ").count(), 1);
+        assert!(svg.contains("marker-end=\"url(#arrow)\""));
+    }
+
+    #[test]
+    fn empty_cluster_gives_an_empty_picture() {
+        let cluster: HashCluster<u32, BasicNode<u32>> = HashCluster::new();
+        let svg = to_svg(&cluster);
+        assert!(svg.contains("width=\"200\" height=\"64\""));
+        assert!(!svg.contains("<line "));
+    }
+}
@@ -0,0 +1,63 @@
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::{Directed, HashCluster};
+
+/// Multiplier of the Fx hash function, as used by rustc.
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// Fast, non cryptographic hasher for small keys such as integers.
+/// It gives no protection against keys crafted to collide, use the default hasher for untrusted keys.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    /// Mix a word into the hash.
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        for byte in chunks.remainder() {
+            self.add_to_hash(u64::from(*byte));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Builder of FxHashers, to parameterize a HashMap or a HashCluster with.
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+/// HashCluster hashing its keys with the FxHasher, much faster than SipHash on integer keys.
+/// It names the hasher explicitly, the plain HashCluster using the FxHasher as well while the fxhash feature is enabled.
+pub type FastCluster<K, N, D = Directed> = HashCluster<K, N, D, FxBuildHasher>;
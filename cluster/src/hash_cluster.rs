@@ -0,0 +1,506 @@
+#[cfg(not(feature = "fxhash"))]
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+use crate::{
+    BasicNode, Cluster, ClusterError, Directed, Direction, KeyConflict, Node, Result, Undirected,
+    WeightedBasicNode,
+};
+
+/// Trait for the keys a HashCluster is able to generate on its own.
+pub trait Key: Hash + Eq + Clone {
+    /// Build a key from a counter value.
+    /// # Parameter
+    /// - n - The counter value the key is built from.
+    /// # Return
+    /// The key corresponding to the counter value.
+    fn from_counter(n: usize) -> Self;
+
+    /// Get the index of the key, letting the algorithms store their bookkeeping in vectors rather than in HashMaps.
+    /// The default implementation gives no index, the integer keys being their own index when not negative.
+    /// # Return
+    /// An Option containing the index of the key, None if it has none.
+    fn to_index(&self) -> Option<usize> {
+        None
+    }
+}
+
+macro_rules! impl_integer_key {
+    ($($t:ty),*) => {
+        $(
+            impl Key for $t {
+                fn from_counter(n: usize) -> Self {
+                    <$t>::try_from(n).expect("The key space of the Cluster is exhausted.")
+                }
+
+                fn to_index(&self) -> Option<usize> {
+                    usize::try_from(*self).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_key!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Key for String {
+    fn from_counter(n: usize) -> Self {
+        n.to_string()
+    }
+}
+
+/// Default hasher of the HashClusters, the FxHasher with the fxhash feature and SipHash without it.
+#[cfg(feature = "fxhash")]
+pub type DefaultBuildHasher = crate::FxBuildHasher;
+
+/// Default hasher of the HashClusters, the FxHasher with the fxhash feature and SipHash without it.
+#[cfg(not(feature = "fxhash"))]
+pub type DefaultBuildHasher = RandomState;
+
+/// Cluster implementation storing its nodes in a HashMap.
+/// The direction marker D tells if the edges are Directed (default) or Undirected.
+/// In an Undirected HashCluster, adding or removing an edge affects both of its nodes.
+/// The hasher S of the HashMap is DefaultBuildHasher, the fast but not DoS-resistant FxHasher when the fxhash feature is enabled,
+/// SipHash otherwise. A HashCluster exposed to untrusted keys should be given RandomState explicitly.
+/// Inserting a node at a used key follows the KeyConflict policy of the Cluster, Overwrite by default.
+#[derive(Debug, Clone)]
+pub struct HashCluster<K, N, D = Directed, S = DefaultBuildHasher> {
+    nodes: HashMap<K, N, S>,
+    conflict: KeyConflict<N>,
+    direction: PhantomData<D>,
+}
+
+/// HashCluster whose edges link both of their nodes to each other.
+pub type UndirectedHashCluster<K, N, S = DefaultBuildHasher> = HashCluster<K, N, Undirected, S>;
+
+impl<K, N, D, S: Default> HashCluster<K, N, D, S> {
+    /// Create a new empty HashCluster.
+    /// # Return
+    /// The newly created HashCluster.
+    pub fn new() -> HashCluster<K, N, D, S> {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, N, D, S> HashCluster<K, N, D, S> {
+    /// Create a new empty HashCluster hashing its keys with the given hasher.
+    /// # Parameter
+    /// - hasher - The hasher used by the HashMap of the nodes.
+    /// # Return
+    /// The newly created HashCluster.
+    pub fn with_hasher(hasher: S) -> HashCluster<K, N, D, S> {
+        HashCluster {
+            nodes: HashMap::with_hasher(hasher),
+            conflict: KeyConflict::Overwrite,
+            direction: PhantomData,
+        }
+    }
+
+    /// Get the number of nodes in the Cluster.
+    /// # Return
+    /// The number of nodes stored in the Cluster.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check if the Cluster has no nodes.
+    /// # Return
+    /// True if the Cluster is empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Get the policy applied by try_insert when a key is already used.
+    /// # Return
+    /// The KeyConflict policy of the Cluster.
+    pub fn conflict_policy(&self) -> KeyConflict<N> {
+        self.conflict
+    }
+
+    /// Set the policy applied by try_insert when a key is already used.
+    /// # Parameter
+    /// - policy - The new KeyConflict policy of the Cluster.
+    pub fn set_conflict_policy(&mut self, policy: KeyConflict<N>) {
+        self.conflict = policy;
+    }
+}
+
+impl<K, N, D, S: Default> Default for HashCluster<K, N, D, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, N: Node<K>, D: Direction, S: BuildHasher> HashCluster<K, N, D, S> {
+    /// Insert a node at the given key, replacing the node previously stored there.
+    /// The adjacency of the node is stored as is, even in an Undirected HashCluster.
+    /// # Parameters
+    /// - key - The key at which the node will be stored.
+    /// - node - The node to store.
+    /// # Return
+    /// An Option containing the node previously stored at key, None otherwise.
+    pub fn insert(&mut self, key: K, node: N) -> Option<N> {
+        self.nodes.insert(key, node)
+    }
+
+    /// Insert a node at the given key, following the KeyConflict policy of the Cluster if the key is used.
+    /// # Parameters
+    /// - key - The key at which the node will be stored.
+    /// - node - The node to store.
+    /// # Return
+    /// An Option containing the node no longer stored, the replaced one or the skipped one, an error if the policy refuses the insertion.
+    pub fn try_insert(&mut self, key: K, node: N) -> Result<Option<N>> {
+        self.insert_with(key, node, self.conflict)
+    }
+
+    /// Insert a node at the given key, following the given policy if the key is used.
+    /// # Parameters
+    /// - key - The key at which the node will be stored.
+    /// - node - The node to store.
+    /// - policy - The KeyConflict policy applied to this insertion only.
+    /// # Return
+    /// An Option containing the node no longer stored, the replaced one or the skipped one, an error if the policy refuses the insertion.
+    pub fn insert_with(&mut self, key: K, node: N, policy: KeyConflict<N>) -> Result<Option<N>> {
+        match self.nodes.get_mut(&key) {
+            Some(stored) => policy.resolve(stored, node),
+            None => {
+                self.nodes.insert(key, node);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Insert nodes following the KeyConflict policy of the Cluster, stopping at the first insertion the policy refuses.
+    /// The nodes before the refused one stay inserted.
+    /// # Parameter
+    /// - iter - The (key, node) pairs to insert.
+    /// # Return
+    /// Nothing if every node has been inserted, an error if the policy refused one.
+    pub fn try_extend<I: IntoIterator<Item = (K, N)>>(&mut self, iter: I) -> Result<()> {
+        for (key, node) in iter {
+            self.try_insert(key, node)?;
+        }
+        Ok(())
+    }
+
+    /// Get the edges of the Cluster.
+    /// In an Undirected HashCluster, each edge is reported once.
+    /// # Return
+    /// An iterator over the (source, destination) pairs of the edges, in no particular order.
+    pub fn edges(&self) -> impl Iterator<Item = (&K, &K)> {
+        let mut reported = HashSet::new();
+        self.nodes
+            .iter()
+            .flat_map(|(src, node)| node.adj().iter().map(move |dst| (src, dst)))
+            .filter(move |(src, dst)| {
+                if D::DIRECTED {
+                    return true;
+                }
+                reported.insert((*src, *dst));
+                !reported.contains(&(*dst, *src)) || src == dst
+            })
+    }
+}
+
+impl<K, N, D, S> HashCluster<K, N, D, S>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K> + Default,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    /// Build a Cluster from a map associating each node to its parent.
+    /// The edges of the resulting Cluster go from the parents to their children.
+    /// A node being its own parent is considered a root and gets no edge.
+    /// # Parameter
+    /// - parents - The map associating each child key to the key of its parent.
+    /// # Return
+    /// The newly built HashCluster.
+    pub fn from_parent_map(parents: HashMap<K, K>) -> HashCluster<K, N, D, S> {
+        let mut cluster = Self::new();
+        for (child, parent) in parents {
+            cluster.node_entry(child.clone());
+            if child != parent {
+                cluster.link(parent, child);
+            }
+        }
+        cluster
+    }
+
+    /// Build a Cluster from a map associating each node to its children.
+    /// The edges of the resulting Cluster go from the parents to their children.
+    /// # Parameter
+    /// - children - The map associating each key to the keys of its children.
+    /// # Return
+    /// The newly built HashCluster.
+    pub fn from_children_map(children: HashMap<K, Vec<K>>) -> HashCluster<K, N, D, S> {
+        let mut cluster = Self::new();
+        for (parent, kids) in children {
+            cluster.node_entry(parent.clone());
+            for child in kids {
+                cluster.link(parent.clone(), child);
+            }
+        }
+        cluster
+    }
+
+    /// Build a Cluster from (source, destination) pairs.
+    /// The destinations of each source are grouped in its adjacency list, duplicated pairs are ignored.
+    /// # Parameter
+    /// - pairs - The (source, destination) pairs describing the edges of the Cluster.
+    /// # Return
+    /// The newly built HashCluster.
+    pub fn from_pairs_grouped<I>(pairs: I) -> HashCluster<K, N, D, S>
+    where
+        I: IntoIterator<Item = (K, K)>,
+    {
+        let mut cluster = Self::new();
+        for (src, dst) in pairs {
+            cluster.link(src, dst);
+        }
+        cluster
+    }
+
+    /// Get the node stored at key, inserting a default node first if there is none.
+    fn node_entry(&mut self, key: K) -> &mut N {
+        self.nodes.entry(key).or_default()
+    }
+
+    /// Add an edge between src and dst, creating the missing nodes.
+    fn link(&mut self, src: K, dst: K) {
+        if !D::DIRECTED {
+            push_unique(self.node_entry(dst.clone()).adj_mut(), src.clone());
+        } else {
+            self.node_entry(dst.clone());
+        }
+        push_unique(self.node_entry(src).adj_mut(), dst);
+    }
+}
+
+impl<K: Key, N: Node<K>, D: Direction, S: BuildHasher> HashCluster<K, N, D, S> {
+    /// Add the edge going from src to dst only, whatever the direction of the Cluster.
+    fn add_directed_edge(&mut self, src: K, dst: K) -> Result<()> {
+        let node = self.nodes.get_mut(&src).ok_or(ClusterError::detailled(
+            "To add edge, both node must exists in the Cluster.",
+        ))?;
+        push_unique(node.adj_mut(), dst);
+        Ok(())
+    }
+
+    /// Remove the edge going from src to dst only, whatever the direction of the Cluster.
+    fn remove_directed_edge(&mut self, src: &K, dst: &K) -> Result<()> {
+        let node = self
+            .nodes
+            .get_mut(src)
+            .ok_or(ClusterError::detailled("<src> node does not exists."))?;
+        node.retain_adj(|k| k != dst);
+        Ok(())
+    }
+}
+
+/// Push key in adj if it is not already in it.
+fn push_unique<K: PartialEq>(adj: &mut Vec<K>, key: K) {
+    if !adj.contains(&key) {
+        adj.push(key);
+    }
+}
+
+impl<K: Key, N: Node<K>, D: Direction, S: BuildHasher> Cluster<K, N> for HashCluster<K, N, D, S> {
+    fn remove(&mut self, key: &K) -> Option<N> {
+        let node = self.nodes.remove(key)?;
+        for other in self.nodes.values_mut() {
+            other.retain_adj(|k| k != key);
+        }
+        Some(node)
+    }
+
+    fn remove_many(&mut self, keys: impl IntoIterator<Item = K>) -> Vec<N> {
+        let mut removed = HashSet::new();
+        let mut nodes: Vec<N> = keys
+            .into_iter()
+            .filter_map(|key| {
+                let node = self.nodes.remove(&key)?;
+                removed.insert(key);
+                Some(node)
+            })
+            .collect();
+        for node in self.nodes.values_mut().chain(nodes.iter_mut()) {
+            node.retain_adj(|k| !removed.contains(k));
+        }
+        nodes
+    }
+
+    fn keys<'clu>(&'clu self) -> impl Iterator<Item = &'clu K>
+    where
+        K: 'clu,
+    {
+        self.nodes.keys()
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.nodes.contains_key(key)
+    }
+
+    fn get(&self, key: &K) -> Option<&N> {
+        self.nodes.get(key)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut N> {
+        self.nodes.get_mut(key)
+    }
+
+    fn new_key(&self) -> K {
+        let mut counter = self.nodes.len();
+        loop {
+            let key = K::from_counter(counter);
+            if !self.nodes.contains_key(&key) {
+                return key;
+            }
+            counter += 1;
+        }
+    }
+
+    fn add(&mut self, node: N) -> K {
+        let key = self.new_key();
+        self.nodes.insert(key.clone(), node);
+        key
+    }
+
+    fn key_index(key: &K) -> Option<usize> {
+        key.to_index()
+    }
+
+    fn is_directed(&self) -> bool {
+        D::DIRECTED
+    }
+
+    fn add_edge(&mut self, src: K, dst: K) -> Result<()> {
+        if D::DIRECTED {
+            return self.add_directed_edge(src, dst);
+        }
+        if !self.nodes.contains_key(&dst) {
+            return Err(ClusterError::detailled(
+                "To add edge, both node must exists in the Cluster.",
+            )
+            .into());
+        }
+        self.add_directed_edge(src.clone(), dst.clone())?;
+        self.add_directed_edge(dst, src)
+    }
+
+    fn remove_edge(&mut self, src: &K, dst: &K) -> Result<()> {
+        if D::DIRECTED {
+            return self.remove_directed_edge(src, dst);
+        }
+        if !self.nodes.contains_key(dst) {
+            return Err(ClusterError::detailled("<dst> node does not exists.").into());
+        }
+        self.remove_directed_edge(src, dst)?;
+        self.remove_directed_edge(dst, src)
+    }
+}
+
+impl<K, N, D, S> Extend<(K, N)> for HashCluster<K, N, D, S>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    D: Direction,
+    S: BuildHasher,
+{
+    /// Insert the nodes following the KeyConflict policy of the Cluster.
+    /// Under the Error policy, the nodes inserted at a used key are skipped, try_extend reporting them instead.
+    fn extend<I: IntoIterator<Item = (K, N)>>(&mut self, iter: I) {
+        for (key, node) in iter {
+            let _ = self.try_insert(key, node);
+        }
+    }
+}
+
+impl<K, N, D, S> FromIterator<(K, N)> for HashCluster<K, N, D, S>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, N)>>(iter: I) -> Self {
+        let mut cluster = Self::new();
+        cluster.extend(iter);
+        cluster
+    }
+}
+
+impl<K, V, D, S> FromIterator<(K, K)> for HashCluster<K, BasicNode<K, V>, D, S>
+where
+    K: Hash + Eq + Clone,
+    V: Default,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, K)>>(iter: I) -> Self {
+        Self::from_pairs_grouped(iter)
+    }
+}
+
+impl<K, W, V, D, S> FromIterator<(K, K)> for HashCluster<K, WeightedBasicNode<K, W, V>, D, S>
+where
+    K: Hash + Eq + Clone,
+    V: Default,
+    D: Direction,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, K)>>(iter: I) -> Self {
+        Self::from_pairs_grouped(iter)
+    }
+}
+
+impl<K, N, D, S> IntoIterator for HashCluster<K, N, D, S> {
+    type Item = (K, N);
+    type IntoIter = std::collections::hash_map::IntoIter<K, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "fxhash")]
+    #[test]
+    fn fxhash_is_the_default_hasher() {
+        let cluster: crate::FastCluster<u32, BasicNode<u32>> = [(0, 1)].into_iter().collect();
+        let cluster: HashCluster<u32, BasicNode<u32>> = cluster;
+        assert_eq!(cluster.get_adj(&0), Some(&[1][..]));
+    }
+
+    #[test]
+    fn integer_keys_are_their_own_index() {
+        assert_eq!(7u8.to_index(), Some(7));
+        assert_eq!((-1i32).to_index(), None);
+        assert_eq!(String::from("7").to_index(), None);
+        assert_eq!(HashCluster::<u64, BasicNode<u64>>::key_index(&3), Some(3));
+    }
+
+    #[test]
+    fn extend_skips_the_used_keys_under_the_error_policy() {
+        let mut cluster: HashCluster<u32, BasicNode<u32, &str>> = HashCluster::new();
+        cluster.set_conflict_policy(KeyConflict::Error);
+        cluster.extend([(0, BasicNode::new("first")), (0, BasicNode::new("second"))]);
+        assert_eq!(cluster.len(), 1);
+        assert_eq!(cluster.get(&0).map(|node| *node.value()), Some("first"));
+        let refused =
+            cluster.try_extend([(1, BasicNode::new("one")), (0, BasicNode::new("again"))]);
+        assert!(refused.is_err());
+        assert!(cluster.contains_key(&1));
+        assert_eq!(cluster.get(&0).map(|node| *node.value()), Some("first"));
+        assert!(cluster.try_extend([(2, BasicNode::new("two"))]).is_ok());
+    }
+}
@@ -1,5 +1,47 @@
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Display;
+use std::hash::Hash;
+
+pub mod algo;
+pub mod analysis;
+#[cfg(feature = "compat")]
+pub mod compat;
+mod concurrent;
+mod conflict;
+mod direction;
+pub mod export;
+#[cfg(feature = "fxhash")]
+mod fx_hash;
+mod hash_cluster;
+mod node;
+mod overlay;
+mod path;
+#[cfg(feature = "rand")]
+mod random;
+mod reverse;
+mod slice_cluster;
+pub mod traversal;
+mod weight_transform;
+mod weighted;
+
+use algo::connectivity::DisjointSet;
+pub use analysis::{ClusterStats, GraphDiff, GraphReport};
+pub use concurrent::{ConcurrentCluster, ConcurrentModification};
+pub use conflict::KeyConflict;
+pub use direction::{Directed, Direction, Undirected};
+#[cfg(feature = "fxhash")]
+pub use fx_hash::{FastCluster, FxBuildHasher, FxHasher};
+pub use hash_cluster::{DefaultBuildHasher, HashCluster, Key, UndirectedHashCluster};
+pub use node::BasicNode;
+pub use overlay::{ExperimentOverlay, OverlayKey};
+pub use path::Path;
+#[cfg(feature = "rand")]
+pub use random::{RandomSource, SplitMix64};
+pub use reverse::{ReverseAdjacency, ReverseIndex};
+pub use slice_cluster::{SliceCluster, SliceNode};
+pub use weight_transform::{WeightTransform, WeightView};
+pub use weighted::{Weight, WeightedBasicNode, WeightedCluster, WeightedNode};
 
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -10,11 +52,10 @@ struct ClusterError {
 
 impl Display for ClusterError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Cluster Error : {}", self.message())?;
         if let Some(source) = self.source() {
-            println!("Cluster Error : {}", self.message());
             writeln!(f, "Caused by {}", source)
         } else {
-            println!("Cluster Error : {}", self.message());
             writeln!(f, "Unknown source.")
         }
     }
@@ -82,13 +123,23 @@ pub trait Node<K> {
     /// Get the adjacency of the current Node.
     /// # Return
     /// A immutable reference to the adjacency list of the current Node.
-    fn adj(&self) -> &Vec<K>;
+    fn adj(&self) -> &[K];
     /// Get the adjacency of the current Node.
     /// # Return
     /// A mutable reference to the adjacency list of the current Node.
     fn adj_mut(&mut self) -> &mut Vec<K>;
-}
 
+    /// Remove the edges of the current Node toward the keys rejected by a predicate.
+    /// The Nodes storing data about their edges override it to forget the data of the removed edges.
+    /// # Parameter
+    /// - keep - The predicate telling if the edge toward a key is kept.
+    fn retain_adj<F: FnMut(&K) -> bool>(&mut self, keep: F)
+    where
+        Self: Sized,
+    {
+        self.adj_mut().retain(keep);
+    }
+}
 
 /// Graph data structure trait.
 /// Named Cluster to help diffenciate from the other implementation of graph data structure.
@@ -104,6 +155,53 @@ where
     /// An Option containing the value if it exists, None otherwise
     fn remove(&mut self, key: &K) -> Option<N>;
 
+    /// Removes a set of nodes from the Cluster, along with all the edges toward them.
+    /// The edges between the removed nodes are dropped as well, the adjacency of each returned node only keeping the others.
+    /// The default implementation cleans the edges in a single pass before removing the nodes one by one.
+    /// # Parameter
+    /// - keys - The keys of the nodes to remove, those not in the Cluster being ignored.
+    /// # Return
+    /// The removed nodes, in the order of their keys.
+    fn remove_many(&mut self, keys: impl IntoIterator<Item = K>) -> Vec<N>
+    where
+        K: Hash + Eq,
+        Self: Sized,
+    {
+        let mut removed = HashSet::new();
+        let keys: Vec<K> = keys
+            .into_iter()
+            .filter(|key| self.contains_key(key) && removed.insert(key.clone()))
+            .collect();
+        let all: Vec<K> = self.keys().cloned().collect();
+        for key in &all {
+            if let Some(node) = self.get_mut(key) {
+                node.retain_adj(|k| !removed.contains(k));
+            }
+        }
+        keys.iter().filter_map(|key| self.remove(key)).collect()
+    }
+
+    /// Get the keys of all the nodes of the Cluster.
+    /// # Return
+    /// An iterator over the keys of the Cluster.
+    fn keys<'clu>(&'clu self) -> impl Iterator<Item = &'clu K>
+    where
+        K: 'clu;
+
+    /// Get the number of nodes in the Cluster.
+    /// # Return
+    /// The number of nodes stored in the Cluster.
+    fn len(&self) -> usize {
+        self.keys().count()
+    }
+
+    /// Check if the Cluster has no nodes.
+    /// # Return
+    /// True if the Cluster is empty, false otherwise.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Check if the Cluster contains a value at a given key.
     /// # Parameter
     /// - key - The key on we want to check the Cluster contains it or no.
@@ -134,7 +232,7 @@ where
     /// - key - the index of the node we want to get the adjacency list.
     /// # Return
     /// An immutable reference to the adjacency list of the desgnated node of the Cluster or None if there is no such Node.
-    fn get_adj<'clu, 'res>(&'clu self, key: &K) -> Option<&'res Vec<K>>
+    fn get_adj<'clu, 'res>(&'clu self, key: &K) -> Option<&'res [K]>
     where
         'clu: 'res,
         N: 'res,
@@ -152,6 +250,37 @@ where
     /// The index at which the node has been stored in the graph.
     fn add(&mut self, node: N) -> K;
 
+    /// Add a node in the Cluster, failing instead of panicking when the Cluster cannot store it.
+    /// The default implementation calls add, the Clusters whose add may panic override it.
+    /// # Parameter
+    /// - node - The node to store.
+    /// # Return
+    /// The key at which the node has been stored, an error if the Cluster refuses it.
+    fn try_add(&mut self, node: N) -> Result<K> {
+        Ok(self.add(node))
+    }
+
+    /// Get the index of a key, letting the algorithms store their bookkeeping in vectors rather than in HashMaps, see traversal::KeyMap.
+    /// The default implementation gives no index, a Cluster whose keys are small integers overrides it.
+    /// # Parameter
+    /// - key - The key to index.
+    /// # Return
+    /// An Option containing the index of the key, None if it has none.
+    fn key_index(key: &K) -> Option<usize>
+    where
+        Self: Sized,
+    {
+        let _ = key;
+        None
+    }
+
+    /// Check if the edges of the Cluster are directed.
+    /// # Return
+    /// True if an edge only goes from its source to its destination, false if it links both nodes to each other.
+    fn is_directed(&self) -> bool {
+        true
+    }
+
     /// Get the adjancy list of the node designed by it key given in parameter.
     /// # Parameter
     /// - key - the index of the node we want to get the adjacency list.
@@ -192,12 +321,9 @@ where
     /// Nothing if everithing gone well, an error otherwise.
     ///
     fn remove_edge(&mut self, src: &K, dst: &K) -> Result<()> {
-        let adj = self
-            .get_adj_mut(&src)
-            .ok_or(ClusterError::detailled("<src> node does not exists."))?;
-        if let Some(index) = adj.iter().position(|i| *i == *dst) {
-            adj.remove(index);
-        }
+        self.get_mut(src)
+            .ok_or(ClusterError::detailled("<src> node does not exists."))?
+            .retain_adj(|k| k != dst);
         Ok(())
     }
 
@@ -228,4 +354,43 @@ where
         self.remove_edge(dst, src)?;
         Ok(())
     }
+
+    /// Build a summary of the Cluster : counts, hubs, components and suspicious structures.
+    /// # Return
+    /// The GraphReport of the Cluster, renderable as text, Markdown or JSON.
+    fn report(&self) -> GraphReport<K>
+    where
+        K: Hash + Eq,
+        Self: Sized,
+    {
+        analysis::report(self)
+    }
+
+    /// Compute the connected components of the Cluster in a DisjointSet, the direction of the edges being ignored.
+    /// The DisjointSet can then be kept up to date with the edges added afterward, without traversing the Cluster again.
+    /// # Return
+    /// The DisjointSet whose sets are the connected components of the Cluster.
+    fn components_via_union_find(&self) -> DisjointSet<K>
+    where
+        K: Hash + Eq,
+        Self: Sized,
+    {
+        DisjointSet::from_cluster(self)
+    }
+
+    /// Shuffle the edges of the Cluster while keeping the degree of every node, to build null models.
+    /// See algo::rewire::rewire for the details of the swaps.
+    /// # Parameters
+    /// - iterations - The number of swaps to attempt.
+    /// - rng - The source of randomness.
+    /// # Return
+    /// The number of swaps actually made.
+    #[cfg(feature = "rand")]
+    fn rewire<R: RandomSource + ?Sized>(&mut self, iterations: usize, rng: &mut R) -> usize
+    where
+        K: Hash + Eq,
+        Self: Sized,
+    {
+        algo::rewire::rewire(self, iterations, rng)
+    }
 }
@@ -0,0 +1,59 @@
+use crate::Node;
+
+/// Ready to use Node storing a value next to its adjacency list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicNode<K, V = ()> {
+    value: V,
+    adj: Vec<K>,
+}
+
+impl<K, V> BasicNode<K, V> {
+    /// Create a new node holding the given value and no adjacency.
+    /// # Parameter
+    /// - value - The value stored in the node.
+    /// # Return
+    /// The newly created BasicNode.
+    pub fn new(value: V) -> BasicNode<K, V> {
+        BasicNode {
+            value,
+            adj: Vec::new(),
+        }
+    }
+
+    /// Get the value stored in the node.
+    /// # Return
+    /// An immutable reference to the value of the node.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Get the value stored in the node.
+    /// # Return
+    /// A mutable reference to the value of the node.
+    pub fn value_mut(&mut self) -> &mut V {
+        &mut self.value
+    }
+
+    /// Consume the node and returns the value it was holding.
+    /// # Return
+    /// The value of the node.
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<K, V: Default> Default for BasicNode<K, V> {
+    fn default() -> Self {
+        Self::new(V::default())
+    }
+}
+
+impl<K, V> Node<K> for BasicNode<K, V> {
+    fn adj(&self) -> &[K] {
+        &self.adj
+    }
+
+    fn adj_mut(&mut self) -> &mut Vec<K> {
+        &mut self.adj
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{Cluster, ClusterError, Node, Result};
+
+/// Key of an endpoint of a tentative edge : either a node of the base Cluster or a tentative node of the overlay.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OverlayKey<K> {
+    /// Node stored in the base Cluster.
+    Base(K),
+    /// Tentative node, designed by the index returned when it was added to the overlay.
+    Tentative(usize),
+}
+
+/// Labeled set of tentative nodes and edges applied over a base Cluster only for the duration of a closure.
+/// Once the closure returns, or panics, every tentative node and edge is removed from the base Cluster.
+#[derive(Debug, Clone)]
+pub struct ExperimentOverlay<K, N> {
+    label: String,
+    nodes: Vec<N>,
+    edges: Vec<(OverlayKey<K>, OverlayKey<K>)>,
+}
+
+impl<K, N> ExperimentOverlay<K, N> {
+    /// Create an overlay holding no tentative node nor edge.
+    /// # Parameter
+    /// - label - The label identifying the experiment.
+    /// # Return
+    /// The newly created ExperimentOverlay.
+    pub fn new(label: &str) -> ExperimentOverlay<K, N> {
+        ExperimentOverlay {
+            label: String::from(label),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Get the label of the experiment.
+    /// # Return
+    /// The label given at the creation of the overlay.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Add a tentative node to the overlay.
+    /// # Parameter
+    /// - node - The node to add to the base Cluster during the experiment.
+    /// # Return
+    /// The index designing the tentative node in OverlayKey::Tentative.
+    pub fn add_node(&mut self, node: N) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Add a tentative edge to the overlay.
+    /// # Parameters
+    /// - src - The key of the source node.
+    /// - dst - The key of the destination node.
+    pub fn add_edge(&mut self, src: OverlayKey<K>, dst: OverlayKey<K>) {
+        self.edges.push((src, dst));
+    }
+}
+
+impl<K: PartialEq + Clone, N: Node<K>> ExperimentOverlay<K, N> {
+    /// Apply the overlay on a Cluster, run a closure on the resulting Cluster, then revert the Cluster to its previous state.
+    /// The tentative nodes are given back to the overlay afterward, so the same experiment can be run again.
+    /// # Parameters
+    /// - cluster - The base Cluster the overlay is applied on.
+    /// - experiment - The closure receiving the Cluster with the overlay applied, and the keys given to the tentative nodes.
+    /// # Return
+    /// The value returned by the closure, an error if a tentative edge refers to a node that does not exist.
+    pub fn run<C, R, F>(&mut self, cluster: &mut C, experiment: F) -> Result<R>
+    where
+        C: Cluster<K, N>,
+        F: FnOnce(&C, &[K]) -> R,
+    {
+        let mut applied = Applied {
+            overlay: self,
+            cluster,
+            keys: Vec::new(),
+            edges: Vec::new(),
+        };
+        applied.apply()?;
+        Ok(experiment(applied.cluster, &applied.keys))
+    }
+}
+
+/// Overlay currently applied on a Cluster, reverted when dropped.
+struct Applied<'a, K: PartialEq + Clone, N: Node<K>, C: Cluster<K, N>> {
+    overlay: &'a mut ExperimentOverlay<K, N>,
+    cluster: &'a mut C,
+    keys: Vec<K>,
+    edges: Vec<(K, K)>,
+}
+
+impl<K: PartialEq + Clone, N: Node<K>, C: Cluster<K, N>> Applied<'_, K, N, C> {
+    /// Add the tentative nodes then the tentative edges that are not already in the Cluster.
+    fn apply(&mut self) -> Result<()> {
+        for node in self.overlay.nodes.drain(..) {
+            self.keys.push(self.cluster.add(node));
+        }
+        for (src, dst) in &self.overlay.edges {
+            let src = resolve(src, &self.keys)?;
+            let dst = resolve(dst, &self.keys)?;
+            if self
+                .cluster
+                .get_adj(&src)
+                .is_some_and(|adj| adj.contains(&dst))
+            {
+                continue;
+            }
+            self.cluster.add_edge(src.clone(), dst.clone())?;
+            self.edges.push((src, dst));
+        }
+        Ok(())
+    }
+}
+
+impl<K: PartialEq + Clone, N: Node<K>, C: Cluster<K, N>> Drop for Applied<'_, K, N, C> {
+    fn drop(&mut self) {
+        for (src, dst) in self.edges.drain(..).rev() {
+            let _ = self.cluster.remove_edge(&src, &dst);
+        }
+        for key in &self.keys {
+            if let Some(node) = self.cluster.remove(key) {
+                self.overlay.nodes.push(node);
+            }
+        }
+    }
+}
+
+/// Get the key of the Cluster designed by an OverlayKey.
+fn resolve<K: Clone>(key: &OverlayKey<K>, tentative: &[K]) -> Result<K> {
+    match key {
+        OverlayKey::Base(key) => Ok(key.clone()),
+        OverlayKey::Tentative(index) => tentative.get(*index).cloned().ok_or(
+            ClusterError::detailled("The tentative node does not exists in the overlay.").into(),
+        ),
+    }
+}
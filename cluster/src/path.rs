@@ -0,0 +1,272 @@
+use std::ops::Index;
+
+use crate::{Cluster, ClusterError, Node, Result, Weight, WeightedCluster, WeightedNode};
+
+/// Sequence of keys where each key is linked to the next one by an edge.
+/// Returned by the path-finding algorithms, the first key being the start of the path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Path<K> {
+    keys: Vec<K>,
+}
+
+impl<K> Path<K> {
+    /// Create a path going through the given keys, in order.
+    /// # Parameter
+    /// - keys - The keys of the nodes of the path.
+    /// # Return
+    /// The newly created Path.
+    pub fn new(keys: Vec<K>) -> Path<K> {
+        Path { keys }
+    }
+
+    /// Get the keys of the path.
+    /// # Return
+    /// The keys of the nodes of the path, in order.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Consume the path and returns its keys.
+    /// # Return
+    /// The keys of the nodes of the path, in order.
+    pub fn into_keys(self) -> Vec<K> {
+        self.keys
+    }
+
+    /// Get the number of nodes of the path.
+    /// # Return
+    /// The number of keys in the path, one more than its number of edges.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Check if the path has no nodes.
+    /// # Return
+    /// True if the path is empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Get the number of edges of the path.
+    /// # Return
+    /// The number of edges linking the keys of the path.
+    pub fn edge_count(&self) -> usize {
+        self.keys.len().saturating_sub(1)
+    }
+
+    /// Get the first key of the path.
+    /// # Return
+    /// An Option containing the key the path starts from, None if the path is empty.
+    pub fn first(&self) -> Option<&K> {
+        self.keys.first()
+    }
+
+    /// Get the last key of the path.
+    /// # Return
+    /// An Option containing the key the path ends at, None if the path is empty.
+    pub fn last(&self) -> Option<&K> {
+        self.keys.last()
+    }
+
+    /// Get the edges of the path.
+    /// # Return
+    /// An iterator over the (source, destination) pairs of the consecutive keys of the path.
+    pub fn edges(&self) -> impl Iterator<Item = (&K, &K)> {
+        self.keys.windows(2).map(|pair| (&pair[0], &pair[1]))
+    }
+
+    /// Reverse the path in place, as needed to follow it backward in an undirected Cluster.
+    pub fn reverse(&mut self) {
+        self.keys.reverse();
+    }
+}
+
+impl<K: PartialEq + Clone> Path<K> {
+    /// Check if the path goes through a node.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// True if the key is in the path, false otherwise.
+    pub fn contains(&self, key: &K) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Check that every key of the path is in the Cluster, and linked to the next one by an edge.
+    /// # Parameter
+    /// - cluster - The Cluster the path should belong to.
+    /// # Return
+    /// Nothing if the path is valid, an error designing the first missing node or edge otherwise.
+    pub fn validate<N: Node<K>, C: Cluster<K, N>>(&self, cluster: &C) -> Result<()>
+    where
+        K: std::fmt::Debug,
+    {
+        if let Some(missing) = self.keys.iter().find(|k| !cluster.contains_key(k)) {
+            return Err(ClusterError::detailled(&format!(
+                "The node {:?} of the path is not in the Cluster.",
+                missing
+            ))
+            .into());
+        }
+        for (src, dst) in self.edges() {
+            if !cluster.get_adj(src).is_some_and(|adj| adj.contains(dst)) {
+                return Err(ClusterError::detailled(&format!(
+                    "The edge from {:?} to {:?} of the path is not in the Cluster.",
+                    src, dst
+                ))
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Compute the total weight of the edges of the path.
+    /// # Parameter
+    /// - cluster - The weighted Cluster holding the edges of the path.
+    /// # Return
+    /// An Option containing the sum of the weights, None if an edge of the path is not in the Cluster.
+    pub fn cost<N, C>(&self, cluster: &C) -> Option<N::Weight>
+    where
+        N: WeightedNode<K>,
+        C: WeightedCluster<K, N>,
+    {
+        self.edges()
+            .try_fold(N::Weight::zero(), |total, (src, dst)| {
+                Some(total + cluster.weight(src, dst)?)
+            })
+    }
+
+    /// Append a path starting where this one ends.
+    /// # Parameter
+    /// - other - The path to append, whose first key must be the last key of this path.
+    /// # Return
+    /// The path going through both paths, an error if they do not join.
+    pub fn concat(mut self, other: Path<K>) -> Result<Path<K>> {
+        if self.keys.is_empty() {
+            return Ok(other);
+        }
+        if other
+            .keys
+            .first()
+            .is_some_and(|first| Some(first) != self.keys.last())
+        {
+            return Err(ClusterError::detailled(
+                "The appended path does not start where the path ends.",
+            )
+            .into());
+        }
+        self.keys.extend(other.keys.into_iter().skip(1));
+        Ok(self)
+    }
+}
+
+impl<K> From<Vec<K>> for Path<K> {
+    fn from(keys: Vec<K>) -> Self {
+        Path::new(keys)
+    }
+}
+
+impl<K> From<Path<K>> for Vec<K> {
+    fn from(path: Path<K>) -> Self {
+        path.keys
+    }
+}
+
+impl<K> FromIterator<K> for Path<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        Path::new(iter.into_iter().collect())
+    }
+}
+
+impl<K> Index<usize> for Path<K> {
+    type Output = K;
+
+    fn index(&self, index: usize) -> &K {
+        &self.keys[index]
+    }
+}
+
+impl<K> IntoIterator for Path<K> {
+    type Item = K;
+    type IntoIter = std::vec::IntoIter<K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.keys.into_iter()
+    }
+}
+
+impl<'a, K> IntoIterator for &'a Path<K> {
+    type Item = &'a K;
+    type IntoIter = std::slice::Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.keys.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashCluster, UndirectedHashCluster, WeightedBasicNode};
+
+    fn network() -> HashCluster<u32, WeightedBasicNode<u32, f64>> {
+        let mut cluster = HashCluster::new();
+        for key in 0..4 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        for (src, dst, weight) in [(0, 1, 1.5), (1, 2, 2.25), (2, 3, 0.5)] {
+            cluster.add_weighted_edge(src, dst, weight).unwrap();
+        }
+        cluster
+    }
+
+    #[test]
+    fn cost_sums_the_weights_of_the_edges() {
+        let cluster = network();
+        let path = Path::new(vec![0, 1, 2, 3]);
+        assert!(path.validate(&cluster).is_ok());
+        assert!((path.cost(&cluster).unwrap() - 4.25).abs() < 1e-9);
+        assert_eq!(Path::new(vec![2]).cost(&cluster), Some(0.0));
+        assert_eq!(Path::new(vec![0, 2]).cost(&cluster), None);
+    }
+
+    #[test]
+    fn validate_designs_the_missing_part() {
+        let cluster = network();
+        let reversed = Path::new(vec![1, 0]).validate(&cluster).unwrap_err();
+        assert!(reversed.to_string().contains("edge from 1 to 0"));
+        let unknown = Path::new(vec![0, 7]).validate(&cluster).unwrap_err();
+        assert!(unknown.to_string().contains("node 7"));
+    }
+
+    #[test]
+    fn undirected_paths_cost_the_same_both_ways() {
+        let mut cluster: UndirectedHashCluster<u32, WeightedBasicNode<u32, u32>> =
+            UndirectedHashCluster::new();
+        for key in 0..3 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        cluster.add_weighted_edge(0, 1, 3).unwrap();
+        cluster.add_weighted_edge(1, 2, 4).unwrap();
+        let mut path = Path::new(vec![0, 1, 2]);
+        assert_eq!(path.cost(&cluster), Some(7));
+        path.reverse();
+        assert_eq!(path.cost(&cluster), Some(7));
+    }
+
+    #[test]
+    fn concat_joins_at_the_shared_key() {
+        let joined = Path::new(vec![0, 1])
+            .concat(Path::new(vec![1, 2, 3]))
+            .unwrap();
+        assert_eq!(joined.keys(), &[0, 1, 2, 3]);
+        assert_eq!(joined.edge_count(), 3);
+        assert!(Path::new(vec![0, 1]).concat(Path::new(vec![2, 3])).is_err());
+        assert_eq!(
+            Path::new(Vec::new())
+                .concat(Path::new(vec![4]))
+                .unwrap()
+                .keys(),
+            &[4]
+        );
+    }
+}
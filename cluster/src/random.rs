@@ -0,0 +1,65 @@
+/// Source of random numbers used by the randomized algorithms of the crate.
+/// Only next_u64 has to be implemented, so any generator can be plugged in with a one line adapter.
+pub trait RandomSource {
+    /// Draw the next random 64 bits word.
+    /// # Return
+    /// A uniformly distributed u64.
+    fn next_u64(&mut self) -> u64;
+
+    /// Draw a random index below a bound.
+    /// # Parameter
+    /// - bound - The exclusive upper bound, greater than 0.
+    /// # Return
+    /// A uniformly distributed index in 0..bound.
+    fn below(&mut self, bound: usize) -> usize {
+        ((u128::from(self.next_u64()) * bound as u128) >> 64) as usize
+    }
+
+    /// Draw a random float between 0 and 1.
+    /// # Return
+    /// A uniformly distributed f64 in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl<R: RandomSource + ?Sized> RandomSource for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// Small, fast and seedable generator (SplitMix64), giving reproducible runs for a given seed.
+/// It is not cryptographically secure.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Create a new SplitMix64 from a seed.
+    /// # Parameter
+    /// - seed - The seed of the generator, the same seed giving the same sequence.
+    /// # Return
+    /// The newly created SplitMix64.
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Shuffle a slice in place with the Fisher–Yates algorithm, every permutation being equally likely.
+pub(crate) fn shuffle<T, R: RandomSource + ?Sized>(items: &mut [T], rng: &mut R) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.below(i + 1));
+    }
+}
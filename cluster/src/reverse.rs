@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cluster, HashCluster, Key, Node, Undirected};
+
+/// Capability of listing the nodes having an edge toward a given node.
+pub trait ReverseAdjacency<K> {
+    /// Get the reverse adjacency list of the node designed by its key.
+    /// # Parameter
+    /// - key - The key of the node we want the predecessors of.
+    /// # Return
+    /// An Option containing the keys of the nodes having an edge toward the node, None if there is no such node.
+    fn reverse_adj(&self, key: &K) -> Option<&[K]>;
+}
+
+impl<K: Key, N: Node<K>, S: BuildHasher> ReverseAdjacency<K> for HashCluster<K, N, Undirected, S> {
+    fn reverse_adj(&self, key: &K) -> Option<&[K]> {
+        self.get_adj(key)
+    }
+}
+
+/// Reverse adjacency of a Cluster, computed once from a snapshot of its edges.
+/// The index is not updated when the Cluster is modified afterward.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReverseIndex<K: Hash + Eq> {
+    predecessors: HashMap<K, Vec<K>>,
+}
+
+impl<K: Hash + Eq + Clone> ReverseIndex<K> {
+    /// Build the reverse adjacency of a Cluster.
+    /// # Parameter
+    /// - cluster - The Cluster whose edges are reversed.
+    /// # Return
+    /// The newly built ReverseIndex.
+    pub fn new<N: Node<K>, C: Cluster<K, N>>(cluster: &C) -> ReverseIndex<K> {
+        let mut predecessors: HashMap<K, Vec<K>> =
+            cluster.keys().map(|k| (k.clone(), Vec::new())).collect();
+        for src in cluster.keys() {
+            for dst in cluster.get_adj(src).into_iter().flatten() {
+                if let Some(preds) = predecessors.get_mut(dst) {
+                    preds.push(src.clone());
+                }
+            }
+        }
+        ReverseIndex { predecessors }
+    }
+}
+
+impl<K: Hash + Eq> ReverseAdjacency<K> for ReverseIndex<K> {
+    fn reverse_adj(&self, key: &K) -> Option<&[K]> {
+        self.predecessors.get(key).map(Vec::as_slice)
+    }
+}
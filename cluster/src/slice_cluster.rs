@@ -0,0 +1,231 @@
+use crate::{Cluster, ClusterError, Node, Result};
+
+/// Node of a SliceCluster, borrowing its value and its neighbours from the data the Cluster was built on.
+/// The neighbours are copied the first time the edges of the node are modified, the other nodes keeping them borrowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SliceNode<'a, T> {
+    key: usize,
+    value: &'a T,
+    neighbours: &'a [usize],
+    owned: Option<Vec<usize>>,
+}
+
+impl<'a, T> SliceNode<'a, T> {
+    /// Get the value of the node.
+    /// # Return
+    /// A reference to the element of the slice the node stands for.
+    pub fn value(&self) -> &'a T {
+        self.value
+    }
+
+    /// Check if the neighbours of the node are still borrowed.
+    /// # Return
+    /// True if the edges have never been modified, false if the neighbours have been copied.
+    pub fn is_borrowed(&self) -> bool {
+        self.owned.is_none()
+    }
+}
+
+impl<T> Node<usize> for SliceNode<'_, T> {
+    fn adj(&self) -> &[usize] {
+        self.owned.as_deref().unwrap_or(self.neighbours)
+    }
+
+    fn adj_mut(&mut self) -> &mut Vec<usize> {
+        let neighbours = self.neighbours;
+        self.owned.get_or_insert_with(|| neighbours.to_vec())
+    }
+
+    fn retain_adj<F: FnMut(&usize) -> bool>(&mut self, mut keep: F) {
+        if let Some(owned) = self.owned.as_mut() {
+            owned.retain(keep);
+            return;
+        }
+        // The neighbours are only copied if an edge is actually removed, keep being called once per edge.
+        let Some(first) = self.neighbours.iter().position(|k| !keep(k)) else {
+            return;
+        };
+        let mut owned = self.neighbours[..first].to_vec();
+        owned.extend(self.neighbours[first + 1..].iter().filter(|k| keep(k)));
+        self.owned = Some(owned);
+    }
+}
+
+/// Cluster over a slice of values, the keys being the indices in the slice.
+/// The values and the neighbour lists are borrowed, the neighbours of a node being copied only when its edges are modified.
+/// Nodes can be removed, but not added since the keys are the indices of the slice : try_add fails and add panics.
+#[derive(Debug, Clone)]
+pub struct SliceCluster<'a, T> {
+    nodes: Vec<Option<SliceNode<'a, T>>>,
+    len: usize,
+}
+
+impl<'a, T> SliceCluster<'a, T> {
+    /// Build a Cluster over a slice, the neighbours of each element being given by a function.
+    /// # Parameters
+    /// - values - The elements standing for the nodes.
+    /// - neighbours - The function giving the indices of the neighbours of the element at an index.
+    /// # Return
+    /// The newly built SliceCluster.
+    pub fn new<F>(values: &'a [T], neighbours: F) -> SliceCluster<'a, T>
+    where
+        F: Fn(usize) -> &'a [usize],
+    {
+        SliceCluster {
+            nodes: values
+                .iter()
+                .enumerate()
+                .map(|(key, value)| {
+                    Some(SliceNode {
+                        key,
+                        value,
+                        neighbours: neighbours(key),
+                        owned: None,
+                    })
+                })
+                .collect(),
+            len: values.len(),
+        }
+    }
+
+    /// Build a Cluster over a slice from an adjacency in compressed sparse row form.
+    /// The neighbours of the element i are targets[offsets[i]..offsets[i + 1]].
+    /// # Parameters
+    /// - values - The elements standing for the nodes.
+    /// - offsets - The start of the neighbours of each element in targets, followed by the length of targets.
+    /// - targets - The indices of the neighbours of all the elements, one after the other.
+    /// # Return
+    /// The newly built SliceCluster.
+    /// # Panics
+    /// If offsets is shorter than values plus one or points outside of targets.
+    pub fn from_csr(
+        values: &'a [T],
+        offsets: &[usize],
+        targets: &'a [usize],
+    ) -> SliceCluster<'a, T> {
+        SliceCluster::new(values, |i| &targets[offsets[i]..offsets[i + 1]])
+    }
+}
+
+impl<'a, T> Cluster<usize, SliceNode<'a, T>> for SliceCluster<'a, T> {
+    fn remove(&mut self, key: &usize) -> Option<SliceNode<'a, T>> {
+        let node = self.nodes.get_mut(*key)?.take()?;
+        self.len -= 1;
+        for other in self.nodes.iter_mut().flatten() {
+            other.retain_adj(|k| k != key);
+        }
+        Some(node)
+    }
+
+    fn keys<'clu>(&'clu self) -> impl Iterator<Item = &'clu usize>
+    where
+        usize: 'clu,
+    {
+        self.nodes.iter().flatten().map(|node| &node.key)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn contains_key(&self, key: &usize) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn get(&self, key: &usize) -> Option<&SliceNode<'a, T>> {
+        self.nodes.get(*key)?.as_ref()
+    }
+
+    fn get_mut(&mut self, key: &usize) -> Option<&mut SliceNode<'a, T>> {
+        self.nodes.get_mut(*key)?.as_mut()
+    }
+
+    fn new_key(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// # Panics
+    /// Always, a SliceCluster cannot store nodes beyond its slice. Use try_add to get an error instead.
+    fn add(&mut self, _: SliceNode<'a, T>) -> usize {
+        panic!("A node cannot be added to a SliceCluster.")
+    }
+
+    fn try_add(&mut self, _: SliceNode<'a, T>) -> Result<usize> {
+        Err(ClusterError::detailled("A node cannot be added to a SliceCluster.").into())
+    }
+
+    fn key_index(key: &usize) -> Option<usize> {
+        Some(*key)
+    }
+
+    fn add_edge(&mut self, src: usize, dst: usize) -> Result<()> {
+        if !self.contains_key(&dst) {
+            return Err(ClusterError::detailled(
+                "To add edge, both node must exists in the Cluster.",
+            )
+            .into());
+        }
+        let adj = self.get_adj_mut(&src).ok_or(ClusterError::detailled(
+            "To add edge, both node must exists in the Cluster.",
+        ))?;
+        if !adj.contains(&dst) {
+            adj.push(dst);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::reachability::transitive_reduction_in_place;
+    use crate::traversal::Bfs;
+
+    const VALUES: [&str; 4] = ["a", "b", "c", "d"];
+    const OFFSETS: [usize; 5] = [0, 2, 3, 4, 4];
+    const TARGETS: [usize; 4] = [1, 2, 2, 3];
+
+    #[test]
+    fn neighbours_are_borrowed_from_the_csr_arrays() {
+        let cluster = SliceCluster::from_csr(&VALUES, &OFFSETS, &TARGETS);
+        assert_eq!(cluster.len(), 4);
+        assert_eq!(cluster.get_adj(&0), Some(&TARGETS[0..2]));
+        assert!(std::ptr::eq(cluster.get_adj(&1).unwrap(), &TARGETS[2..3]));
+        assert_eq!(cluster.get(&3).map(SliceNode::value), Some(&"d"));
+        let order: Vec<usize> = Bfs::new(&cluster, 0).collect();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn modified_nodes_copy_their_neighbours() {
+        let mut cluster = SliceCluster::from_csr(&VALUES, &OFFSETS, &TARGETS);
+        assert_eq!(transitive_reduction_in_place(&mut cluster).unwrap(), 1);
+        assert_eq!(cluster.get_adj(&0), Some(&[1][..]));
+        assert!(!cluster.get(&0).unwrap().is_borrowed());
+        assert!(cluster.get(&1).unwrap().is_borrowed());
+        cluster.add_edge(3, 0).unwrap();
+        assert_eq!(cluster.get_adj(&3), Some(&[0][..]));
+        assert!(cluster.add_edge(3, 4).is_err());
+        assert_eq!(TARGETS, [1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn removed_nodes_leave_the_other_indices_in_place() {
+        let mut cluster = SliceCluster::from_csr(&VALUES, &OFFSETS, &TARGETS);
+        assert!(cluster.remove(&2).is_some());
+        assert!(cluster.remove(&2).is_none());
+        assert_eq!(cluster.keys().copied().collect::<Vec<_>>(), vec![0, 1, 3]);
+        assert_eq!(cluster.get_adj(&0), Some(&[1][..]));
+        assert_eq!(cluster.get_adj(&1), Some(&[][..]));
+        assert!(cluster.get(&3).unwrap().is_borrowed());
+        assert!(!cluster.contains_key(&2));
+    }
+
+    #[test]
+    fn nodes_cannot_be_added() {
+        let mut cluster = SliceCluster::from_csr(&VALUES, &OFFSETS, &TARGETS);
+        let node = cluster.remove(&3).unwrap();
+        assert!(cluster.try_add(node).is_err());
+        assert_eq!(cluster.len(), 3);
+    }
+}
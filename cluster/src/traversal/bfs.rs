@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::traversal::{KeyMap, VisitMap};
+use crate::{Cluster, Node};
+
+/// Breadth-first traversal of a Cluster, yielding the keys of the nodes reachable from a start node.
+/// The depth of each visited node is recorded in a VisitMap, by default a KeyMap built for the Cluster.
+/// Edges pointing to keys that are not in the Cluster are ignored.
+pub struct Bfs<'clu, K, N, C, M = KeyMap<K, usize>> {
+    cluster: &'clu C,
+    queue: VecDeque<(K, usize)>,
+    visited: M,
+    node: PhantomData<N>,
+}
+
+impl<'clu, K, N, C> Bfs<'clu, K, N, C>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    /// Create a breadth-first traversal starting at the given node.
+    /// # Parameters
+    /// - cluster - The Cluster to traverse.
+    /// - start - The key of the node the traversal starts from.
+    /// # Return
+    /// The newly created Bfs.
+    pub fn new(cluster: &'clu C, start: K) -> Bfs<'clu, K, N, C> {
+        Bfs::with_visit_map(cluster, start, KeyMap::for_cluster(cluster))
+    }
+}
+
+impl<'clu, K, N, C, M> Bfs<'clu, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, usize>,
+{
+    /// Create a breadth-first traversal starting at the given node and recording the depth of the visited keys in the given map.
+    /// Keys already in the map are never yielded, passing a mutable reference keeps the map for a later traversal.
+    /// # Parameters
+    /// - cluster - The Cluster to traverse.
+    /// - start - The key of the node the traversal starts from.
+    /// - visited - The map in which the depth of the visited keys is recorded.
+    /// # Return
+    /// The newly created Bfs.
+    pub fn with_visit_map(cluster: &'clu C, start: K, mut visited: M) -> Bfs<'clu, K, N, C, M> {
+        let mut queue = VecDeque::new();
+        if cluster.contains_key(&start) && !visited.contains_key(&start) {
+            visited.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+        Bfs {
+            cluster,
+            queue,
+            visited,
+            node: PhantomData,
+        }
+    }
+
+    /// Get the next node of the traversal along with its depth.
+    /// # Return
+    /// An Option containing the key of the next node and its distance in edges to the start node, None if the traversal is over.
+    pub fn next_with_depth(&mut self) -> Option<(K, usize)> {
+        let (key, depth) = self.queue.pop_front()?;
+        if let Some(adj) = self.cluster.get_adj(&key) {
+            for next in adj {
+                if self.cluster.contains_key(next) && !self.visited.contains_key(next) {
+                    self.visited.insert(next.clone(), depth + 1);
+                    self.queue.push_back((next.clone(), depth + 1));
+                }
+            }
+        }
+        Some((key, depth))
+    }
+
+    /// Turn the traversal into an iterator yielding the depth of each node along with its key.
+    /// # Return
+    /// The newly created BfsWithDepth.
+    pub fn with_depth(self) -> BfsWithDepth<'clu, K, N, C, M> {
+        BfsWithDepth { bfs: self }
+    }
+
+    /// Get the depth of a node discovered so far, which may not have been yielded yet.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the distance in edges from the start node, None if the node has not been discovered.
+    pub fn depth(&self, key: &K) -> Option<usize> {
+        self.visited.get(key).copied()
+    }
+
+    /// Get the map of the keys visited so far.
+    /// # Return
+    /// An immutable reference to the VisitMap of the traversal, giving the depth of each discovered key.
+    pub fn visited(&self) -> &M {
+        &self.visited
+    }
+}
+
+impl<K, N, C, M> Iterator for Bfs<'_, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, usize>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        self.next_with_depth().map(|(key, _)| key)
+    }
+}
+
+/// Breadth-first traversal yielding the depth of each node along with its key.
+pub struct BfsWithDepth<'clu, K, N, C, M = KeyMap<K, usize>> {
+    bfs: Bfs<'clu, K, N, C, M>,
+}
+
+impl<K, N, C, M> Iterator for BfsWithDepth<'_, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, usize>,
+{
+    type Item = (K, usize);
+
+    fn next(&mut self) -> Option<(K, usize)> {
+        self.bfs.next_with_depth()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::*;
+    use crate::traversal::DenseMap;
+    use crate::{BasicNode, HashCluster};
+
+    fn diamond() -> HashCluster<u32, BasicNode<u32>> {
+        [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 0)]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn nodes_come_by_increasing_depth() {
+        let cluster = diamond();
+        let order: Vec<(u32, usize)> = Bfs::new(&cluster, 0).with_depth().collect();
+        assert_eq!(order, vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 3)]);
+        assert_eq!(Bfs::new(&cluster, 9).next(), None);
+    }
+
+    #[test]
+    fn depths_are_recorded_in_the_visit_map() {
+        let cluster = diamond();
+        let mut bfs = Bfs::with_visit_map(&cluster, 0, BTreeMap::new());
+        assert_eq!(bfs.next(), Some(0));
+        assert_eq!(bfs.depth(&2), Some(1));
+        assert_eq!(bfs.depth(&3), None);
+        bfs.by_ref().for_each(drop);
+        let visited: Vec<(u32, usize)> = bfs.visited().clone().into_iter().collect();
+        assert_eq!(visited, vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 3)]);
+    }
+
+    #[test]
+    fn a_borrowed_visit_map_is_kept_between_traversals() {
+        let cluster = diamond();
+        let mut visited = HashMap::new();
+        let first: Vec<u32> = Bfs::with_visit_map(&cluster, 3, &mut visited).collect();
+        let second: Vec<u32> = Bfs::with_visit_map(&cluster, 5, &mut visited).collect();
+        assert_eq!(first, vec![3, 4]);
+        assert_eq!(second, vec![5, 0, 1, 2]);
+        assert_eq!(visited.len(), 6);
+    }
+
+    #[test]
+    fn every_visit_map_gives_the_same_traversal() {
+        let cluster = diamond();
+        let default: Vec<u32> = Bfs::new(&cluster, 5).collect();
+        let hashed: Vec<u32> = Bfs::with_visit_map(&cluster, 5, HashMap::new()).collect();
+        let dense: Vec<u32> = Bfs::with_visit_map(&cluster, 5, DenseMap::new()).collect();
+        assert_eq!(default, hashed);
+        assert_eq!(default, dense);
+    }
+}
@@ -0,0 +1,134 @@
+use std::marker::PhantomData;
+
+/// Trait for the integer keys usable as indices, allowing bitsets and vectors to replace hash based bookkeeping.
+pub trait DenseKey: Copy {
+    /// Get the index of the key.
+    /// # Return
+    /// The key as an index.
+    fn dense_index(self) -> usize;
+}
+
+macro_rules! impl_dense_key {
+    ($($t:ty),*) => {
+        $(
+            impl DenseKey for $t {
+                fn dense_index(self) -> usize {
+                    usize::try_from(self).expect("The key does not fit in an index.")
+                }
+            }
+        )*
+    };
+}
+
+impl_dense_key!(u8, u16, u32, u64, usize);
+
+/// Map from dense integer keys to values stored in a vector indexed by the keys, replacing a HashMap for distances or parents.
+/// The memory used grows with the highest key inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseMap<K, V> {
+    values: Vec<Option<V>>,
+    len: usize,
+    key: PhantomData<K>,
+}
+
+impl<K: DenseKey, V> DenseMap<K, V> {
+    /// Create a new empty DenseMap.
+    /// # Return
+    /// The newly created DenseMap.
+    pub fn new() -> DenseMap<K, V> {
+        DenseMap::with_capacity(0)
+    }
+
+    /// Create a new empty DenseMap able to hold the keys below capacity without growing.
+    /// # Parameter
+    /// - capacity - The number of keys the map can hold without growing.
+    /// # Return
+    /// The newly created DenseMap.
+    pub fn with_capacity(capacity: usize) -> DenseMap<K, V> {
+        let mut values = Vec::with_capacity(capacity);
+        values.resize_with(capacity, || None);
+        DenseMap {
+            values,
+            len: 0,
+            key: PhantomData,
+        }
+    }
+
+    /// Get the value stored at a key.
+    /// # Parameter
+    /// - key - The key of the value.
+    /// # Return
+    /// An Option containing a reference to the value, None if there is no value at this key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.values.get(key.dense_index())?.as_ref()
+    }
+
+    /// Get the value stored at a key.
+    /// # Parameter
+    /// - key - The key of the value.
+    /// # Return
+    /// An Option containing a mutable reference to the value, None if there is no value at this key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.values.get_mut(key.dense_index())?.as_mut()
+    }
+
+    /// Check if a value is stored at a key.
+    /// # Parameter
+    /// - key - The key to check.
+    /// # Return
+    /// True if there is a value at this key, false otherwise.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Store a value at a key.
+    /// # Parameters
+    /// - key - The key of the value.
+    /// - value - The value to store.
+    /// # Return
+    /// An Option containing the value previously stored at this key, None if there was none.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let index = key.dense_index();
+        if index >= self.values.len() {
+            self.values.resize_with(index + 1, || None);
+        }
+        let previous = self.values[index].replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    /// Remove the value stored at a key.
+    /// # Parameter
+    /// - key - The key of the value.
+    /// # Return
+    /// An Option containing the removed value, None if there was none.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let previous = self.values.get_mut(key.dense_index())?.take();
+        if previous.is_some() {
+            self.len -= 1;
+        }
+        previous
+    }
+
+    /// Get the number of values stored in the map.
+    /// # Return
+    /// The number of keys having a value.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the map stores no value.
+    /// # Return
+    /// True if the map is empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: DenseKey, V> Default for DenseMap<K, V> {
+    fn default() -> DenseMap<K, V> {
+        DenseMap::new()
+    }
+}
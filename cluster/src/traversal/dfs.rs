@@ -0,0 +1,371 @@
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::traversal::{KeyMap, VisitMap};
+use crate::{Cluster, Node};
+
+/// Event emitted by a depth-first traversal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DfsEvent<K> {
+    /// A node is reached for the first time.
+    Discover(K),
+    /// The edge (src, dst) leads to a newly discovered node.
+    TreeEdge(K, K),
+    /// The edge (src, dst) leads to a node still being explored, it closes a cycle.
+    BackEdge(K, K),
+    /// The edge (src, dst) leads to a node whose exploration is already over.
+    CrossForwardEdge(K, K),
+    /// All the nodes reachable from a node have been explored.
+    Finish(K),
+}
+
+/// Depth-first traversal of a Cluster emitting a DfsEvent for each step of the exploration.
+/// The exploration uses an explicit stack, so deep Clusters do not overflow the call stack.
+/// Each discovered key is recorded in a VisitMap, by default a KeyMap built for the Cluster, along with true once its exploration is over.
+/// Edges pointing to keys that are not in the Cluster are ignored.
+pub struct DfsEvents<'clu, K, N, C, M = KeyMap<K, bool>> {
+    cluster: &'clu C,
+    stack: Vec<(K, usize)>,
+    pending: Option<DfsEvent<K>>,
+    visited: M,
+    node: PhantomData<N>,
+}
+
+impl<'clu, K, N, C> DfsEvents<'clu, K, N, C>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    /// Create a depth-first traversal starting at the given node.
+    /// # Parameters
+    /// - cluster - The Cluster to traverse.
+    /// - start - The key of the node the traversal starts from.
+    /// # Return
+    /// The newly created DfsEvents.
+    pub fn new(cluster: &'clu C, start: K) -> DfsEvents<'clu, K, N, C> {
+        DfsEvents::with_visit_map(cluster, start, KeyMap::for_cluster(cluster))
+    }
+}
+
+impl<'clu, K, N, C, M> DfsEvents<'clu, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, bool>,
+{
+    /// Create a depth-first traversal starting at the given node and recording its progress in the given map.
+    /// Keys already in the map are never explored, passing a mutable reference keeps the map for a later traversal.
+    /// # Parameters
+    /// - cluster - The Cluster to traverse.
+    /// - start - The key of the node the traversal starts from.
+    /// - visited - The map in which the discovered keys are recorded, along with true once their exploration is over.
+    /// # Return
+    /// The newly created DfsEvents.
+    pub fn with_visit_map(cluster: &'clu C, start: K, visited: M) -> DfsEvents<'clu, K, N, C, M> {
+        let mut dfs = DfsEvents {
+            cluster,
+            stack: Vec::new(),
+            pending: None,
+            visited,
+            node: PhantomData,
+        };
+        dfs.move_to(start);
+        dfs
+    }
+
+    /// Restart the traversal from another node once the current exploration is over.
+    /// Nodes already discovered are not explored again, which allows to cover the whole Cluster.
+    /// # Parameter
+    /// - start - The key of the node the traversal restarts from.
+    /// # Return
+    /// True if the node is explored from now on, false if it was already discovered or is not in the Cluster.
+    pub fn move_to(&mut self, start: K) -> bool {
+        if !self.cluster.contains_key(&start) || self.visited.contains_key(&start) {
+            return false;
+        }
+        self.visited.insert(start.clone(), false);
+        self.stack.push((start.clone(), 0));
+        self.pending = Some(DfsEvent::Discover(start));
+        true
+    }
+
+    /// Stop exploring the edges of the node currently on top of the exploration stack.
+    /// Called right after a Discover event, the nodes below the discovered one are skipped.
+    pub fn prune(&mut self) {
+        if let Some((_, index)) = self.stack.last_mut() {
+            *index = usize::MAX;
+        }
+    }
+
+    /// Get the map of the keys discovered so far.
+    /// # Return
+    /// An immutable reference to the VisitMap of the traversal, true for the keys whose exploration is over.
+    pub fn visited(&self) -> &M {
+        &self.visited
+    }
+}
+
+impl<K, N, C, M> Iterator for DfsEvents<'_, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, bool>,
+{
+    type Item = DfsEvent<K>;
+
+    fn next(&mut self) -> Option<DfsEvent<K>> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+        loop {
+            let (key, index) = self.stack.last_mut()?;
+            let next = self
+                .cluster
+                .get_adj(key)
+                .and_then(|adj| adj.get(*index))
+                .cloned();
+            let Some(next) = next else {
+                let (key, _) = self.stack.pop()?;
+                self.visited.insert(key.clone(), true);
+                return Some(DfsEvent::Finish(key));
+            };
+            *index += 1;
+            if !self.cluster.contains_key(&next) {
+                continue;
+            }
+            let src = key.clone();
+            if !self.visited.contains_key(&next) {
+                self.visited.insert(next.clone(), false);
+                self.stack.push((next.clone(), 0));
+                self.pending = Some(DfsEvent::Discover(next.clone()));
+                return Some(DfsEvent::TreeEdge(src, next));
+            }
+            if self.visited.get(&next) == Some(&true) {
+                return Some(DfsEvent::CrossForwardEdge(src, next));
+            }
+            return Some(DfsEvent::BackEdge(src, next));
+        }
+    }
+}
+
+/// Depth-first traversal of a Cluster, yielding the keys of the nodes in pre-order.
+pub struct Dfs<'clu, K, N, C, M = KeyMap<K, bool>> {
+    events: DfsEvents<'clu, K, N, C, M>,
+}
+
+impl<'clu, K, N, C> Dfs<'clu, K, N, C>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    /// Create a depth-first traversal starting at the given node.
+    /// # Parameters
+    /// - cluster - The Cluster to traverse.
+    /// - start - The key of the node the traversal starts from.
+    /// # Return
+    /// The newly created Dfs.
+    pub fn new(cluster: &'clu C, start: K) -> Dfs<'clu, K, N, C> {
+        Dfs {
+            events: DfsEvents::new(cluster, start),
+        }
+    }
+}
+
+impl<'clu, K, N, C, M> Dfs<'clu, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, bool>,
+{
+    /// Create a depth-first traversal starting at the given node and recording its progress in the given map.
+    /// # Parameters
+    /// - cluster - The Cluster to traverse.
+    /// - start - The key of the node the traversal starts from.
+    /// - visited - The map in which the discovered keys are recorded, along with true once their exploration is over.
+    /// # Return
+    /// The newly created Dfs.
+    pub fn with_visit_map(cluster: &'clu C, start: K, visited: M) -> Dfs<'clu, K, N, C, M> {
+        Dfs {
+            events: DfsEvents::with_visit_map(cluster, start, visited),
+        }
+    }
+
+    /// Restart the traversal from another node once the current exploration is over.
+    /// # Parameter
+    /// - start - The key of the node the traversal restarts from.
+    /// # Return
+    /// True if the node is explored from now on, false if it was already discovered or is not in the Cluster.
+    pub fn move_to(&mut self, start: K) -> bool {
+        self.events.move_to(start)
+    }
+}
+
+impl<K, N, C, M> Iterator for Dfs<'_, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, bool>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        self.events.find_map(|event| match event {
+            DfsEvent::Discover(key) => Some(key),
+            _ => None,
+        })
+    }
+}
+
+/// Depth-first traversal of a Cluster, yielding the keys of the nodes in post-order.
+pub struct DfsPostOrder<'clu, K, N, C, M = KeyMap<K, bool>> {
+    events: DfsEvents<'clu, K, N, C, M>,
+}
+
+impl<'clu, K, N, C> DfsPostOrder<'clu, K, N, C>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+{
+    /// Create a post-order depth-first traversal starting at the given node.
+    /// # Parameters
+    /// - cluster - The Cluster to traverse.
+    /// - start - The key of the node the traversal starts from.
+    /// # Return
+    /// The newly created DfsPostOrder.
+    pub fn new(cluster: &'clu C, start: K) -> DfsPostOrder<'clu, K, N, C> {
+        DfsPostOrder {
+            events: DfsEvents::new(cluster, start),
+        }
+    }
+}
+
+impl<'clu, K, N, C, M> DfsPostOrder<'clu, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, bool>,
+{
+    /// Create a post-order depth-first traversal starting at the given node and recording its progress in the given map.
+    /// # Parameters
+    /// - cluster - The Cluster to traverse.
+    /// - start - The key of the node the traversal starts from.
+    /// - visited - The map in which the discovered keys are recorded, along with true once their exploration is over.
+    /// # Return
+    /// The newly created DfsPostOrder.
+    pub fn with_visit_map(
+        cluster: &'clu C,
+        start: K,
+        visited: M,
+    ) -> DfsPostOrder<'clu, K, N, C, M> {
+        DfsPostOrder {
+            events: DfsEvents::with_visit_map(cluster, start, visited),
+        }
+    }
+
+    /// Restart the traversal from another node once the current exploration is over.
+    /// # Parameter
+    /// - start - The key of the node the traversal restarts from.
+    /// # Return
+    /// True if the node is explored from now on, false if it was already discovered or is not in the Cluster.
+    pub fn move_to(&mut self, start: K) -> bool {
+        self.events.move_to(start)
+    }
+}
+
+impl<K, N, C, M> Iterator for DfsPostOrder<'_, K, N, C, M>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, bool>,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        self.events.find_map(|event| match event {
+            DfsEvent::Finish(key) => Some(key),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::traversal::DenseMap;
+    use crate::{BasicNode, HashCluster};
+
+    fn cyclic() -> HashCluster<u32, BasicNode<u32>> {
+        [(0, 1), (1, 2), (2, 0), (0, 3), (3, 2)]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn events_classify_the_edges() {
+        let cluster = cyclic();
+        let events: Vec<DfsEvent<u32>> = DfsEvents::new(&cluster, 0).collect();
+        assert_eq!(
+            events,
+            vec![
+                DfsEvent::Discover(0),
+                DfsEvent::TreeEdge(0, 1),
+                DfsEvent::Discover(1),
+                DfsEvent::TreeEdge(1, 2),
+                DfsEvent::Discover(2),
+                DfsEvent::BackEdge(2, 0),
+                DfsEvent::Finish(2),
+                DfsEvent::Finish(1),
+                DfsEvent::TreeEdge(0, 3),
+                DfsEvent::Discover(3),
+                DfsEvent::CrossForwardEdge(3, 2),
+                DfsEvent::Finish(3),
+                DfsEvent::Finish(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn pre_and_post_orders() {
+        let cluster = cyclic();
+        assert_eq!(Dfs::new(&cluster, 0).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(
+            DfsPostOrder::new(&cluster, 0).collect::<Vec<_>>(),
+            vec![2, 1, 3, 0]
+        );
+        assert_eq!(Dfs::new(&cluster, 7).next(), None);
+    }
+
+    #[test]
+    fn progress_is_recorded_in_the_visit_map() {
+        let cluster = cyclic();
+        let mut visited = BTreeMap::new();
+        let order: Vec<u32> = Dfs::with_visit_map(&cluster, 3, &mut visited).collect();
+        assert_eq!(order, vec![3, 2, 0, 1]);
+        assert!(visited.values().all(|finished| *finished));
+        let mut dfs = DfsPostOrder::with_visit_map(&cluster, 0, &mut visited);
+        assert_eq!(dfs.next(), None);
+        assert!(!dfs.move_to(1));
+    }
+
+    #[test]
+    fn move_to_covers_the_rest_of_the_cluster() {
+        let mut cluster = cyclic();
+        cluster.extend([(4, BasicNode::new(()))]);
+        let mut dfs = Dfs::with_visit_map(&cluster, 1, DenseMap::new());
+        let mut order: Vec<u32> = dfs.by_ref().collect();
+        assert!(dfs.move_to(4));
+        order.extend(dfs);
+        assert_eq!(order, vec![1, 2, 0, 3, 4]);
+    }
+}
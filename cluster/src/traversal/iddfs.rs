@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::{Cluster, Node, Path};
+
+/// Iterative deepening depth-first search of the shallowest node matching a goal.
+/// Only the current path is kept in memory, so the memory used grows with the depth and not with the frontier.
+/// # Parameters
+/// - cluster - The Cluster to search.
+/// - start - The key of the node the search starts from.
+/// - goal - The predicate telling if a node is the one searched.
+/// - max_depth - The maximum number of edges of the returned path.
+/// # Return
+/// An Option containing the keys of the shallowest path from start to a goal node, None if there is no such path within max_depth.
+pub fn iddfs<K, N, C, F>(cluster: &C, start: K, mut goal: F, max_depth: usize) -> Option<Path<K>>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    F: FnMut(&K) -> bool,
+{
+    if !cluster.contains_key(&start) {
+        return None;
+    }
+    if goal(&start) {
+        return Some(Path::new(vec![start]));
+    }
+    for limit in 1..=max_depth {
+        let mut path = vec![start.clone()];
+        let mut on_path = HashSet::from([start.clone()]);
+        let mut indexes = vec![0];
+        let mut cut_off = false;
+        while let (Some(key), Some(index)) = (path.last(), indexes.last_mut()) {
+            let next = cluster
+                .get_adj(key)
+                .and_then(|adj| adj.get(*index))
+                .cloned();
+            let Some(next) = next else {
+                on_path.remove(key);
+                path.pop();
+                indexes.pop();
+                continue;
+            };
+            *index += 1;
+            if !cluster.contains_key(&next) || on_path.contains(&next) {
+                continue;
+            }
+            if path.len() == limit {
+                if goal(&next) {
+                    path.push(next);
+                    return Some(Path::new(path));
+                }
+                cut_off = true;
+                continue;
+            }
+            on_path.insert(next.clone());
+            path.push(next);
+            indexes.push(0);
+        }
+        if !cut_off {
+            return None;
+        }
+    }
+    None
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Cluster, Node};
+
+/// Smallest number of keys a KeyMap built for a Cluster stores in its vector.
+const MIN_DENSE_LIMIT: usize = 1024;
+
+/// Map from keys to values picking its storage from the Cluster it is built for, the default VisitMap of the traversals.
+/// The keys the Cluster maps to a small index, such as the integer keys of a HashCluster, are stored in a vector indexed by them,
+/// the others in a HashMap, so integer keyed Clusters are traversed without hashing while any other key still works.
+/// The vector never grows past twice the number of nodes of the Cluster or 1024 values, a key with a larger index going to the HashMap.
+#[derive(Debug, Clone)]
+pub struct KeyMap<K, V> {
+    index: fn(&K) -> Option<usize>,
+    limit: usize,
+    dense: Vec<Option<V>>,
+    sparse: HashMap<K, V>,
+    len: usize,
+}
+
+impl<K: Hash + Eq, V> KeyMap<K, V> {
+    /// Create a new empty KeyMap storing all its keys in a HashMap.
+    /// # Return
+    /// The newly created KeyMap.
+    pub fn new() -> KeyMap<K, V> {
+        KeyMap {
+            index: |_| None,
+            limit: 0,
+            dense: Vec::new(),
+            sparse: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Create a new empty KeyMap storing the keys in a vector whenever the Cluster gives them an index, see Cluster::key_index.
+    /// # Parameter
+    /// - cluster - The Cluster whose keys are going to be stored.
+    /// # Return
+    /// The newly created KeyMap.
+    pub fn for_cluster<N, C>(cluster: &C) -> KeyMap<K, V>
+    where
+        K: Clone,
+        N: Node<K>,
+        C: Cluster<K, N>,
+    {
+        KeyMap {
+            index: C::key_index,
+            limit: (2 * cluster.len()).max(MIN_DENSE_LIMIT),
+            ..KeyMap::new()
+        }
+    }
+
+    fn slot(&self, key: &K) -> Option<usize> {
+        (self.index)(key).filter(|index| *index < self.limit)
+    }
+
+    /// Get the value stored at a key.
+    /// # Parameter
+    /// - key - The key of the value.
+    /// # Return
+    /// An Option containing a reference to the value, None if there is no value at this key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.slot(key) {
+            Some(index) => self.dense.get(index)?.as_ref(),
+            None => self.sparse.get(key),
+        }
+    }
+
+    /// Get the value stored at a key.
+    /// # Parameter
+    /// - key - The key of the value.
+    /// # Return
+    /// An Option containing a mutable reference to the value, None if there is no value at this key.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.slot(key) {
+            Some(index) => self.dense.get_mut(index)?.as_mut(),
+            None => self.sparse.get_mut(key),
+        }
+    }
+
+    /// Check if a value is stored at a key.
+    /// # Parameter
+    /// - key - The key to check.
+    /// # Return
+    /// True if there is a value at this key, false otherwise.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Store a value at a key.
+    /// # Parameters
+    /// - key - The key of the value.
+    /// - value - The value to store.
+    /// # Return
+    /// An Option containing the value previously stored at this key, None if there was none.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let previous = match self.slot(&key) {
+            Some(index) => {
+                if index >= self.dense.len() {
+                    self.dense.resize_with(index + 1, || None);
+                }
+                self.dense[index].replace(value)
+            }
+            None => self.sparse.insert(key, value),
+        };
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    /// Get the number of values stored in the map.
+    /// # Return
+    /// The number of keys having a value.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the map stores no value.
+    /// # Return
+    /// True if the map is empty, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: Hash + Eq, V> Default for KeyMap<K, V> {
+    fn default() -> KeyMap<K, V> {
+        KeyMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicNode, HashCluster};
+
+    #[test]
+    fn integer_keys_of_a_hash_cluster_are_stored_in_the_vector() {
+        let cluster: HashCluster<i64, BasicNode<i64>> = [(0, 1), (1, 2)].into_iter().collect();
+        let mut map = KeyMap::for_cluster(&cluster);
+        map.insert(2, "two");
+        map.insert(-1, "negative");
+        map.insert(1 << 40, "far");
+        assert_eq!(map.dense.len(), 3);
+        assert_eq!(map.sparse.len(), 2);
+        assert_eq!(map.insert(2, "again"), Some("two"));
+        assert_eq!(map.get(&2), Some(&"again"));
+        assert_eq!(map.get(&-1), Some(&"negative"));
+        assert_eq!(map.get(&(1 << 40)), Some(&"far"));
+        assert!(!map.contains_key(&0));
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn string_keys_are_hashed() {
+        let cluster: HashCluster<String, BasicNode<String>> =
+            [(String::from("a"), String::from("b"))]
+                .into_iter()
+                .collect();
+        let mut map = KeyMap::for_cluster(&cluster);
+        map.insert(String::from("0"), 0);
+        assert!(map.dense.is_empty());
+        assert_eq!(map.get(&String::from("0")), Some(&0));
+    }
+}
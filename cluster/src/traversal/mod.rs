@@ -0,0 +1,21 @@
+//! Iterators walking through the nodes of a Cluster.
+
+mod bfs;
+mod dense_map;
+mod dfs;
+mod iddfs;
+mod key_map;
+mod visit;
+mod visit_map;
+#[cfg(feature = "rand")]
+mod walk;
+
+pub use bfs::{Bfs, BfsWithDepth};
+pub use dense_map::{DenseKey, DenseMap};
+pub use dfs::{Dfs, DfsEvent, DfsEvents, DfsPostOrder};
+pub use iddfs::iddfs;
+pub use key_map::KeyMap;
+pub use visit::{bfs_visit, bfs_visit_with, dfs_visit, dfs_visit_with, Control};
+pub use visit_map::VisitMap;
+#[cfg(feature = "rand")]
+pub use walk::{random_walk, RandomWalk};
@@ -0,0 +1,138 @@
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use crate::traversal::{DfsEvent, DfsEvents, KeyMap, VisitMap};
+use crate::{Cluster, Node};
+
+/// Decision returned by a visitor callback to drive the traversal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control<T> {
+    /// Keep on traversing normally.
+    Continue,
+    /// Do not explore further from the current node, the rest of the traversal goes on.
+    Prune,
+    /// Stop the traversal right away and return the given value.
+    Break(T),
+}
+
+impl<T> Control<T> {
+    /// Get the value carried by a Break.
+    /// # Return
+    /// An Option containing the value if the Control is a Break, None otherwise.
+    pub fn break_value(self) -> Option<T> {
+        match self {
+            Control::Break(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Breadth-first traversal calling a visitor on each discovered node.
+/// # Parameters
+/// - cluster - The Cluster to traverse.
+/// - start - The key of the node the traversal starts from.
+/// - visitor - The callback receiving each node key and its depth, its Control drives the traversal.
+/// # Return
+/// An Option containing the value of the Break returned by the visitor, None if the traversal went to its end.
+pub fn bfs_visit<K, N, C, T, F>(cluster: &C, start: K, visitor: F) -> Option<T>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    F: FnMut(&K, usize) -> Control<T>,
+{
+    bfs_visit_with(cluster, start, KeyMap::for_cluster(cluster), visitor)
+}
+
+/// Breadth-first traversal calling a visitor on each discovered node and recording the depth of the visited keys in the given map.
+/// Keys already in the map are never visited, passing a mutable reference keeps the map for a later traversal.
+/// # Parameters
+/// - cluster - The Cluster to traverse.
+/// - start - The key of the node the traversal starts from.
+/// - visited - The map in which the depth of the visited keys is recorded.
+/// - visitor - The callback receiving each node key and its depth, its Control drives the traversal.
+/// # Return
+/// An Option containing the value of the Break returned by the visitor, None if the traversal went to its end.
+pub fn bfs_visit_with<K, N, C, M, T, F>(
+    cluster: &C,
+    start: K,
+    mut visited: M,
+    mut visitor: F,
+) -> Option<T>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, usize>,
+    F: FnMut(&K, usize) -> Control<T>,
+{
+    if !cluster.contains_key(&start) || visited.contains_key(&start) {
+        return None;
+    }
+    visited.insert(start.clone(), 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((key, depth)) = queue.pop_front() {
+        match visitor(&key, depth) {
+            Control::Break(value) => return Some(value),
+            Control::Prune => continue,
+            Control::Continue => {}
+        }
+        for next in cluster.get_adj(&key).into_iter().flatten() {
+            if cluster.contains_key(next) && !visited.contains_key(next) {
+                visited.insert(next.clone(), depth + 1);
+                queue.push_back((next.clone(), depth + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Depth-first traversal calling a visitor on each DfsEvent.
+/// Returning Prune on a Discover event skips the nodes below the discovered one, it acts as Continue on other events.
+/// # Parameters
+/// - cluster - The Cluster to traverse.
+/// - start - The key of the node the traversal starts from.
+/// - visitor - The callback receiving each event, its Control drives the traversal.
+/// # Return
+/// An Option containing the value of the Break returned by the visitor, None if the traversal went to its end.
+pub fn dfs_visit<K, N, C, T, F>(cluster: &C, start: K, visitor: F) -> Option<T>
+where
+    K: Hash + Eq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    F: FnMut(&DfsEvent<K>) -> Control<T>,
+{
+    dfs_visit_with(cluster, start, KeyMap::for_cluster(cluster), visitor)
+}
+
+/// Depth-first traversal calling a visitor on each DfsEvent and recording its progress in the given map.
+/// # Parameters
+/// - cluster - The Cluster to traverse.
+/// - start - The key of the node the traversal starts from.
+/// - visited - The map in which the discovered keys are recorded, along with true once their exploration is over.
+/// - visitor - The callback receiving each event, its Control drives the traversal.
+/// # Return
+/// An Option containing the value of the Break returned by the visitor, None if the traversal went to its end.
+pub fn dfs_visit_with<K, N, C, M, T, F>(
+    cluster: &C,
+    start: K,
+    visited: M,
+    mut visitor: F,
+) -> Option<T>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    M: VisitMap<K, bool>,
+    F: FnMut(&DfsEvent<K>) -> Control<T>,
+{
+    let mut events = DfsEvents::with_visit_map(cluster, start, visited);
+    while let Some(event) = events.next() {
+        match visitor(&event) {
+            Control::Break(value) => return Some(value),
+            Control::Prune if matches!(event, DfsEvent::Discover(_)) => events.prune(),
+            _ => {}
+        }
+    }
+    None
+}
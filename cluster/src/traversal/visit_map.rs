@@ -0,0 +1,103 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+use crate::traversal::{DenseKey, DenseMap, KeyMap};
+
+/// Map from the keys met by an algorithm to its bookkeeping values, such as distances or parents.
+/// Implemented for HashMap, BTreeMap, DenseMap and KeyMap, custom key types can supply their own storage.
+/// A traversal only recording the visited keys stores () or a flag as value.
+pub trait VisitMap<K, V> {
+    /// Get the value recorded for a key.
+    /// # Parameter
+    /// - key - The key of the value.
+    /// # Return
+    /// An Option containing a reference to the value, None if no value is recorded for this key.
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Record the value of a key.
+    /// # Parameters
+    /// - key - The key of the value.
+    /// - value - The value to record.
+    /// # Return
+    /// An Option containing the value previously recorded for this key, None if there was none.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+
+    /// Check if a value is recorded for a key.
+    /// # Parameter
+    /// - key - The key to check.
+    /// # Return
+    /// True if there is a value for this key, false otherwise.
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> VisitMap<K, V> for HashMap<K, V, S> {
+    fn get(&self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        HashMap::insert(self, key, value)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        HashMap::contains_key(self, key)
+    }
+}
+
+impl<K: Ord, V> VisitMap<K, V> for BTreeMap<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        BTreeMap::contains_key(self, key)
+    }
+}
+
+impl<K: DenseKey, V> VisitMap<K, V> for DenseMap<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        DenseMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        DenseMap::insert(self, key, value)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        DenseMap::contains_key(self, key)
+    }
+}
+
+impl<K: Hash + Eq, V> VisitMap<K, V> for KeyMap<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        KeyMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        KeyMap::insert(self, key, value)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        KeyMap::contains_key(self, key)
+    }
+}
+
+impl<K, V, M: VisitMap<K, V> + ?Sized> VisitMap<K, V> for &mut M {
+    fn get(&self, key: &K) -> Option<&V> {
+        (**self).get(key)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        (**self).insert(key, value)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        (**self).contains_key(key)
+    }
+}
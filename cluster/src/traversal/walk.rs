@@ -0,0 +1,90 @@
+use std::marker::PhantomData;
+
+use crate::{Cluster, Node, RandomSource};
+
+/// Random walk through a Cluster, each step following an edge of the current node chosen uniformly.
+/// Edges pointing to keys that are not in the Cluster are ignored, the walk stopping at a node without any other edge.
+pub struct RandomWalk<'clu, K, N, C, R> {
+    cluster: &'clu C,
+    next: Option<K>,
+    steps: usize,
+    rng: R,
+    node: PhantomData<N>,
+}
+
+impl<'clu, K, N, C, R> RandomWalk<'clu, K, N, C, R>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: RandomSource,
+{
+    /// Create a random walk starting at the given node.
+    /// # Parameters
+    /// - cluster - The Cluster to walk through.
+    /// - start - The key of the node the walk starts from.
+    /// - steps - The maximum number of edges followed.
+    /// - rng - The source of randomness, a mutable reference to a generator being one too.
+    /// # Return
+    /// The newly created RandomWalk.
+    pub fn new(cluster: &'clu C, start: K, steps: usize, rng: R) -> RandomWalk<'clu, K, N, C, R> {
+        RandomWalk {
+            cluster,
+            next: cluster.contains_key(&start).then_some(start),
+            steps,
+            rng,
+            node: PhantomData,
+        }
+    }
+}
+
+impl<K, N, C, R> Iterator for RandomWalk<'_, K, N, C, R>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: RandomSource,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        let current = self.next.take()?;
+        if self.steps > 0 {
+            self.steps -= 1;
+            let neighbours: Vec<&K> = self
+                .cluster
+                .get_adj(&current)
+                .into_iter()
+                .flatten()
+                .filter(|k| self.cluster.contains_key(k))
+                .collect();
+            if !neighbours.is_empty() {
+                self.next = Some(neighbours[self.rng.below(neighbours.len())].clone());
+            }
+        }
+        Some(current)
+    }
+}
+
+/// Walk randomly through a Cluster, each step following an edge of the current node chosen uniformly.
+/// # Parameters
+/// - cluster - The Cluster to walk through.
+/// - start - The key of the node the walk starts from.
+/// - steps - The maximum number of edges followed.
+/// - rng - The source of randomness, a mutable reference to a generator being one too.
+/// # Return
+/// A lazy iterator over the keys of the nodes visited, starting with start, empty if start is not in the Cluster.
+pub fn random_walk<K, N, C, R>(
+    cluster: &C,
+    start: K,
+    steps: usize,
+    rng: R,
+) -> RandomWalk<'_, K, N, C, R>
+where
+    K: PartialEq + Clone,
+    N: Node<K>,
+    C: Cluster<K, N>,
+    R: RandomSource,
+{
+    RandomWalk::new(cluster, start, steps, rng)
+}
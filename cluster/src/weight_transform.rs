@@ -0,0 +1,329 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::{HashCluster, Node, WeightedBasicNode, WeightedCluster, WeightedNode};
+
+/// Step of a WeightTransform.
+#[derive(Clone)]
+enum Step {
+    Log,
+    MinMax,
+    Invert,
+    Scale(f64),
+    Offset(f64),
+    Map(Arc<dyn Fn(f64) -> f64 + Send + Sync>),
+}
+
+impl Debug for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Log => write!(f, "Log"),
+            Step::MinMax => write!(f, "MinMax"),
+            Step::Invert => write!(f, "Invert"),
+            Step::Scale(factor) => write!(f, "Scale({})", factor),
+            Step::Offset(offset) => write!(f, "Offset({})", offset),
+            Step::Map(_) => write!(f, "Map"),
+        }
+    }
+}
+
+/// Pipeline of transformations applied to the weights of the edges of a Cluster, in the order they were added.
+/// The steps depending on the whole set of weights, such as min_max, see the weights as transformed by the previous steps.
+/// For instance, WeightTransform::new().min_max().offset(0.01).invert() turns similarity scores of any range into distances.
+#[derive(Debug, Clone, Default)]
+pub struct WeightTransform {
+    steps: Vec<Step>,
+}
+
+impl WeightTransform {
+    /// Create a new WeightTransform leaving the weights unchanged.
+    /// # Return
+    /// The newly created WeightTransform.
+    pub fn new() -> WeightTransform {
+        WeightTransform { steps: Vec::new() }
+    }
+
+    /// Take the natural logarithm of 1 + w, so that a zero weight stays zero.
+    /// # Return
+    /// The WeightTransform with the step added.
+    pub fn log(mut self) -> WeightTransform {
+        self.steps.push(Step::Log);
+        self
+    }
+
+    /// Map the weights linearly onto [0, 1], the smallest weight becoming 0 and the largest 1.
+    /// When all the weights are equal, they all become 0.
+    /// # Return
+    /// The WeightTransform with the step added.
+    pub fn min_max(mut self) -> WeightTransform {
+        self.steps.push(Step::MinMax);
+        self
+    }
+
+    /// Replace each weight w by 1 / w, turning similarities into distances.
+    /// # Return
+    /// The WeightTransform with the step added.
+    pub fn invert(mut self) -> WeightTransform {
+        self.steps.push(Step::Invert);
+        self
+    }
+
+    /// Multiply the weights by a factor.
+    /// # Parameter
+    /// - factor - The factor of the weights.
+    /// # Return
+    /// The WeightTransform with the step added.
+    pub fn scale(mut self, factor: f64) -> WeightTransform {
+        self.steps.push(Step::Scale(factor));
+        self
+    }
+
+    /// Add a constant to the weights.
+    /// # Parameter
+    /// - offset - The constant added to the weights.
+    /// # Return
+    /// The WeightTransform with the step added.
+    pub fn offset(mut self, offset: f64) -> WeightTransform {
+        self.steps.push(Step::Offset(offset));
+        self
+    }
+
+    /// Apply any function to the weights.
+    /// # Parameter
+    /// - function - The function mapping a weight to the new one.
+    /// # Return
+    /// The WeightTransform with the step added.
+    pub fn map<F>(mut self, function: F) -> WeightTransform
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        self.steps.push(Step::Map(Arc::new(function)));
+        self
+    }
+
+    /// Transform the weights of all the edges of a Cluster in place.
+    /// Edges toward keys not in the Cluster are left untouched.
+    /// # Parameter
+    /// - cluster - The Cluster whose weights are transformed.
+    pub fn apply<K, N, C>(&self, cluster: &mut C)
+    where
+        K: Hash + Eq + Clone,
+        N: WeightedNode<K, Weight = f64>,
+        C: WeightedCluster<K, N>,
+    {
+        let resolved = self.resolve(edge_weights(cluster));
+        let keys: Vec<K> = cluster.keys().cloned().collect();
+        for key in &keys {
+            let adj: Vec<(K, f64)> = cluster.weighted_adj(key).into_iter().flatten().collect();
+            for (dst, weight) in adj {
+                if !cluster.contains_key(&dst) {
+                    continue;
+                }
+                if let Some(node) = cluster.get_mut(key) {
+                    node.set_weight(dst, evaluate(&resolved, weight));
+                }
+            }
+        }
+    }
+
+    /// Create a read-only view of the transformed weights of a Cluster, the Cluster itself being left unchanged.
+    /// The steps depending on the whole set of weights are resolved once, so the view does not follow later changes of the Cluster.
+    /// # Parameter
+    /// - cluster - The Cluster whose weights are transformed.
+    /// # Return
+    /// The WeightView of the Cluster.
+    pub fn view<'a, K, N, C>(&self, cluster: &'a C) -> WeightView<'a, C>
+    where
+        K: Hash + Eq + Clone,
+        N: WeightedNode<K>,
+        N::Weight: Into<f64>,
+        C: WeightedCluster<K, N>,
+    {
+        let weights = edge_weights(cluster).into_iter().map(Into::into).collect();
+        WeightView {
+            cluster,
+            resolved: self.resolve(weights),
+        }
+    }
+
+    /// Turn the steps into functions of a single weight, computing the bounds needed by min_max.
+    fn resolve(&self, mut weights: Vec<f64>) -> Vec<Resolved> {
+        let mut resolved = Vec::with_capacity(self.steps.len());
+        for step in &self.steps {
+            let function = match step {
+                Step::Log => Resolved::Log,
+                Step::Invert => Resolved::Invert,
+                Step::Scale(factor) => Resolved::Affine(*factor, 0.0),
+                Step::Offset(offset) => Resolved::Affine(1.0, *offset),
+                Step::Map(function) => Resolved::Map(function.clone()),
+                Step::MinMax => {
+                    let min = weights.iter().copied().fold(f64::INFINITY, f64::min);
+                    let max = weights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                    if max > min {
+                        Resolved::Affine(1.0 / (max - min), -min / (max - min))
+                    } else {
+                        Resolved::Affine(0.0, 0.0)
+                    }
+                }
+            };
+            for weight in weights.iter_mut() {
+                *weight = function.evaluate(*weight);
+            }
+            resolved.push(function);
+        }
+        resolved
+    }
+}
+
+/// Step of a WeightTransform whose bounds have been computed.
+#[derive(Clone)]
+enum Resolved {
+    Log,
+    Invert,
+    Affine(f64, f64),
+    Map(Arc<dyn Fn(f64) -> f64 + Send + Sync>),
+}
+
+impl Resolved {
+    fn evaluate(&self, weight: f64) -> f64 {
+        match self {
+            Resolved::Log => weight.ln_1p(),
+            Resolved::Invert => 1.0 / weight,
+            Resolved::Affine(factor, offset) => weight * factor + offset,
+            Resolved::Map(function) => function(weight),
+        }
+    }
+}
+
+fn evaluate(resolved: &[Resolved], weight: f64) -> f64 {
+    resolved
+        .iter()
+        .fold(weight, |weight, step| step.evaluate(weight))
+}
+
+/// Collect the weights of the edges of a Cluster, edges toward keys not in the Cluster being skipped.
+fn edge_weights<K, N, C>(cluster: &C) -> Vec<N::Weight>
+where
+    K: PartialEq + Clone,
+    N: WeightedNode<K>,
+    C: WeightedCluster<K, N>,
+{
+    cluster
+        .keys()
+        .flat_map(|key| cluster.weighted_adj(key).into_iter().flatten())
+        .filter(|(dst, _)| cluster.contains_key(dst))
+        .map(|(_, weight)| weight)
+        .collect()
+}
+
+/// Read-only view of the weights of a Cluster as transformed by a WeightTransform.
+pub struct WeightView<'a, C> {
+    cluster: &'a C,
+    resolved: Vec<Resolved>,
+}
+
+impl<C> WeightView<'_, C> {
+    /// Get the transformed weight of the edge between src and dst.
+    /// # Parameters
+    /// - src - The key of the source node.
+    /// - dst - The key of the destination node.
+    /// # Return
+    /// An Option containing the transformed weight, None if there is no such edge.
+    pub fn weight<K, N>(&self, src: &K, dst: &K) -> Option<f64>
+    where
+        K: PartialEq + Clone,
+        N: WeightedNode<K>,
+        N::Weight: Into<f64>,
+        C: WeightedCluster<K, N>,
+    {
+        let weight = self.cluster.weight(src, dst)?;
+        Some(evaluate(&self.resolved, weight.into()))
+    }
+
+    /// Get the adjacency of a node along with the transformed weight of each edge.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the (destination, weight) pairs of the edges of the node, None if there is no such node.
+    pub fn weighted_adj<K, N>(&self, key: &K) -> Option<Vec<(K, f64)>>
+    where
+        K: PartialEq + Clone,
+        N: WeightedNode<K>,
+        N::Weight: Into<f64>,
+        C: WeightedCluster<K, N>,
+    {
+        let adj = self.cluster.weighted_adj(key)?;
+        Some(
+            adj.into_iter()
+                .map(|(dst, weight)| (dst, evaluate(&self.resolved, weight.into())))
+                .collect(),
+        )
+    }
+
+    /// Copy the Cluster with its transformed weights, so that it can be given to the weighted algorithms.
+    /// # Return
+    /// A new Cluster with the same keys and edges as the viewed one, weighted by the transformed weights.
+    pub fn to_cluster<K, N>(&self) -> HashCluster<K, WeightedBasicNode<K, f64>>
+    where
+        K: Hash + Eq + Clone,
+        N: WeightedNode<K>,
+        N::Weight: Into<f64>,
+        C: WeightedCluster<K, N>,
+    {
+        self.cluster
+            .keys()
+            .map(|key| {
+                let mut node = WeightedBasicNode::new(());
+                for (dst, weight) in self.weighted_adj(key).into_iter().flatten() {
+                    node.adj_mut().push(dst.clone());
+                    node.set_weight(dst, weight);
+                }
+                (key.clone(), node)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cluster;
+
+    fn similarities() -> HashCluster<u32, WeightedBasicNode<u32, f64>> {
+        let mut cluster = HashCluster::new();
+        for key in 0..4 {
+            cluster.insert(key, WeightedBasicNode::new(()));
+        }
+        cluster.add_weighted_edge(0, 1, 0.5).unwrap();
+        cluster.add_weighted_edge(0, 2, 0.05).unwrap();
+        cluster.add_weighted_edge(1, 3, 0.25).unwrap();
+        cluster.add_edge(2, 3).unwrap();
+        cluster
+    }
+
+    #[test]
+    fn to_cluster_keeps_the_transformed_weights() {
+        let cluster = similarities();
+        let view = WeightTransform::new().invert().view(&cluster);
+        assert_eq!(view.weight(&0, &2), Some(20.0));
+        let copy = view.to_cluster();
+        for key in cluster.keys() {
+            assert_eq!(copy.weighted_adj(key), view.weighted_adj(key));
+        }
+        assert_eq!(copy.weight(&0, &2), Some(20.0));
+    }
+
+    #[test]
+    fn apply_transforms_the_weights_in_place() {
+        let mut cluster = similarities();
+        WeightTransform::new()
+            .min_max()
+            .scale(2.0)
+            .apply(&mut cluster);
+        let expected = [((2, 3), 2.0), ((0, 2), 0.0), ((0, 1), 2.0 * 0.45 / 0.95)];
+        for ((src, dst), weight) in expected {
+            assert!((cluster.weight(&src, &dst).unwrap() - weight).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+use crate::{Cluster, ClusterError, Node, Result};
+
+/// Trait for the values that can weigh the edges of a Cluster.
+pub trait Weight: Copy + PartialOrd + Debug + Add<Output = Self> + Sub<Output = Self> {
+    /// Get the neutral weight of the addition.
+    /// # Return
+    /// The weight of an empty path.
+    fn zero() -> Self;
+
+    /// Get the unit weight.
+    /// # Return
+    /// The weight of an edge added without giving its weight.
+    fn one() -> Self;
+}
+
+macro_rules! impl_weight {
+    ($zero:literal, $one:literal, $($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    $zero
+                }
+
+                fn one() -> Self {
+                    $one
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(0, 1, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_weight!(0.0, 1.0, f32, f64);
+
+/// Trait for the Nodes able to store the weight of their edges.
+pub trait WeightedNode<K>: Node<K> {
+    /// Type of the weights of the edges.
+    type Weight: Weight;
+
+    /// Get the weight of the edge toward dst, which must be in the adjacency of the Node.
+    /// # Parameter
+    /// - dst - The key of the destination of the edge.
+    /// # Return
+    /// The weight of the edge, Weight::one() if it has been added without weight.
+    fn weight(&self, dst: &K) -> Self::Weight;
+
+    /// Set the weight of the edge toward dst.
+    /// # Parameters
+    /// - dst - The key of the destination of the edge.
+    /// - weight - The new weight of the edge.
+    fn set_weight(&mut self, dst: K, weight: Self::Weight);
+}
+
+/// Cluster whose Nodes store the weight of their edges.
+/// Implemented for every Cluster made of WeightedNodes.
+pub trait WeightedCluster<K, N>: Cluster<K, N>
+where
+    K: PartialEq + Clone,
+    N: WeightedNode<K>,
+{
+    /// Get the weight of the edge between src and dst.
+    /// # Parameters
+    /// - src - The key of the source node.
+    /// - dst - The key of the destination node.
+    /// # Return
+    /// An Option containing the weight of the edge, None if there is no such edge.
+    fn weight(&self, src: &K, dst: &K) -> Option<N::Weight> {
+        let node = self.get(src)?;
+        node.adj().contains(dst).then(|| node.weight(dst))
+    }
+
+    /// Get the adjacency of a node along with the weight of each edge.
+    /// # Parameter
+    /// - key - The key of the node.
+    /// # Return
+    /// An Option containing the (destination, weight) pairs of the edges of the node, None if there is no such node.
+    fn weighted_adj(&self, key: &K) -> Option<Vec<(K, N::Weight)>> {
+        let node = self.get(key)?;
+        Some(
+            node.adj()
+                .iter()
+                .map(|dst| (dst.clone(), node.weight(dst)))
+                .collect(),
+        )
+    }
+
+    /// Add a weighted edge between src and dst, or update its weight if it already exists.
+    /// In an undirected Cluster, the weight is set on both directions.
+    /// # Parameters
+    /// - src - The key of the source node.
+    /// - dst - The key of the destination node.
+    /// - weight - The weight of the edge.
+    /// # Return
+    /// Nothing if everithing gone well, an error otherwise.
+    fn add_weighted_edge(&mut self, src: K, dst: K, weight: N::Weight) -> Result<()> {
+        self.add_edge(src.clone(), dst.clone())?;
+        if !self.is_directed() {
+            self.get_mut(&dst)
+                .ok_or(ClusterError::detailled("<dst> node does not exists."))?
+                .set_weight(src.clone(), weight);
+        }
+        self.get_mut(&src)
+            .ok_or(ClusterError::detailled("<src> node does not exists."))?
+            .set_weight(dst, weight);
+        Ok(())
+    }
+}
+
+impl<K, N, C> WeightedCluster<K, N> for C
+where
+    K: PartialEq + Clone,
+    N: WeightedNode<K>,
+    C: Cluster<K, N>,
+{
+}
+
+/// Ready to use WeightedNode storing a value next to its adjacency list and the weights of its edges.
+/// Removing an edge through retain_adj, as the Clusters do, forgets its weight, so that an edge added again without a weight gets the default one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedBasicNode<K: Hash + Eq, W, V = ()> {
+    value: V,
+    adj: Vec<K>,
+    weights: HashMap<K, W>,
+}
+
+impl<K: Hash + Eq, W, V> WeightedBasicNode<K, W, V> {
+    /// Create a new node holding the given value and no adjacency.
+    /// # Parameter
+    /// - value - The value stored in the node.
+    /// # Return
+    /// The newly created WeightedBasicNode.
+    pub fn new(value: V) -> WeightedBasicNode<K, W, V> {
+        WeightedBasicNode {
+            value,
+            adj: Vec::new(),
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Get the value stored in the node.
+    /// # Return
+    /// An immutable reference to the value of the node.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Get the value stored in the node.
+    /// # Return
+    /// A mutable reference to the value of the node.
+    pub fn value_mut(&mut self) -> &mut V {
+        &mut self.value
+    }
+
+    /// Consume the node and returns the value it was holding.
+    /// # Return
+    /// The value of the node.
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
+
+impl<K: Hash + Eq, W, V: Default> Default for WeightedBasicNode<K, W, V> {
+    fn default() -> Self {
+        Self::new(V::default())
+    }
+}
+
+impl<K: Hash + Eq, W, V> Node<K> for WeightedBasicNode<K, W, V> {
+    fn adj(&self) -> &[K] {
+        &self.adj
+    }
+
+    fn adj_mut(&mut self) -> &mut Vec<K> {
+        &mut self.adj
+    }
+
+    fn retain_adj<F: FnMut(&K) -> bool>(&mut self, mut keep: F) {
+        let weights = &mut self.weights;
+        self.adj.retain(|dst| {
+            keep(dst) || {
+                weights.remove(dst);
+                false
+            }
+        });
+    }
+}
+
+impl<K: Hash + Eq, W: Weight, V> WeightedNode<K> for WeightedBasicNode<K, W, V> {
+    type Weight = W;
+
+    fn weight(&self, dst: &K) -> W {
+        self.weights.get(dst).copied().unwrap_or(W::one())
+    }
+
+    fn set_weight(&mut self, dst: K, weight: W) {
+        self.weights.insert(dst, weight);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashCluster, UndirectedHashCluster};
+
+    fn node() -> WeightedBasicNode<u32, f64> {
+        WeightedBasicNode::new(())
+    }
+
+    #[test]
+    fn weight_set_before_the_edge_is_pushed_is_kept() {
+        let mut node = node();
+        node.set_weight(3, 20.0);
+        node.adj_mut().push(3);
+        assert_eq!(node.weight(&3), 20.0);
+        node.adj_mut().push(4);
+        assert_eq!(node.weight(&3), 20.0);
+        assert_eq!(node.weight(&4), 1.0);
+    }
+
+    #[test]
+    fn removed_edge_added_again_gets_the_default_weight() {
+        let mut cluster: HashCluster<u32, WeightedBasicNode<u32, f64>> = HashCluster::new();
+        cluster.insert(0, node());
+        cluster.insert(1, node());
+        cluster.add_weighted_edge(0, 1, 7.0).unwrap();
+        assert_eq!(cluster.weight(&0, &1), Some(7.0));
+        cluster.remove_edge(&0, &1).unwrap();
+        assert_eq!(cluster.weight(&0, &1), None);
+        cluster.add_edge(0, 1).unwrap();
+        assert_eq!(cluster.weight(&0, &1), Some(1.0));
+    }
+
+    #[test]
+    fn removed_undirected_edge_forgets_both_weights() {
+        let mut cluster: UndirectedHashCluster<u32, WeightedBasicNode<u32, f64>> =
+            UndirectedHashCluster::new();
+        cluster.insert(0, node());
+        cluster.insert(1, node());
+        cluster.add_weighted_edge(0, 1, 4.0).unwrap();
+        assert_eq!(cluster.weight(&1, &0), Some(4.0));
+        cluster.remove_edge(&0, &1).unwrap();
+        cluster.add_edge(1, 0).unwrap();
+        assert_eq!(cluster.weight(&0, &1), Some(1.0));
+        assert_eq!(cluster.weight(&1, &0), Some(1.0));
+    }
+
+    #[test]
+    fn removed_node_forgets_the_weights_toward_it() {
+        let mut cluster: HashCluster<u32, WeightedBasicNode<u32, f64>> = HashCluster::new();
+        for key in 0..3 {
+            cluster.insert(key, node());
+        }
+        cluster.add_weighted_edge(0, 1, 9.0).unwrap();
+        cluster.add_weighted_edge(0, 2, 5.0).unwrap();
+        cluster.remove(&1);
+        cluster.remove_many([2]);
+        cluster.insert(1, node());
+        cluster.insert(2, node());
+        cluster.add_edge(0, 1).unwrap();
+        cluster.add_edge(0, 2).unwrap();
+        assert_eq!(cluster.weighted_adj(&0), Some(vec![(1, 1.0), (2, 1.0)]));
+    }
+
+    #[test]
+    fn into_value_gives_back_the_value() {
+        let mut node: WeightedBasicNode<u32, f64, &str> = WeightedBasicNode::new("a");
+        *node.value_mut() = "b";
+        assert_eq!(node.into_value(), "b");
+    }
+}